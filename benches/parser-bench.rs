@@ -1,5 +1,6 @@
 #![cfg(test)]
 
+extern crate easter;
 extern crate esprit;
 extern crate estree;
 extern crate joker;
@@ -12,7 +13,9 @@ extern crate test;
 
 extern crate unjson;
 
-use esprit::script;
+use easter::stmt::Script;
+use esprit::{script, Parser};
+use esprit::result::Result;
 use estree::IntoScript;
 use joker::track::Untrack;
 use serde_json::value::Value;
@@ -20,11 +23,48 @@ use std::ffi::OsStr;
 use std::fs::{File, read_dir};
 use std::io::Read;
 use std::path::Path;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{thread, env};
 use test::{TestDesc, TestDescAndFn, TestName, TestFn, Bencher, TDynBenchFn, test_main};
 use test::ShouldPanic::No;
 use unjson::Unjson;
 
+// Counts every allocation, to compare parsing a string in place against
+// parsing its chars.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+fn script_chars(source: &str) -> Result<Script> {
+    Parser::from_chars(source.chars()).script(false)
+}
+
 struct DynBenchFn<F> {
     run: F
 }
@@ -75,6 +115,7 @@ fn integration_tests(target: &mut Vec<TestDescAndFn>, ignore: bool) {
     if ignore {
         // Fast path for ignored tests needs only their names
         for (_, _, name) in files {
+            add_bench(target, format!("{} (chars)", name), true, |_| {});
             add_bench(target, name, true, |_| {});
         }
         return;
@@ -130,21 +171,34 @@ fn integration_tests(target: &mut Vec<TestDescAndFn>, ignore: bool) {
         .collect::<Vec<_>>();
 
     for (name, source, expected_ast) in tests {
-        add_bench(target, name, false, move |mut bench| {
-            let mut result = None;
-            bench.iter(|| {
-                result = Some(script(&source[..]))
-            });
-            match result.unwrap() {
-                Ok(mut actual_ast) => {
-                    actual_ast.untrack();
-                    assert!(actual_ast == expected_ast, "integration test got wrong result");
+        let from_chars = allocations(|| { script_chars(&source[..]).ok(); });
+        let from_str = allocations(|| { script(&source[..]).ok(); });
+        assert!(from_str < from_chars, "{}: parsing the string in place made {} allocations, parsing its chars {}",
+                name, from_str, from_chars);
+
+        let parsers: [(String, fn(&str) -> Result<Script>); 2] = [
+            (format!("{} (chars, {} allocations)", name, from_chars), script_chars),
+            (format!("{} ({} allocations)", name, from_str), script)
+        ];
+        for &(ref name, parse) in parsers.iter() {
+            let source = source.clone();
+            let expected_ast = expected_ast.clone();
+            add_bench(target, name.clone(), false, move |mut bench| {
+                let mut result = None;
+                bench.iter(|| {
+                    result = Some(parse(&source[..]))
+                });
+                match result.unwrap() {
+                    Ok(mut actual_ast) => {
+                        actual_ast.untrack();
+                        assert!(actual_ast == expected_ast, "integration test got wrong result");
+                    }
+                    Err(actual_err) => {
+                        panic!("integration test failed to parse:\n{:#?}", actual_err);
+                    }
                 }
-                Err(actual_err) => {
-                    panic!("integration test failed to parse:\n{:#?}", actual_err);
-                }
-            }
-        });
+            });
+        }
     }
 }
 
@@ -0,0 +1,48 @@
+use joker::track::*;
+
+use id::Id;
+use expr::Expr;
+use fun::Fun;
+use obj::PropKey;
+use patt::Patt;
+use stmt::{Script, StmtListItem};
+
+#[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
+pub struct Class<Id> {
+    pub location: Option<Span>,
+    pub id: Id,
+    pub extends: Option<Box<Expr>>,
+    pub body: Vec<ClassElement>
+}
+
+// The boolean flag marks `static` elements.
+#[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
+pub enum ClassElement {
+    Method(Option<Span>, bool, Fun<PropKey>),
+    Getter(Option<Span>, bool, PropKey, Script),
+    Setter(Option<Span>, bool, PropKey, Patt<Id>, Script),
+    Field(Option<Span>, bool, PropKey, Option<Expr>),
+    StaticBlock(Option<Span>, Vec<StmtListItem>)
+}
+
+impl ClassElement {
+    pub fn is_static(&self) -> bool {
+        match *self {
+            ClassElement::Method(_, is_static, _)
+          | ClassElement::Getter(_, is_static, _, _)
+          | ClassElement::Setter(_, is_static, _, _, _)
+          | ClassElement::Field(_, is_static, _, _) => is_static,
+            ClassElement::StaticBlock(_, _) => true
+        }
+    }
+
+    pub fn key(&self) -> Option<&PropKey> {
+        match *self {
+            ClassElement::Method(_, _, ref fun) => Some(&fun.id),
+            ClassElement::Getter(_, _, ref key, _)
+          | ClassElement::Setter(_, _, ref key, _, _)
+          | ClassElement::Field(_, _, ref key, _) => Some(key),
+            ClassElement::StaticBlock(_, _) => None
+        }
+    }
+}
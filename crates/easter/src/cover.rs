@@ -4,11 +4,14 @@ use joker::track::{Span, TrackingRef};
 use expr::{Expr, ExprListItem};
 use patt::{Patt, RestPatt, AssignTarget, CompoundPatt, PropPatt};
 use obj::{Prop, PropVal};
+use fun::Params;
+use id::Id;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Error {
     InvalidAssignTarget(Option<Span>),
-    InvalidPropPatt(Option<Span>)
+    InvalidPropPatt(Option<Span>),
+    InvalidBinding(Option<Span>)
 }
 
 impl Display for Error {
@@ -20,6 +23,9 @@ impl Display for Error {
             &Error::InvalidPropPatt(_) => {
                 fmt.write_str("invalid object property in assignment pattern")
             }
+            &Error::InvalidBinding(_) => {
+                fmt.write_str("invalid binding pattern")
+            }
         }
     }
 }
@@ -33,6 +39,7 @@ impl IntoAssignTarget for Expr {
         Ok(match self {
             Expr::Id(id)                     => AssignTarget::Id(id),
             Expr::Dot(location, obj, key)    => AssignTarget::Dot(location, obj, key),
+            Expr::PrivateDot(location, obj, key) => AssignTarget::PrivateDot(location, obj, key),
             Expr::Brack(location, obj, prop) => AssignTarget::Brack(location, obj, prop),
             _ => { return Err(Error::InvalidAssignTarget(*self.tracking_ref())); }
         })
@@ -46,20 +53,32 @@ pub trait IntoAssignPatt : IntoAssignTarget {
 impl IntoAssignPatt for Expr {
     fn into_assign_patt(self) -> Result<Patt<AssignTarget>, Error> {
         Ok(match self {
-            Expr::Obj(location, props) => {
+            Expr::Obj(location, mut props) => {
                 let mut prop_patts = Vec::with_capacity(props.len());
+                let mut rest = None;
+                if let Some(last) = props.pop() {
+                    // The rest of an object pattern can't be destructured further.
+                    if let Prop::Spread(rest_location, expr) = last {
+                        rest = Some(Box::new(RestPatt {
+                            location: rest_location,
+                            patt: Patt::Simple(expr.into_assign_target()?)
+                        }));
+                    } else {
+                        props.push(last);
+                    }
+                }
                 for prop in props {
                     prop_patts.push(prop.into_assign_prop()?);
                 }
-                Patt::Compound(CompoundPatt::Obj(location, prop_patts))
+                Patt::Compound(CompoundPatt::Obj(location, prop_patts, rest))
             }
             Expr::Arr(location, mut exprs) => {
                 let mut patts = Vec::with_capacity(exprs.len());
                 let mut rest = None;
                 if let Some(last) = exprs.pop() {
-                    if let Some(ExprListItem::Spread(None, expr)) = last {
+                    if let Some(ExprListItem::Spread(rest_location, expr)) = last {
                         rest = Some(Box::new(RestPatt {
-                            location: None,
+                            location: rest_location,
                             patt: expr.into_assign_patt()?
                         }));
                     } else {
@@ -68,7 +87,7 @@ impl IntoAssignPatt for Expr {
                 }
                 for expr in exprs {
                     patts.push(match expr {
-                        Some(ExprListItem::Expr(expr)) => Some(expr.into_assign_patt()?),
+                        Some(ExprListItem::Expr(expr)) => Some(into_assign_elt(expr)?),
                         Some(ExprListItem::Spread(loc, _)) => { return Err(Error::InvalidAssignTarget(loc)); }
                         None => None
                     });
@@ -80,6 +99,24 @@ impl IntoAssignPatt for Expr {
     }
 }
 
+// Array elements and property values may have a default value, which
+// the cover grammar parses as an assignment expression.
+fn into_assign_elt(expr: Expr) -> Result<Patt<AssignTarget>, Error> {
+    match expr {
+        Expr::Assign(location, patt, default) => Ok(Patt::Default(location, Box::new(patt), default)),
+        _ => expr.into_assign_patt()
+    }
+}
+
+fn into_binding_elt(expr: Expr) -> Result<Patt<Id>, Error> {
+    match expr {
+        Expr::Assign(location, patt, default) => {
+            Ok(Patt::Default(location, Box::new(patt.into_binding_patt()?), default))
+        }
+        _ => expr.into_binding_patt()
+    }
+}
+
 pub trait IntoAssignProp {
     fn into_assign_prop(self) -> Result<PropPatt<AssignTarget>, Error>;
 }
@@ -89,12 +126,178 @@ impl IntoAssignProp for Prop {
         let location = *self.tracking_ref();
         Ok(match self {
             Prop::Regular(location, key, PropVal::Init(expr)) => {
-                PropPatt::Regular(location, key, expr.into_assign_patt()?)
+                PropPatt::Regular(location, key, into_assign_elt(expr)?)
+            }
+            Prop::Shorthand(id) => {
+                PropPatt::Shorthand(id)
+            }
+            Prop::CoverInit(location, id, default) => {
+                PropPatt::ShorthandDefault(location, id, default)
+            }
+            _ => { return Err(Error::InvalidPropPatt(location)); }
+        })
+    }
+}
+
+pub trait IntoBindingPatt {
+    fn into_binding_patt(self) -> Result<Patt<Id>, Error>;
+}
+
+impl IntoBindingPatt for Expr {
+    fn into_binding_patt(self) -> Result<Patt<Id>, Error> {
+        Ok(match self {
+            Expr::Id(id) => Patt::Simple(id),
+            Expr::Obj(location, mut props) => {
+                let mut prop_patts = Vec::with_capacity(props.len());
+                let mut rest = None;
+                if let Some(last) = props.pop() {
+                    if let Prop::Spread(rest_location, expr) = last {
+                        let id = match expr {
+                            Expr::Id(id) => id,
+                            _ => { return Err(Error::InvalidBinding(*expr.tracking_ref())); }
+                        };
+                        rest = Some(Box::new(RestPatt {
+                            location: rest_location,
+                            patt: Patt::Simple(id)
+                        }));
+                    } else {
+                        props.push(last);
+                    }
+                }
+                for prop in props {
+                    prop_patts.push(prop.into_binding_prop()?);
+                }
+                Patt::Compound(CompoundPatt::Obj(location, prop_patts, rest))
+            }
+            Expr::Arr(location, mut exprs) => {
+                let mut patts = Vec::with_capacity(exprs.len());
+                let mut rest = None;
+                if let Some(last) = exprs.pop() {
+                    if let Some(ExprListItem::Spread(rest_location, expr)) = last {
+                        rest = Some(Box::new(RestPatt {
+                            location: rest_location,
+                            patt: expr.into_binding_patt()?
+                        }));
+                    } else {
+                        exprs.push(last);
+                    }
+                }
+                for expr in exprs {
+                    patts.push(match expr {
+                        Some(ExprListItem::Expr(expr)) => Some(into_binding_elt(expr)?),
+                        Some(ExprListItem::Spread(loc, _)) => { return Err(Error::InvalidBinding(loc)); }
+                        None => None
+                    });
+                }
+                Patt::Compound(CompoundPatt::Arr(location, patts, rest))
+            }
+            _ => { return Err(Error::InvalidBinding(*self.tracking_ref())); }
+        })
+    }
+}
+
+fn into_binding_rest(rest: Option<Box<RestPatt<AssignTarget>>>) -> Result<Option<Box<RestPatt<Id>>>, Error> {
+    Ok(match rest {
+        Some(rest) => {
+            let RestPatt { location, patt } = *rest;
+            Some(Box::new(RestPatt {
+                location: location,
+                patt: patt.into_binding_patt()?
+            }))
+        }
+        None => None
+    })
+}
+
+// An assignment pattern parsed as part of a cover grammar, such as a
+// default value in arrow function parameters.
+impl IntoBindingPatt for Patt<AssignTarget> {
+    fn into_binding_patt(self) -> Result<Patt<Id>, Error> {
+        Ok(match self {
+            Patt::Simple(AssignTarget::Id(id)) => Patt::Simple(id),
+            Patt::Simple(target) => { return Err(Error::InvalidBinding(*target.tracking_ref())); }
+            Patt::Compound(CompoundPatt::Arr(location, elts, rest)) => {
+                let mut patts = Vec::with_capacity(elts.len());
+                for elt in elts {
+                    patts.push(match elt {
+                        Some(elt) => Some(elt.into_binding_patt()?),
+                        None => None
+                    });
+                }
+                Patt::Compound(CompoundPatt::Arr(location, patts, into_binding_rest(rest)?))
+            }
+            Patt::Compound(CompoundPatt::Obj(location, props, rest)) => {
+                let mut prop_patts = Vec::with_capacity(props.len());
+                for prop in props {
+                    prop_patts.push(match prop {
+                        PropPatt::Regular(location, key, patt) => {
+                            PropPatt::Regular(location, key, patt.into_binding_patt()?)
+                        }
+                        PropPatt::Shorthand(id) => PropPatt::Shorthand(id),
+                        PropPatt::ShorthandDefault(location, id, default) => {
+                            PropPatt::ShorthandDefault(location, id, default)
+                        }
+                    });
+                }
+                Patt::Compound(CompoundPatt::Obj(location, prop_patts, into_binding_rest(rest)?))
+            }
+            Patt::Default(location, patt, default) => {
+                Patt::Default(location, Box::new(patt.into_binding_patt()?), default)
+            }
+        })
+    }
+}
+
+pub trait IntoBindingProp {
+    fn into_binding_prop(self) -> Result<PropPatt<Id>, Error>;
+}
+
+impl IntoBindingProp for Prop {
+    fn into_binding_prop(self) -> Result<PropPatt<Id>, Error> {
+        let location = *self.tracking_ref();
+        Ok(match self {
+            Prop::Regular(location, key, PropVal::Init(expr)) => {
+                PropPatt::Regular(location, key, into_binding_elt(expr)?)
             }
             Prop::Shorthand(id) => {
                 PropPatt::Shorthand(id)
             }
+            Prop::CoverInit(location, id, default) => {
+                PropPatt::ShorthandDefault(location, id, default)
+            }
             _ => { return Err(Error::InvalidPropPatt(location)); }
         })
     }
 }
+
+pub trait IntoParams {
+    fn into_params(self, location: Option<Span>) -> Result<Params, Error>;
+}
+
+impl IntoParams for Vec<ExprListItem> {
+    fn into_params(mut self, location: Option<Span>) -> Result<Params, Error> {
+        let mut rest = None;
+        if let Some(last) = self.pop() {
+            if let ExprListItem::Spread(rest_location, expr) = last {
+                rest = Some(RestPatt {
+                    location: rest_location,
+                    patt: expr.into_binding_patt()?
+                });
+            } else {
+                self.push(last);
+            }
+        }
+        let mut list = Vec::with_capacity(self.len());
+        for item in self {
+            match item {
+                ExprListItem::Expr(expr) => { list.push(into_binding_elt(expr)?); }
+                ExprListItem::Spread(loc, _) => { return Err(Error::InvalidBinding(loc)); }
+            }
+        }
+        Ok(Params {
+            location: location,
+            list: list,
+            rest: rest
+        })
+    }
+}
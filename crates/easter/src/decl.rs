@@ -3,36 +3,135 @@ use joker::token::StringLiteral;
 
 use id::Id;
 use fun::Fun;
+use class::Class;
 use patt::{Patt, CompoundPatt};
 use expr::Expr;
+use obj::{DotKey, PropKey};
 use punc::Semi;
 
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub enum Import {
-    // ES6: more import forms
-    ForEffect(Option<Span>, StringLiteral)
+    ForEffect(Option<Span>, StringLiteral<'static>, Vec<ImportAttribute>),
+    Named(Option<Span>, Option<Id>, Vec<ImportSpec>, StringLiteral<'static>, Vec<ImportAttribute>),
+    Namespace(Option<Span>, Option<Id>, Id, StringLiteral<'static>, Vec<ImportAttribute>)
+}
+
+impl Import {
+    // The local bindings introduced by the import, in source order.
+    pub fn bindings(&self) -> Vec<&Id> {
+        match *self {
+            Import::ForEffect(..) => Vec::new(),
+            Import::Named(_, ref default, ref specs, _, _) => {
+                default.iter().chain(specs.iter().map(|spec| &spec.local)).collect()
+            }
+            Import::Namespace(_, ref default, ref ns, _, _) => {
+                default.iter().chain(Some(ns)).collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
+pub struct ImportSpec {
+    pub location: Option<Span>,
+    pub imported: DotKey,
+    pub local: Id
+}
+
+// An entry of a `with { type: "json" }` clause. The key is either an
+// identifier name or a string literal.
+#[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
+pub struct ImportAttribute {
+    pub location: Option<Span>,
+    pub key: PropKey,
+    pub value: StringLiteral<'static>
 }
 
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub enum Export {
-    // ES6: more export forms
     Var(Option<Span>, Vec<Dtor>, Semi),
-    Decl(Decl)
+    Decl(Decl),
+    DefaultFun(Fun<Option<Id>>),
+    DefaultClass(Class<Option<Id>>),
+    DefaultExpr(Option<Span>, Expr, Semi),
+    Local(Option<Span>, Vec<ExportSpec>, Semi),
+    From(Option<Span>, Vec<ExportSpec>, StringLiteral<'static>, Vec<ImportAttribute>, Semi),
+    All(Option<Span>, StringLiteral<'static>, Vec<ImportAttribute>, Semi),
+    AllAs(Option<Span>, DotKey, StringLiteral<'static>, Vec<ImportAttribute>, Semi)
+}
+
+impl Export {
+    // The local bindings declared by the export, in source order.
+    pub fn bindings(&self) -> Vec<&Id> {
+        match *self {
+            Export::Var(_, ref dtors, _) => dtors.iter().flat_map(|dtor| dtor.bound_names()).collect(),
+            Export::Decl(ref decl) => decl.bound_names(),
+            Export::DefaultFun(ref fun) => fun.id.iter().collect(),
+            Export::DefaultClass(ref class) => class.id.iter().collect(),
+            _ => Vec::new()
+        }
+    }
+
+    // The names visible to importers of the module, in source order.
+    pub fn exported_names(&self) -> Vec<(Option<Span>, &str)> {
+        match *self {
+            Export::Var(..) | Export::Decl(_) => {
+                self.bindings().into_iter().map(|id| (id.location, id.name.as_ref())).collect()
+            }
+            Export::DefaultFun(Fun { location, .. })
+          | Export::DefaultClass(Class { location, .. })
+          | Export::DefaultExpr(location, _, _) => vec![(location, "default")],
+            Export::Local(_, ref specs, _)
+          | Export::From(_, ref specs, _, _, _) => {
+                specs.iter().map(|spec| (spec.exported.location, &spec.exported.value[..])).collect()
+            }
+            Export::All(..) => Vec::new(),
+            Export::AllAs(_, ref ns, _, _, _) => vec![(ns.location, &ns.value[..])]
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
+pub struct ExportSpec {
+    pub location: Option<Span>,
+    pub local: DotKey,
+    pub exported: DotKey
 }
 
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub enum Decl {
     Fun(Fun<Id>),
+    Class(Class<Id>),
     Let(Option<Span>, Vec<Dtor>, Semi),
     Const(Option<Span>, Vec<ConstDtor>, Semi)
 }
 
+impl Decl {
+    pub fn bound_names(&self) -> Vec<&Id> {
+        match *self {
+            Decl::Fun(ref fun) => vec![&fun.id],
+            Decl::Class(ref class) => vec![&class.id],
+            Decl::Let(_, ref dtors, _) => dtors.iter().flat_map(|dtor| dtor.bound_names()).collect(),
+            Decl::Const(_, ref dtors, _) => dtors.iter().flat_map(|dtor| dtor.patt.bound_names()).collect()
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub enum Dtor {
     Simple(Option<Span>, Id, Option<Expr>),
     Compound(Option<Span>, CompoundPatt<Id>, Expr)
 }
 
+impl Dtor {
+    pub fn bound_names(&self) -> Vec<&Id> {
+        match *self {
+            Dtor::Simple(_, ref id, _) => vec![id],
+            Dtor::Compound(_, ref patt, _) => patt.bound_names()
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub struct ConstDtor {
     pub location: Option<Span>,
@@ -59,7 +158,8 @@ impl DtorExt for Dtor {
     fn from_init(lhs: Patt<Id>, rhs: Expr) -> Dtor {
         match lhs {
             Patt::Simple(id) => Dtor::from_simple_init(id, rhs),
-            Patt::Compound(patt) => Dtor::from_compound_init(patt, rhs)
+            Patt::Compound(patt) => Dtor::from_compound_init(patt, rhs),
+            Patt::Default(..) => unreachable!("default values only occur within patterns")
         }
     }
 
@@ -72,6 +172,7 @@ impl DtorExt for Dtor {
             (Patt::Compound(patt), Some(rhs)) => {
                 Ok(Dtor::from_compound_init(patt, rhs))
             }
+            (lhs @ Patt::Default(..), _) => Err(lhs)
         }
     }
 }
@@ -1,10 +1,11 @@
 use std::fmt;
 use std::fmt::{Debug, Formatter};
 use joker::track::{TrackingRef, TrackingMut, Span, Untrack};
-use joker::token::{NumberLiteral, StringLiteral, RegExpLiteral};
+use joker::token::{NumberLiteral, BigIntLiteral, StringLiteral, RegExpLiteral, TemplateLiteral};
 
 use obj::{DotKey, Prop};
-use fun::Fun;
+use fun::{Fun, Arrow};
+use class::Class;
 use punc::{Unop, Binop, Assop, Logop};
 use id::Id;
 use patt::{Patt, AssignTarget};
@@ -15,6 +16,22 @@ pub enum ExprListItem {
     Spread(Option<Span>, Expr)
 }
 
+impl ExprListItem {
+    pub fn is_spread(&self) -> bool {
+        match *self {
+            ExprListItem::Spread(..) => true,
+            ExprListItem::Expr(_) => false
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, TrackingRef, TrackingMut, Untrack)]
+pub struct Template {
+    pub location: Option<Span>,
+    pub quasis: Vec<TemplateLiteral>,
+    pub exprs: Vec<Expr>
+}
+
 #[derive(Clone, TrackingRef, TrackingMut, Untrack)]
 pub enum Expr {
     This(Option<Span>),
@@ -22,6 +39,8 @@ pub enum Expr {
     Arr(Option<Span>, Vec<Option<ExprListItem>>),
     Obj(Option<Span>, Vec<Prop>),
     Fun(Fun<Option<Id>>),
+    Arrow(Arrow),
+    Class(Class<Option<Id>>),
     Seq(Option<Span>, Vec<Expr>),
     Unop(Option<Span>, Unop, Box<Expr>),
     Binop(Option<Span>, Binop, Box<Expr>, Box<Expr>),
@@ -33,17 +52,38 @@ pub enum Expr {
     Assign(Option<Span>, Patt<AssignTarget>, Box<Expr>),
     BinAssign(Option<Span>, Assop, AssignTarget, Box<Expr>),
     Cond(Option<Span>, Box<Expr>, Box<Expr>, Box<Expr>),
+    Yield(Option<Span>, Option<Box<Expr>>),
+    YieldDelegate(Option<Span>, Box<Expr>),
+    Await(Option<Span>, Box<Expr>),
     Call(Option<Span>, Box<Expr>, Vec<ExprListItem>),
     New(Option<Span>, Box<Expr>, Option<Vec<ExprListItem>>),
     Dot(Option<Span>, Box<Expr>, DotKey),
     Brack(Option<Span>, Box<Expr>, Box<Expr>),
+    // Private names are stored without the leading `#`. A bare one is
+    // only valid as the left operand of `in`.
+    PrivateDot(Option<Span>, Box<Expr>, DotKey),
+    PrivateName(Option<Span>, DotKey),
+    // The links of an optional chain are ordinary `Call`, `Dot` and
+    // `Brack` expressions, except those that start with `?.`, and the
+    // whole chain short-circuits as one.
+    OptionalChain(Option<Span>, Box<Expr>),
+    OptionalCall(Option<Span>, Box<Expr>, Vec<ExprListItem>),
+    OptionalDot(Option<Span>, Box<Expr>, DotKey),
+    OptionalBrack(Option<Span>, Box<Expr>, Box<Expr>),
+    OptionalPrivateDot(Option<Span>, Box<Expr>, DotKey),
     NewTarget(Option<Span>),
+    ImportMeta(Option<Span>),
+    ImportCall(Option<Span>, Box<Expr>, Option<Box<Expr>>),
+    Super(Option<Span>),
     True(Option<Span>),
     False(Option<Span>),
     Null(Option<Span>),
-    Number(Option<Span>, NumberLiteral),
+    Number(Option<Span>, NumberLiteral<'static>),
+    BigInt(Option<Span>, BigIntLiteral<'static>),
     RegExp(Option<Span>, RegExpLiteral),
-    String(Option<Span>, StringLiteral)
+    String(Option<Span>, StringLiteral<'static>),
+    Template(Template),
+    TaggedTemplate(Option<Span>, Box<Expr>, Template)
 }
 
 impl PartialEq for Expr {
@@ -57,6 +97,8 @@ impl PartialEq for Expr {
             (&Expr::Arr(_, ref elts_l),           &Expr::Arr(_, ref elts_r))           => elts_l == elts_r,
             (&Expr::Obj(_, ref props_l),          &Expr::Obj(_, ref props_r))          => props_l == props_r,
             (&Expr::Fun(ref fun_l),               &Expr::Fun(ref fun_r))               => fun_l == fun_r,
+            (&Expr::Arrow(ref arrow_l),           &Expr::Arrow(ref arrow_r))           => arrow_l == arrow_r,
+            (&Expr::Class(ref class_l),           &Expr::Class(ref class_r))           => class_l == class_r,
             (&Expr::Seq(_, ref exprs_l),          &Expr::Seq(_, ref exprs_r))          => exprs_l == exprs_r,
             (&Expr::Unop(_, ref op_l, ref arg_l), &Expr::Unop(_, ref op_r, ref arg_r)) => (op_l, arg_l) == (op_r, arg_r),
             (&Expr::Binop(_, ref op_l, ref arg1_l, ref arg2_l),
@@ -73,6 +115,9 @@ impl PartialEq for Expr {
              &Expr::BinAssign(_, ref op_r, ref patt_r, ref arg_r))                     => (op_l, patt_l, arg_l) == (op_r, patt_r, arg_r),
             (&Expr::Cond(_, ref test_l, ref cons_l, ref alt_l),
              &Expr::Cond(_, ref test_r, ref cons_r, ref alt_r))                        => (test_l, cons_l, alt_l) == (test_r, cons_r, alt_r),
+            (&Expr::Yield(_, ref arg_l),          &Expr::Yield(_, ref arg_r))          => arg_l == arg_r,
+            (&Expr::YieldDelegate(_, ref arg_l),  &Expr::YieldDelegate(_, ref arg_r))  => arg_l == arg_r,
+            (&Expr::Await(_, ref arg_l),          &Expr::Await(_, ref arg_r))          => arg_l == arg_r,
             (&Expr::Call(_, ref callee_l, ref args_l),
              &Expr::Call(_, ref callee_r, ref args_r))                                 => (callee_l, args_l) == (callee_r, args_r),
             (&Expr::New(_, ref callee_l, None),   &Expr::New(_, ref callee_r, None))   => callee_l == callee_r,
@@ -84,13 +129,33 @@ impl PartialEq for Expr {
             (&Expr::Dot(_, ref obj_l, ref key_l), &Expr::Dot(_, ref obj_r, ref key_r)) => (obj_l, key_l) == (obj_r, key_r),
             (&Expr::Brack(_, ref obj_l, ref prop_l),
              &Expr::Brack(_, ref obj_r, ref prop_r))                                   => (obj_l, prop_l) == (obj_r, prop_r),
+            (&Expr::PrivateDot(_, ref obj_l, ref key_l),
+             &Expr::PrivateDot(_, ref obj_r, ref key_r))                               => (obj_l, key_l) == (obj_r, key_r),
+            (&Expr::PrivateName(_, ref key_l), &Expr::PrivateName(_, ref key_r))       => key_l == key_r,
+            (&Expr::OptionalChain(_, ref chain_l), &Expr::OptionalChain(_, ref chain_r)) => chain_l == chain_r,
+            (&Expr::OptionalCall(_, ref callee_l, ref args_l),
+             &Expr::OptionalCall(_, ref callee_r, ref args_r))                         => (callee_l, args_l) == (callee_r, args_r),
+            (&Expr::OptionalDot(_, ref obj_l, ref key_l),
+             &Expr::OptionalDot(_, ref obj_r, ref key_r))                              => (obj_l, key_l) == (obj_r, key_r),
+            (&Expr::OptionalBrack(_, ref obj_l, ref prop_l),
+             &Expr::OptionalBrack(_, ref obj_r, ref prop_r))                           => (obj_l, prop_l) == (obj_r, prop_r),
+            (&Expr::OptionalPrivateDot(_, ref obj_l, ref key_l),
+             &Expr::OptionalPrivateDot(_, ref obj_r, ref key_r))                       => (obj_l, key_l) == (obj_r, key_r),
             (&Expr::NewTarget(_),          &Expr::NewTarget(_))                        => true,
+            (&Expr::ImportMeta(_),         &Expr::ImportMeta(_))                       => true,
+            (&Expr::ImportCall(_, ref specifier_l, ref options_l),
+             &Expr::ImportCall(_, ref specifier_r, ref options_r))                     => (specifier_l, options_l) == (specifier_r, options_r),
+            (&Expr::Super(_),              &Expr::Super(_))                            => true,
             (&Expr::True(_),               &Expr::True(_))                             => true,
             (&Expr::False(_),              &Expr::False(_))                            => true,
             (&Expr::Null(_),               &Expr::Null(_))                             => true,
             (&Expr::Number(_, ref lit_l),  &Expr::Number(_, ref lit_r))                => lit_l == lit_r,
+            (&Expr::BigInt(_, ref lit_l),  &Expr::BigInt(_, ref lit_r))                => lit_l == lit_r,
             (&Expr::RegExp(_, ref lit_l),  &Expr::RegExp(_, ref lit_r))                => lit_l == lit_r,
             (&Expr::String(_, ref lit_l),  &Expr::String(_, ref lit_r))                => lit_l == lit_r,
+            (&Expr::Template(ref tmpl_l),  &Expr::Template(ref tmpl_r))                => tmpl_l == tmpl_r,
+            (&Expr::TaggedTemplate(_, ref tag_l, ref tmpl_l),
+             &Expr::TaggedTemplate(_, ref tag_r, ref tmpl_r))                          => (tag_l, tmpl_l) == (tag_r, tmpl_r),
             _ => false
         }
     }
@@ -104,6 +169,8 @@ impl Debug for Expr {
             &Expr::Arr(_, ref elts)                          => fmt.debug_tuple("Arr").field(elts).finish(),
             &Expr::Obj(_, ref props)                         => fmt.debug_tuple("Obj").field(props).finish(),
             &Expr::Fun(ref fun)                              => fmt.debug_tuple("Fun").field(fun).finish(),
+            &Expr::Arrow(ref arrow)                          => fmt.debug_tuple("Arrow").field(arrow).finish(),
+            &Expr::Class(ref class)                          => fmt.debug_tuple("Class").field(class).finish(),
             &Expr::Seq(_, ref exprs)                         => fmt.debug_tuple("Seq").field(exprs).finish(),
             &Expr::Unop(_, ref op, ref arg)                  => fmt.debug_tuple("Unop").field(op).field(arg).finish(),
             &Expr::Binop(_, ref op, ref left, ref right)     => fmt.debug_tuple("Binop").field(op).field(left).field(right).finish(),
@@ -115,6 +182,9 @@ impl Debug for Expr {
             &Expr::Assign(_, ref left, ref right)            => fmt.debug_tuple("Assign").field(left).field(right).finish(),
             &Expr::BinAssign(_, ref op, ref left, ref right) => fmt.debug_tuple("BinAssign").field(op).field(left).field(right).finish(),
             &Expr::Cond(_, ref test, ref cons, ref alt)      => fmt.debug_tuple("Cond").field(test).field(cons).field(alt).finish(),
+            &Expr::Yield(_, ref arg)                         => fmt.debug_tuple("Yield").field(arg).finish(),
+            &Expr::YieldDelegate(_, ref arg)                 => fmt.debug_tuple("YieldDelegate").field(arg).finish(),
+            &Expr::Await(_, ref arg)                         => fmt.debug_tuple("Await").field(arg).finish(),
             &Expr::Call(_, ref callee, ref args)             => fmt.debug_tuple("Call").field(callee).field(args).finish(),
             &Expr::New(_, ref ctor, None) => {
                 let args: Vec<Expr> = vec![];
@@ -123,13 +193,26 @@ impl Debug for Expr {
             &Expr::New(_, ref ctor, Some(ref args))          => fmt.debug_tuple("New").field(ctor).field(args).finish(),
             &Expr::Dot(_, ref expr, ref key)                 => fmt.debug_tuple("Dot").field(expr).field(key).finish(),
             &Expr::Brack(_, ref expr, ref prop)              => fmt.debug_tuple("Brack").field(expr).field(prop).finish(),
+            &Expr::PrivateDot(_, ref expr, ref key)          => fmt.debug_tuple("PrivateDot").field(expr).field(key).finish(),
+            &Expr::PrivateName(_, ref key)                   => fmt.debug_tuple("PrivateName").field(key).finish(),
+            &Expr::OptionalChain(_, ref chain)               => fmt.debug_tuple("OptionalChain").field(chain).finish(),
+            &Expr::OptionalCall(_, ref callee, ref args)     => fmt.debug_tuple("OptionalCall").field(callee).field(args).finish(),
+            &Expr::OptionalDot(_, ref expr, ref key)         => fmt.debug_tuple("OptionalDot").field(expr).field(key).finish(),
+            &Expr::OptionalBrack(_, ref expr, ref prop)      => fmt.debug_tuple("OptionalBrack").field(expr).field(prop).finish(),
+            &Expr::OptionalPrivateDot(_, ref expr, ref key)  => fmt.debug_tuple("OptionalPrivateDot").field(expr).field(key).finish(),
             &Expr::NewTarget(_)                              => fmt.write_str("NewTarget"),
+            &Expr::ImportMeta(_)                             => fmt.write_str("ImportMeta"),
+            &Expr::ImportCall(_, ref specifier, ref options) => fmt.debug_tuple("ImportCall").field(specifier).field(options).finish(),
+            &Expr::Super(_)                                  => fmt.write_str("Super"),
             &Expr::True(_)                                   => fmt.write_str("True"),
             &Expr::False(_)                                  => fmt.write_str("False"),
             &Expr::Null(_)                                   => fmt.write_str("Null"),
             &Expr::Number(_, ref lit)                        => fmt.debug_tuple("Number").field(lit).finish(),
+            &Expr::BigInt(_, ref lit)                        => fmt.debug_tuple("BigInt").field(lit).finish(),
             &Expr::RegExp(_, ref lit)                        => fmt.debug_tuple("RegExp").field(lit).finish(),
-            &Expr::String(_, ref lit)                        => fmt.debug_tuple("String").field(lit).finish()
+            &Expr::String(_, ref lit)                        => fmt.debug_tuple("String").field(lit).finish(),
+            &Expr::Template(ref tmpl)                        => fmt.debug_tuple("Template").field(tmpl).finish(),
+            &Expr::TaggedTemplate(_, ref tag, ref tmpl)      => fmt.debug_tuple("TaggedTemplate").field(tag).field(tmpl).finish()
         }
     }
 }
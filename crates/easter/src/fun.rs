@@ -3,6 +3,7 @@ use joker::track::*;
 use id::Id;
 use patt::{Patt, RestPatt};
 use stmt::Script;
+use expr::Expr;
 
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub struct Params {
@@ -15,6 +16,22 @@ pub struct Params {
 pub struct Fun<Id> {
     pub location: Option<Span>,
     pub id: Id,
+    pub generator: bool,
+    pub asynchronous: bool,
     pub params: Params,
     pub body: Script
 }
+
+#[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
+pub enum ArrowBody {
+    Expr(Box<Expr>),
+    Body(Script)
+}
+
+#[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
+pub struct Arrow {
+    pub location: Option<Span>,
+    pub asynchronous: bool,
+    pub params: Params,
+    pub body: ArrowBody
+}
@@ -8,7 +8,7 @@ use patt::Patt;
 #[derive(Debug, Eq, PartialEq, Clone, TrackingRef, TrackingMut)]
 pub struct Id {
     pub location: Option<Span>,
-    pub name: Name
+    pub name: Name<'static>
 }
 
 impl Untrack for Id {
@@ -16,14 +16,14 @@ impl Untrack for Id {
 }
 
 pub trait IdExt {
-    fn new(Name, Option<Span>) -> Id;
+    fn new(Name<'static>, Option<Span>) -> Id;
     fn into_patt(self) -> Patt<Id>;
     fn into_expr(self) -> Expr;
     fn into_dtor(self) -> Dtor;
 }
 
 impl IdExt for Id {
-    fn new(name: Name, location: Option<Span>) -> Id {
+    fn new(name: Name<'static>, location: Option<Span>) -> Id {
         Id {
             location: location,
             name: name
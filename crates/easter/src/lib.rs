@@ -13,3 +13,4 @@ pub mod decl;
 pub mod patt;
 pub mod punc;
 pub mod cover;
+pub mod class;
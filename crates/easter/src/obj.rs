@@ -21,18 +21,43 @@ impl Untrack for DotKey {
 pub enum Prop {
     Regular(Option<Span>, PropKey, PropVal),
     Method(Fun<PropKey>),
-    Shorthand(Id)
+    Shorthand(Id),
+    Spread(Option<Span>, Expr),
+    // `{ x = e }` is only valid as a destructuring pattern, so the parser
+    // rejects it anywhere it isn't reinterpreted as one.
+    CoverInit(Option<Span>, Id, Expr)
 }
 
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut)]
 pub enum PropKey {
     Id(Option<Span>, String),
-    String(Option<Span>, StringLiteral),
-    Number(Option<Span>, NumberLiteral)
+    String(Option<Span>, StringLiteral<'static>),
+    Number(Option<Span>, NumberLiteral<'static>),
+    Computed(Option<Span>, Expr),
+    // Only class elements have private names, which are stored without
+    // the leading `#`.
+    Private(Option<Span>, String)
+}
+
+impl PropKey {
+    // Tests the property name, as seen by early errors such as duplicate
+    // constructors or `__proto__` properties.
+    pub fn is_name(&self, name: &str) -> bool {
+        match *self {
+            PropKey::Id(_, ref key) => key == name,
+            PropKey::String(_, ref lit) => lit.value == name,
+            PropKey::Number(_, _) => false,
+            PropKey::Computed(_, _) => false,
+            PropKey::Private(_, _) => false
+        }
+    }
 }
 
 impl Untrack for PropKey {
     fn untrack(&mut self) {
+        if let PropKey::Computed(_, ref mut expr) = *self {
+            expr.untrack();
+        }
         *self.tracking_mut() = None;
     }
 }
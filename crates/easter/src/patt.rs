@@ -13,35 +13,80 @@ pub struct RestPatt<T> {
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub enum CompoundPatt<T> {
     Arr(Option<Span>, Vec<Option<Patt<T>>>, Option<Box<RestPatt<T>>>),
-    Obj(Option<Span>, Vec<PropPatt<T>>)
+    Obj(Option<Span>, Vec<PropPatt<T>>, Option<Box<RestPatt<T>>>)
 }
 
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub enum PropPatt<T> {
     Regular(Option<Span>, PropKey, Patt<T>),
-    Shorthand(Id)
+    Shorthand(Id),
+    ShorthandDefault(Option<Span>, Id, Expr)
 }
 
+// Default values only occur nested in array elements, object
+// properties and function parameters, never at the top of a pattern.
 #[derive(Debug, PartialEq, Clone, Untrack)]
 pub enum Patt<T> {
     Simple(T),
-    Compound(CompoundPatt<T>)
+    Compound(CompoundPatt<T>),
+    Default(Option<Span>, Box<Patt<T>>, Box<Expr>)
 }
 
 impl<T> Patt<T> {
     pub fn is_simple(&self) -> bool {
         match *self {
             Patt::Simple(_)   => true,
-            Patt::Compound(_) => false
+            Patt::Compound(_) => false,
+            Patt::Default(..) => false
         }
     }
 }
 
+impl Patt<Id> {
+    pub fn bound_names(&self) -> Vec<&Id> {
+        match *self {
+            Patt::Simple(ref id) => vec![id],
+            Patt::Compound(ref patt) => patt.bound_names(),
+            Patt::Default(_, ref patt, _) => patt.bound_names()
+        }
+    }
+}
+
+impl CompoundPatt<Id> {
+    pub fn bound_names(&self) -> Vec<&Id> {
+        let mut names = Vec::new();
+        match *self {
+            CompoundPatt::Arr(_, ref elts, ref rest) => {
+                for elt in elts.iter().filter_map(|elt| elt.as_ref()) {
+                    names.extend(elt.bound_names());
+                }
+                if let Some(ref rest) = *rest {
+                    names.extend(rest.patt.bound_names());
+                }
+            }
+            CompoundPatt::Obj(_, ref props, ref rest) => {
+                for prop in props {
+                    match *prop {
+                        PropPatt::Regular(_, _, ref patt) => { names.extend(patt.bound_names()); }
+                        PropPatt::Shorthand(ref id)
+                      | PropPatt::ShorthandDefault(_, ref id, _) => { names.push(id); }
+                    }
+                }
+                if let Some(ref rest) = *rest {
+                    names.extend(rest.patt.bound_names());
+                }
+            }
+        }
+        names
+    }
+}
+
 impl<T: TrackingRef> TrackingRef for Patt<T> {
     fn tracking_ref(&self) -> &Option<Span> {
         match *self {
             Patt::Simple(ref simple) => simple.tracking_ref(),
-            Patt::Compound(ref patt) => patt.tracking_ref()
+            Patt::Compound(ref patt) => patt.tracking_ref(),
+            Patt::Default(ref location, _, _) => location
         }
     }
 }
@@ -50,7 +95,8 @@ impl<T: TrackingMut> TrackingMut for Patt<T> {
     fn tracking_mut(&mut self) -> &mut Option<Span> {
         match *self {
             Patt::Simple(ref mut simple) => simple.tracking_mut(),
-            Patt::Compound(ref mut patt) => patt.tracking_mut()
+            Patt::Compound(ref mut patt) => patt.tracking_mut(),
+            Patt::Default(ref mut location, _, _) => location
         }
     }
 }
@@ -59,6 +105,7 @@ impl<T: TrackingMut> TrackingMut for Patt<T> {
 pub enum AssignTarget {
     Id(Id),
     Dot(Option<Span>, Box<Expr>, DotKey),
+    PrivateDot(Option<Span>, Box<Expr>, DotKey),
     Brack(Option<Span>, Box<Expr>, Box<Expr>)
 }
 
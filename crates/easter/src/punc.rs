@@ -112,6 +112,7 @@ pub enum BinopTag {
     Times,
     Div,
     Mod,
+    Exp,
     BitOr,
     BitXor,
     BitAnd,
@@ -140,6 +141,7 @@ impl FromStr for BinopTag {
             "*"          => BinopTag::Times,
             "/"          => BinopTag::Div,
             "%"          => BinopTag::Mod,
+            "**"         => BinopTag::Exp,
             "|"          => BinopTag::BitOr,
             "^"          => BinopTag::BitXor,
             "&"          => BinopTag::BitAnd,
@@ -169,6 +171,7 @@ impl Precedence for BinopTag {
             BinopTag::Times      => 11,
             BinopTag::Div        => 11,
             BinopTag::Mod        => 11,
+            BinopTag::Exp        => 12,
             BinopTag::BitOr      => 4,
             BinopTag::BitXor     => 5,
             BinopTag::BitAnd     => 6,
@@ -199,6 +202,7 @@ impl Display for BinopTag {
             BinopTag::Times      => "*",
             BinopTag::Div        => "/",
             BinopTag::Mod        => "%",
+            BinopTag::Exp        => "**",
             BinopTag::BitOr      => "|",
             BinopTag::BitXor     => "^",
             BinopTag::BitAnd     => "&",
@@ -211,7 +215,8 @@ impl Display for BinopTag {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum LogopTag {
     Or,
-    And
+    And,
+    Coalesce
 }
 
 impl FromStr for LogopTag {
@@ -221,6 +226,7 @@ impl FromStr for LogopTag {
         Ok(match s {
             "||" => LogopTag::Or,
             "&&" => LogopTag::And,
+            "??" => LogopTag::Coalesce,
             _    => { return Err(()); }
         })
     }
@@ -229,8 +235,9 @@ impl FromStr for LogopTag {
 impl Precedence for LogopTag {
     fn precedence(&self) -> u32 {
         match *self {
-            LogopTag::Or  => 2,
-            LogopTag::And => 3
+            LogopTag::Coalesce => 1,
+            LogopTag::Or       => 2,
+            LogopTag::And      => 3
         }
     }
 }
@@ -240,8 +247,9 @@ pub type Logop = Op<LogopTag>;
 impl Display for LogopTag {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
         fmt.write_str(match *self {
-            LogopTag::Or  => "||",
-            LogopTag::And => "&&"
+            LogopTag::Or       => "||",
+            LogopTag::And      => "&&",
+            LogopTag::Coalesce => "??"
         })
     }
 }
@@ -251,6 +259,7 @@ pub enum AssopTag {
     PlusEq,
     MinusEq,
     TimesEq,
+    ExpEq,
     DivEq,
     ModEq,
     LShiftEq,
@@ -258,7 +267,10 @@ pub enum AssopTag {
     URShiftEq,
     BitOrEq,
     BitXorEq,
-    BitAndEq
+    BitAndEq,
+    OrEq,
+    AndEq,
+    CoalesceEq
 }
 
 impl FromStr for AssopTag {
@@ -269,6 +281,7 @@ impl FromStr for AssopTag {
             "+="   => AssopTag::PlusEq,
             "-="   => AssopTag::MinusEq,
             "*="   => AssopTag::TimesEq,
+            "**="  => AssopTag::ExpEq,
             "/="   => AssopTag::DivEq,
             "%="   => AssopTag::ModEq,
             "<<="  => AssopTag::LShiftEq,
@@ -277,6 +290,9 @@ impl FromStr for AssopTag {
             "|="   => AssopTag::BitOrEq,
             "^="   => AssopTag::BitXorEq,
             "&="   => AssopTag::BitAndEq,
+            "||="  => AssopTag::OrEq,
+            "&&="  => AssopTag::AndEq,
+            "??="  => AssopTag::CoalesceEq,
             _      => { return Err(()); }
         })
     }
@@ -291,17 +307,21 @@ pub type Assop = Op<AssopTag>;
 impl Display for AssopTag {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
         fmt.write_str(match *self {
-            AssopTag::PlusEq    => "+=",
-            AssopTag::MinusEq   => "-=",
-            AssopTag::TimesEq   => "*=",
-            AssopTag::DivEq     => "/=",
-            AssopTag::ModEq     => "%=",
-            AssopTag::LShiftEq  => "<<=",
-            AssopTag::RShiftEq  => ">>=",
-            AssopTag::URShiftEq => ">>>=",
-            AssopTag::BitOrEq   => "|=",
-            AssopTag::BitXorEq  => "^=",
-            AssopTag::BitAndEq  => "&="
+            AssopTag::PlusEq     => "+=",
+            AssopTag::MinusEq    => "-=",
+            AssopTag::TimesEq    => "*=",
+            AssopTag::ExpEq      => "**=",
+            AssopTag::DivEq      => "/=",
+            AssopTag::ModEq      => "%=",
+            AssopTag::LShiftEq   => "<<=",
+            AssopTag::RShiftEq   => ">>=",
+            AssopTag::URShiftEq  => ">>>=",
+            AssopTag::BitOrEq    => "|=",
+            AssopTag::BitXorEq   => "^=",
+            AssopTag::BitAndEq   => "&=",
+            AssopTag::OrEq       => "||=",
+            AssopTag::AndEq      => "&&=",
+            AssopTag::CoalesceEq => "??="
         })
     }
 }
@@ -312,12 +332,13 @@ pub trait ToOp {
     fn to_assop(&self) -> Option<Assop>;
 }
 
-impl ToOp for Token {
+impl<'a> ToOp for Token<'a> {
     fn to_binop(&self, allow_in: bool) -> Option<Binop> {
         Some(Op {
             location: Some(self.location),
             tag: match self.value {
                 TokenData::Star                               => BinopTag::Times,
+                TokenData::StarStar                           => BinopTag::Exp,
                 TokenData::Slash                              => BinopTag::Div,
                 TokenData::Mod                                => BinopTag::Mod,
                 TokenData::Plus                               => BinopTag::Plus,
@@ -349,6 +370,7 @@ impl ToOp for Token {
             tag: match self.value {
                 TokenData::LogicalAnd => LogopTag::And,
                 TokenData::LogicalOr  => LogopTag::Or,
+                TokenData::Coalesce   => LogopTag::Coalesce,
                 _ => { return None; }
             }
         })
@@ -361,6 +383,7 @@ impl ToOp for Token {
                 TokenData::PlusAssign    => AssopTag::PlusEq,
                 TokenData::MinusAssign   => AssopTag::MinusEq,
                 TokenData::StarAssign    => AssopTag::TimesEq,
+                TokenData::StarStarAssign => AssopTag::ExpEq,
                 TokenData::SlashAssign   => AssopTag::DivEq,
                 TokenData::ModAssign     => AssopTag::ModEq,
                 TokenData::LShiftAssign  => AssopTag::LShiftEq,
@@ -369,6 +392,9 @@ impl ToOp for Token {
                 TokenData::BitAndAssign  => AssopTag::BitAndEq,
                 TokenData::BitOrAssign   => AssopTag::BitOrEq,
                 TokenData::BitXorAssign  => AssopTag::BitXorEq,
+                TokenData::LogicalOrAssign  => AssopTag::OrEq,
+                TokenData::LogicalAndAssign => AssopTag::AndEq,
+                TokenData::CoalesceAssign   => AssopTag::CoalesceEq,
                 _ => { return None; }
             }
         })
@@ -4,6 +4,7 @@ use joker::token::StringLiteral;
 use id::Id;
 use expr::Expr;
 use decl::{Decl, Dtor, ConstDtor, Import, Export};
+use fun::Fun;
 use patt::{Patt, AssignTarget};
 use punc::Semi;
 
@@ -27,12 +28,15 @@ pub enum Stmt {
     For(Option<Span>, Option<Box<ForHead>>, Option<Expr>, Option<Expr>, Box<Stmt>),
     ForIn(Option<Span>, Box<ForInHead>, Expr, Box<Stmt>),
     ForOf(Option<Span>, Box<ForOfHead>, Expr, Box<Stmt>),
-    Debugger(Option<Span>, Semi)
+    ForAwait(Option<Span>, Box<ForOfHead>, Expr, Box<Stmt>),
+    Debugger(Option<Span>, Semi),
+    Fun(Fun<Id>) // web scripts allow `if` and label bodies to be functions
 }
 
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub struct Body<Item> {
     pub location: Option<Span>,
+    pub hashbang: Option<String>, // only ever set on a whole script or module
     pub dirs: Vec<Dir>,
     pub items: Vec<Item>
 }
@@ -48,6 +52,76 @@ pub type Script = Body<StmtListItem>;
 pub type Module = Body<ModItem>;
 
 impl Stmt {
+    // The names declared by `var` anywhere in the statement, excluding
+    // nested functions.
+    pub fn var_declared_names(&self) -> Vec<&Id> {
+        let mut names = Vec::new();
+        match *self {
+            Stmt::Var(_, ref dtors, _) => {
+                for dtor in dtors {
+                    names.extend(dtor.bound_names());
+                }
+            }
+            Stmt::Block(Block { ref items, .. }) => {
+                for item in items {
+                    names.extend(item.var_declared_names());
+                }
+            }
+            Stmt::If(_, _, ref cons, ref alt) => {
+                names.extend(cons.var_declared_names());
+                if let Some(ref alt) = *alt {
+                    names.extend(alt.var_declared_names());
+                }
+            }
+            Stmt::Label(_, _, ref body)
+          | Stmt::With(_, _, ref body)
+          | Stmt::While(_, _, ref body)
+          | Stmt::DoWhile(_, ref body, _, _) => {
+                names.extend(body.var_declared_names());
+            }
+            Stmt::Switch(_, _, ref cases) => {
+                for item in cases.iter().flat_map(|case| case.body.iter()) {
+                    names.extend(item.var_declared_names());
+                }
+            }
+            Stmt::Try(_, ref body, ref catch, ref finally) => {
+                let blocks = Some(body).into_iter()
+                    .chain(catch.iter().map(|catch| &catch.body))
+                    .chain(finally.iter());
+                for item in blocks.flat_map(|block| block.items.iter()) {
+                    names.extend(item.var_declared_names());
+                }
+            }
+            Stmt::For(_, ref head, _, _, ref body) => {
+                if let Some(ref head) = *head {
+                    if let ForHead::Var(_, ref dtors) = **head {
+                        for dtor in dtors {
+                            names.extend(dtor.bound_names());
+                        }
+                    }
+                }
+                names.extend(body.var_declared_names());
+            }
+            Stmt::ForIn(_, ref head, _, ref body) => {
+                match **head {
+                    ForInHead::VarInit(_, ref id, _) => { names.push(id); }
+                    ForInHead::Var(_, ref patt) => { names.extend(patt.bound_names()); }
+                    _ => { }
+                }
+                names.extend(body.var_declared_names());
+            }
+            Stmt::ForOf(_, ref head, _, ref body)
+          | Stmt::ForAwait(_, ref head, _, ref body) => {
+                if let ForOfHead::Var(_, ref patt) = **head {
+                    names.extend(patt.bound_names());
+                }
+                names.extend(body.var_declared_names());
+            }
+            _ => { }
+        }
+        names
+    }
+
     pub fn is_directive(&self) -> bool {
         match *self {
             Stmt::Expr(_, Expr::String(_, _), _) => true,
@@ -108,7 +182,7 @@ pub enum ForOfHead {
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub struct Catch {
     pub location: Option<Span>,
-    pub param: Patt<Id>,
+    pub param: Option<Patt<Id>>,
     pub body: Block
 }
 
@@ -122,14 +196,15 @@ pub struct Case {
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub struct Dir {
     pub location: Option<Span>,
-    pub string: StringLiteral,
+    pub string: StringLiteral<'static>,
     pub semi: Semi
 }
 
 impl Dir {
+    // The directive's raw text, without its quotes.
     pub fn pragma(&self) -> &str {
         if let Some(ref source) = self.string.source {
-            source
+            &source[1..source.len() - 1]
         } else {
             &self.string.value
         }
@@ -143,6 +218,22 @@ pub enum ModItem {
     StmtListItem(StmtListItem)
 }
 
+impl ModItem {
+    pub fn is_directive(&self) -> bool {
+        match *self {
+            ModItem::StmtListItem(ref item) => item.is_directive(),
+            _ => false
+        }
+    }
+
+    pub fn to_directive(&self) -> Option<Dir> {
+        match *self {
+            ModItem::StmtListItem(ref item) => item.to_directive(),
+            _ => None
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, TrackingRef, TrackingMut, Untrack)]
 pub enum StmtListItem {
     Decl(Decl),
@@ -150,6 +241,13 @@ pub enum StmtListItem {
 }
 
 impl StmtListItem {
+    pub fn var_declared_names(&self) -> Vec<&Id> {
+        match *self {
+            StmtListItem::Stmt(ref stmt) => stmt.var_declared_names(),
+            StmtListItem::Decl(_) => Vec::new()
+        }
+    }
+
     pub fn is_directive(&self) -> bool {
         match *self {
             StmtListItem::Stmt(ref stmt) => stmt.is_directive(),
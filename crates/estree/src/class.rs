@@ -0,0 +1,76 @@
+use easter::class::{Class, ClassElement};
+use unjson::ty::{Object, Ty};
+use unjson::{ExtractField, Unjson};
+
+use tag::{Tag, TagOf};
+use result::{Result, Map};
+use error::{Error, type_error, array_error, node_type_error};
+use node::ExtractNode;
+use fun::IntoFun;
+
+pub trait IntoClass<Id> {
+    fn into_class(self, Id) -> Result<Class<Id>>;
+}
+
+impl<Id> IntoClass<Id> for Object {
+    fn into_class(mut self, id: Id) -> Result<Class<Id>> {
+        let extends = self.extract_expr_opt("superClass")?.map(Box::new);
+        let mut body = self.extract_object("body").map_err(Error::Json)?;
+        let tag = body.tag()?;
+        if tag != Tag::ClassBody {
+            return node_type_error("class body", tag);
+        }
+        let list = body.extract_array("body").map_err(Error::Json)?;
+        let objs = list.map(|v| v.into_object().map_err(Error::Json))?;
+        Ok(Class {
+            location: None,
+            id: id,
+            extends: extends,
+            body: objs.map(|o| o.into_class_element())?
+        })
+    }
+}
+
+trait IntoClassElement {
+    fn into_class_element(self) -> Result<ClassElement>;
+}
+
+impl IntoClassElement for Object {
+    fn into_class_element(mut self) -> Result<ClassElement> {
+        let tag = self.tag()?;
+        match tag {
+            Tag::MethodDefinition => { }
+            Tag::PropertyDefinition => {
+                let key = self.extract_prop_key("key")?;
+                let is_static = self.extract_bool("static").map_err(Error::Json)?;
+                let value = self.extract_expr_opt("value")?;
+                return Ok(ClassElement::Field(None, is_static, key, value));
+            }
+            Tag::StaticBlock => {
+                return Ok(ClassElement::StaticBlock(None, self.extract_stmt_list("body")?));
+            }
+            _ => { return node_type_error("class element", tag); }
+        }
+        let key = self.extract_prop_key("key")?;
+        let is_static = self.extract_bool("static").map_err(Error::Json)?;
+        let val = self.extract_object("value").map_err(Error::Json)?;
+        let kind = self.extract_string("kind").map_err(Error::Json)?;
+        Ok(match &kind[..] {
+            "constructor" | "method" => ClassElement::Method(None, is_static, val.into_fun(key)?),
+            "get" => {
+                let fun = val.into_fun(())?;
+                ClassElement::Getter(None, is_static, key, fun.body)
+            }
+            "set" => {
+                let fun = val.into_fun(())?;
+                let params = fun.params.list;
+                if params.len() != 1 {
+                    return array_error(1, params.len());
+                }
+                let param = params.into_iter().next().unwrap();
+                ClassElement::Setter(None, is_static, key, param, fun.body)
+            }
+            _ => { return type_error("'constructor', 'method', 'get', or 'set'", Ty::String); }
+        })
+    }
+}
@@ -1,10 +1,22 @@
-use easter::decl::{Dtor, ConstDtor, DtorExt};
+use easter::decl::{Dtor, ConstDtor, DtorExt, Import, ImportSpec, ImportAttribute, Export, ExportSpec};
+use easter::stmt::{Stmt, StmtListItem};
 use easter::patt::Patt;
+use easter::obj::DotKey;
+use easter::punc::Semi;
+use joker::token::StringLiteral;
 use unjson::ty::Object;
+use unjson::{ExtractField, Unjson};
 
-use result::Result;
-use error::Error;
+use tag::{Tag, TagOf};
+use result::{Result, Map};
+use error::{Error, node_type_error};
 use node::ExtractNode;
+use lit::IntoStringLiteral;
+use obj::IntoObj;
+use stmt::IntoStmt;
+use expr::IntoExpr;
+use fun::IntoFun;
+use class::IntoClass;
 
 pub trait IntoDecl {
     fn into_dtor(self) -> Result<Dtor>;
@@ -38,4 +50,136 @@ impl IntoConst for Vec<Dtor> {
             })
         }).collect()
     }
-}
\ No newline at end of file
+}
+
+fn extract_source(this: &mut Object) -> Result<StringLiteral<'static>> {
+    Ok(this.extract_object("source").map_err(Error::Json)?
+           .extract_string("value").map_err(Error::Json)?
+           .into_string_literal())
+}
+
+// Trees that predate import attributes have no "attributes" field at all.
+fn extract_attributes(this: &mut Object) -> Result<Vec<ImportAttribute>> {
+    if !this.contains_key("attributes") {
+        return Ok(Vec::new());
+    }
+    let list = this.extract_array("attributes").map_err(Error::Json)?;
+    let objs = list.map(|v| v.into_object().map_err(Error::Json))?;
+    objs.map(|mut obj| {
+        Ok(ImportAttribute {
+            location: None,
+            key: obj.extract_object("key").map_err(Error::Json)?.into_prop_key()?,
+            value: obj.extract_object("value").map_err(Error::Json)?
+                      .extract_string("value").map_err(Error::Json)?
+                      .into_string_literal()
+        })
+    })
+}
+
+fn extract_dot_key(this: &mut Object, name: &'static str) -> Result<DotKey> {
+    Ok(DotKey {
+        location: None,
+        value: this.extract_id(name)?.name.into_string()
+    })
+}
+
+pub trait IntoImport {
+    fn into_import(self) -> Result<Import>;
+}
+
+impl IntoImport for Object {
+    fn into_import(mut self) -> Result<Import> {
+        let source = extract_source(&mut self)?;
+        let attributes = extract_attributes(&mut self)?;
+        let list = self.extract_array("specifiers").map_err(Error::Json)?;
+        let objs = list.map(|v| v.into_object().map_err(Error::Json))?;
+        let mut default = None;
+        let mut ns = None;
+        let mut specs = Vec::new();
+        for mut obj in objs {
+            let tag = obj.tag()?;
+            match tag {
+                Tag::ImportDefaultSpecifier => { default = Some(obj.extract_id("local")?); }
+                Tag::ImportNamespaceSpecifier => { ns = Some(obj.extract_id("local")?); }
+                Tag::ImportSpecifier => {
+                    specs.push(ImportSpec {
+                        location: None,
+                        imported: extract_dot_key(&mut obj, "imported")?,
+                        local: obj.extract_id("local")?
+                    });
+                }
+                _ => { return node_type_error("import specifier", tag); }
+            }
+        }
+        Ok(match ns {
+            Some(ns) => Import::Namespace(None, default, ns, source, attributes),
+            None if default.is_none() && specs.is_empty() => Import::ForEffect(None, source, attributes),
+            None => Import::Named(None, default, specs, source, attributes)
+        })
+    }
+}
+
+pub trait IntoExport {
+    fn into_export(self) -> Result<Export>;
+}
+
+impl IntoExport for Object {
+    fn into_export(mut self) -> Result<Export> {
+        let tag = self.tag()?;
+        Ok(match tag {
+            Tag::ExportNamedDeclaration => {
+                if let Some(decl) = self.extract_object_opt("declaration").map_err(Error::Json)? {
+                    return Ok(match decl.into_stmt_list_item()? {
+                        StmtListItem::Decl(decl) => Export::Decl(decl),
+                        StmtListItem::Stmt(Stmt::Var(_, dtors, semi)) => Export::Var(None, dtors, semi),
+                        StmtListItem::Stmt(_) => { return node_type_error("declaration", tag); }
+                    });
+                }
+                let list = self.extract_array("specifiers").map_err(Error::Json)?;
+                let objs = list.map(|v| v.into_object().map_err(Error::Json))?;
+                let specs = objs.map(|mut obj| {
+                    Ok(ExportSpec {
+                        location: None,
+                        local: extract_dot_key(&mut obj, "local")?,
+                        exported: extract_dot_key(&mut obj, "exported")?
+                    })
+                })?;
+                match self.extract_object_opt("source").map_err(Error::Json)? {
+                    Some(mut source) => {
+                        let source = source.extract_string("value").map_err(Error::Json)?.into_string_literal();
+                        Export::From(None, specs, source, extract_attributes(&mut self)?, Semi::Explicit(None))
+                    }
+                    None => Export::Local(None, specs, Semi::Explicit(None))
+                }
+            }
+            Tag::ExportDefaultDeclaration => {
+                let mut decl = self.extract_object("declaration").map_err(Error::Json)?;
+                match decl.tag()? {
+                    Tag::FunctionDeclaration => {
+                        let id = decl.extract_id_opt("id")?;
+                        Export::DefaultFun(decl.into_fun(id)?)
+                    }
+                    Tag::ClassDeclaration => {
+                        let id = decl.extract_id_opt("id")?;
+                        Export::DefaultClass(decl.into_class(id)?)
+                    }
+                    _ => Export::DefaultExpr(None, decl.into_expr()?, Semi::Explicit(None))
+                }
+            }
+            Tag::ExportAllDeclaration => {
+                let source = extract_source(&mut self)?;
+                let attributes = extract_attributes(&mut self)?;
+                let ns = if self.contains_key("exported") {
+                    self.extract_id_opt("exported")?
+                } else {
+                    None
+                };
+                match ns {
+                    Some(ns) => Export::AllAs(None, DotKey { location: None, value: ns.name.into_string() }, source, attributes, Semi::Explicit(None)),
+                    None => Export::All(None, source, attributes, Semi::Explicit(None))
+                }
+            }
+            _ => { return node_type_error("export declaration", tag); }
+        })
+    }
+}
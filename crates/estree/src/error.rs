@@ -39,8 +39,9 @@ impl Display for Error {
             &Error::UninitializedPattern(ref patt) => {
                 let ty = match *patt {
                     Patt::Compound(CompoundPatt::Arr(_, _, _)) => "array",
-                    Patt::Compound(CompoundPatt::Obj(_, _)) => "object",
-                    Patt::Simple(_) => "constant"
+                    Patt::Compound(CompoundPatt::Obj(_, _, _)) => "object",
+                    Patt::Simple(_) => "constant",
+                    Patt::Default(..) => "default"
                 };
                 fmt.write_fmt(format_args!("uninitialized {} pattern in declarator", ty))
             }
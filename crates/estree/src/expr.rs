@@ -1,6 +1,7 @@
 use serde_json::value::Value;
 use easter::expr::{Expr, ExprListItem};
 use easter::obj::DotKey;
+use easter::fun::{Arrow, ArrowBody};
 use easter::id::IdExt;
 use easter::punc::{Unop, Binop, Assop, Logop};
 use unjson::ty::{Object, TyOf};
@@ -13,7 +14,8 @@ use result::Result;
 use error::{Error, string_error, node_type_error, type_error};
 use node::ExtractNode;
 use fun::IntoFun;
-use lit::{IntoStringLiteral, IntoNumberLiteral};
+use class::IntoClass;
+use lit::{IntoStringLiteral, IntoNumberLiteral, IntoBigIntLiteral, IntoTemplate};
 
 pub trait IntoExpr {
     fn into_expr(self) -> Result<Expr>;
@@ -21,11 +23,23 @@ pub trait IntoExpr {
     fn into_lit(self) -> Result<Expr>;
 }
 
+// Trees that predate optional chaining have no "optional" field at all.
+fn extract_optional(obj: &mut Object) -> Result<bool> {
+    if !obj.contains_key("optional") {
+        return Ok(false);
+    }
+    obj.extract_bool("optional").map_err(Error::Json)
+}
+
 impl IntoExpr for Object {
     fn into_expr(mut self) -> Result<Expr> {
         let tag = self.tag()?;
         Ok(match tag {
             Tag::Identifier => { return Ok(self.into_id()?.into_expr()); }
+            Tag::PrivateIdentifier => {
+                let name = self.extract_string("name").map_err(Error::Json)?;
+                Expr::PrivateName(None, DotKey { location: None, value: name })
+            }
             Tag::Literal => IntoExpr::into_lit(self)?,
             Tag::BinaryExpression => {
                 let str = self.extract_string("operator").map_err(Error::Json)?;
@@ -84,19 +98,45 @@ impl IntoExpr for Object {
             }
             Tag::MemberExpression => {
                 let obj = Box::new(self.extract_expr("object")?);
+                let optional = extract_optional(&mut self)?;
                 if self.extract_bool("computed").map_err(Error::Json)? {
                     let prop = Box::new(self.extract_expr("property")?);
-                    Expr::Brack(None, obj, prop)
+                    if optional {
+                        Expr::OptionalBrack(None, obj, prop)
+                    } else {
+                        Expr::Brack(None, obj, prop)
+                    }
                 } else {
-                    let id = self.extract_object("property").map_err(Error::Json)?.into_id()?;
+                    let mut prop = self.extract_object("property").map_err(Error::Json)?;
+                    if prop.tag()? == Tag::PrivateIdentifier {
+                        let key = DotKey { location: None, value: prop.extract_string("name").map_err(Error::Json)? };
+                        return Ok(if optional {
+                            Expr::OptionalPrivateDot(None, obj, key)
+                        } else {
+                            Expr::PrivateDot(None, obj, key)
+                        });
+                    }
+                    let id = prop.into_id()?;
                     let key = DotKey { location: None, value: id.name.into_string() };
-                    Expr::Dot(None, obj, key)
+                    if optional {
+                        Expr::OptionalDot(None, obj, key)
+                    } else {
+                        Expr::Dot(None, obj, key)
+                    }
                 }
             }
             Tag::CallExpression => {
                 let callee = Box::new(self.extract_expr("callee")?);
                 let args = self.extract_expr_list("arguments")?;
-                Expr::Call(None, callee, args)
+                if extract_optional(&mut self)? {
+                    Expr::OptionalCall(None, callee, args)
+                } else {
+                    Expr::Call(None, callee, args)
+                }
+            }
+            Tag::ChainExpression => {
+                let chain = self.extract_expr("expression")?;
+                Expr::OptionalChain(None, Box::new(chain))
             }
             Tag::NewExpression => {
                 let callee = Box::new(self.extract_expr("callee")?);
@@ -112,6 +152,17 @@ impl IntoExpr for Object {
                 let fun = self.into_fun(id)?;
                 Expr::Fun(fun)
             }
+            Tag::ArrowFunctionExpression => {
+                let asynchronous = self.extract_bool("async").map_err(Error::Json)?;
+                let params = self.extract_params("params")?;
+                let body = if self.extract_bool("expression").map_err(Error::Json)? {
+                    ArrowBody::Expr(Box::new(self.extract_expr("body")?))
+                } else {
+                    let mut obj = self.extract_object("body").map_err(Error::Json)?;
+                    ArrowBody::Body(obj.extract_script("body")?)
+                };
+                Expr::Arrow(Arrow { location: None, asynchronous: asynchronous, params: params, body: body })
+            }
             Tag::SequenceExpression => {
                 let exprs = self.extract_exprs("expressions")?;
                 Expr::Seq(None, exprs)
@@ -127,12 +178,43 @@ impl IntoExpr for Object {
                 Expr::Cond(None, test, cons, alt)
             }
             Tag::ThisExpression => Expr::This(None),
+            Tag::YieldExpression => {
+                if self.extract_bool("delegate").map_err(Error::Json)? {
+                    Expr::YieldDelegate(None, Box::new(self.extract_expr("argument")?))
+                } else {
+                    Expr::Yield(None, self.extract_expr_opt("argument")?.map(Box::new))
+                }
+            }
+            Tag::AwaitExpression => {
+                Expr::Await(None, Box::new(self.extract_expr("argument")?))
+            }
+            Tag::ClassExpression => {
+                let id = self.extract_id_opt("id")?;
+                Expr::Class(self.into_class(id)?)
+            }
+            Tag::Super => Expr::Super(None),
+            Tag::ImportExpression => {
+                let source = Box::new(self.extract_expr("source")?);
+                let options = if self.contains_key("options") {
+                    self.extract_expr_opt("options")?.map(Box::new)
+                } else {
+                    None
+                };
+                Expr::ImportCall(None, source, options)
+            }
+            Tag::TemplateLiteral => Expr::Template(self.into_template()?),
+            Tag::TaggedTemplateExpression => {
+                let tag = Box::new(self.extract_expr("tag")?);
+                let quasi = self.extract_object("quasi").map_err(Error::Json)?.into_template()?;
+                Expr::TaggedTemplate(None, tag, quasi)
+            }
             Tag::MetaProperty => {
                 let meta = self.extract_id("meta")?.name;
                 let prop = self.extract_id("property")?.name;
                 match (meta.as_ref(), prop.as_ref()) {
                     ("new", "target") => Expr::NewTarget(None),
-                    (meta, prop) => { return string_error("new.target", format!("{}.{}", meta, prop)); }
+                    ("import", "meta") => Expr::ImportMeta(None),
+                    (meta, prop) => { return string_error("new.target or import.meta", format!("{}.{}", meta, prop)); }
                 }
             }
             _ => { return node_type_error("expression", tag); }
@@ -149,6 +231,10 @@ impl IntoExpr for Object {
     }
 
     fn into_lit(mut self) -> Result<Expr> {
+        if self.contains_key("bigint") {
+            let digits = self.extract_string("bigint").map_err(Error::Json)?;
+            return Ok(Expr::BigInt(None, digits.into_bigint_literal()?));
+        }
         let json = self.extract_field("value").map_err(Error::Json)?;
         Ok(match json {
             Value::Null if !self.contains_key("regex") => Expr::Null(None),
@@ -12,9 +12,18 @@ pub trait IntoFun<Id> {
 
 impl<Id> IntoFun<Id> for Object {
     fn into_fun(mut self, id: Id) -> Result<Fun<Id>> {
+        let generator = self.extract_bool("generator").map_err(Error::Json)?;
+        let asynchronous = self.extract_bool("async").map_err(Error::Json)?;
         let params = self.extract_params("params")?;
         let mut obj = self.extract_object("body").map_err(Error::Json)?;
         let body = obj.extract_script("body")?;
-        Ok(Fun { location: None, id: id, params: params, body: body })
+        Ok(Fun {
+            location: None,
+            id: id,
+            generator: generator,
+            asynchronous: asynchronous,
+            params: params,
+            body: body
+        })
     }
 }
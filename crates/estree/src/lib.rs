@@ -12,6 +12,7 @@ mod expr;
 mod id;
 mod node;
 mod fun;
+mod class;
 mod patt;
 mod obj;
 mod decl;
@@ -22,7 +23,7 @@ use serde::de::Error;
 use serde::de::{Deserialize, Deserializer};
 use easter::stmt::Script;
 use unjson::ty::Object;
-pub use prog::IntoScript;
+pub use prog::{IntoScript, IntoModule};
 
 pub struct ESTreeScript(Script);
 
@@ -1,45 +1,96 @@
-use joker::token::{StringLiteral, NumberLiteral, NumberSource};
+use joker::token::{StringLiteral, NumberLiteral, BigIntLiteral, NumberSource, TemplateLiteral};
+use joker::token::radix_to_decimal;
+use easter::expr::Template;
+use unjson::ty::Object;
+use unjson::{ExtractField, Unjson};
+
+use error::{Error, string_error};
+use result::{Result, Map};
+use node::ExtractNode;
 
 pub trait IntoStringLiteral {
-    fn into_string_literal(self) -> StringLiteral;
+    fn into_string_literal(self) -> StringLiteral<'static>;
 }
 
 impl IntoStringLiteral for String {
-    fn into_string_literal(self) -> StringLiteral {
+    fn into_string_literal(self) -> StringLiteral<'static> {
         StringLiteral {
             source: None,
-            value: self
+            value: self.into()
         }
     }
 }
 
 pub trait IntoNumberLiteral {
-    fn into_number_literal(self) -> NumberLiteral;
+    fn into_number_literal(self) -> NumberLiteral<'static>;
 }
 
 impl IntoNumberLiteral for i64 {
-    fn into_number_literal(self) -> NumberLiteral {
+    fn into_number_literal(self) -> NumberLiteral<'static> {
         NumberLiteral {
-            source: Some(NumberSource::DecimalInt(self.to_string(), None)),
+            source: Some(NumberSource::DecimalInt(self.to_string().into(), None)),
             value: self as f64
         }
     }
 }
 
 impl IntoNumberLiteral for u64 {
-    fn into_number_literal(self) -> NumberLiteral {
+    fn into_number_literal(self) -> NumberLiteral<'static> {
         NumberLiteral {
-            source: Some(NumberSource::DecimalInt(self.to_string(), None)),
+            source: Some(NumberSource::DecimalInt(self.to_string().into(), None)),
             value: self as f64
         }
     }
 }
 
 impl IntoNumberLiteral for f64 {
-    fn into_number_literal(self) -> NumberLiteral {
+    fn into_number_literal(self) -> NumberLiteral<'static> {
         NumberLiteral {
             source: None,
             value: self
         }
     }
 }
+
+pub trait IntoBigIntLiteral {
+    fn into_bigint_literal(self) -> Result<BigIntLiteral<'static>>;
+}
+
+impl IntoBigIntLiteral for String {
+    fn into_bigint_literal(self) -> Result<BigIntLiteral<'static>> {
+        let (radix, digits) = match self.get(..2) {
+            Some("0x") | Some("0X") => (16, &self[2..]),
+            Some("0o") | Some("0O") => (8, &self[2..]),
+            Some("0b") | Some("0B") => (2, &self[2..]),
+            _ => (10, &self[..])
+        };
+        let digits: String = digits.chars().filter(|&ch| ch != '_').collect();
+        if digits.is_empty() || !digits.chars().all(|ch| ch.is_digit(radix)) {
+            return string_error("BigInt digits", self);
+        }
+        Ok(BigIntLiteral {
+            source: None,
+            value: radix_to_decimal(&digits, radix)
+        })
+    }
+}
+
+pub trait IntoTemplate {
+    fn into_template(self) -> Result<Template>;
+}
+
+impl IntoTemplate for Object {
+    fn into_template(mut self) -> Result<Template> {
+        let quasis = self.extract_array("quasis").map_err(Error::Json)?;
+        let quasis = quasis.map(|v| {
+            let mut elt = v.into_object().map_err(Error::Json)?;
+            let mut value = elt.extract_object("value").map_err(Error::Json)?;
+            Ok(TemplateLiteral {
+                cooked: value.extract_string_opt("cooked").map_err(Error::Json)?,
+                raw: value.extract_string("raw").map_err(Error::Json)?
+            })
+        })?;
+        let exprs = self.extract_exprs("expressions")?;
+        Ok(Template { location: None, quasis: quasis, exprs: exprs })
+    }
+}
@@ -2,11 +2,11 @@ use unjson::ty::Object;
 use unjson::{ExtractField, Unjson};
 use easter::id::Id;
 use easter::expr::{Expr, ExprListItem};
-use easter::stmt::{Stmt, Block, StmtListItem, Case, Catch, Script};
+use easter::stmt::{Stmt, Block, StmtListItem, ModItem, Case, Catch, Script, Module};
 use easter::patt::{Patt, RestPatt, AssignTarget};
-use easter::obj::Prop;
+use easter::obj::{Prop, PropKey};
 use easter::decl::Dtor;
-use easter::cover::{IntoAssignTarget, IntoAssignPatt};
+use easter::cover::IntoAssignTarget;
 use easter::fun::Params;
 
 use tag::{Tag, TagOf};
@@ -35,11 +35,13 @@ pub trait ExtractNode {
     fn extract_block(&mut self, &'static str) -> Result<Block>;
     fn extract_patt(&mut self, &'static str) -> Result<Patt<Id>>;
     fn extract_params(&mut self, &'static str) -> Result<Params>;
+    fn extract_prop_key(&mut self, &'static str) -> Result<PropKey>;
     fn extract_prop_list(&mut self, &'static str) -> Result<Vec<Prop>>;
     fn extract_dtor_list(&mut self, &'static str) -> Result<Vec<Dtor>>;
     fn extract_case_list(&mut self, &'static str) -> Result<Vec<Case>>;
     fn extract_catch_opt(&mut self, &'static str) -> Result<Option<Catch>>;
     fn extract_script(&mut self, &'static str) -> Result<Script>;
+    fn extract_module(&mut self, &'static str) -> Result<Module>;
 }
 
 fn split_prefix<T, F>(v: &mut Vec<T>, mut p: F) -> Vec<T>
@@ -72,11 +74,7 @@ impl ExtractNode for Object {
     }
 
     fn extract_assign_patt(&mut self, name: &'static str) -> Result<Patt<AssignTarget>> {
-        let expr = self.extract_expr(name)?;
-        match expr.into_assign_patt() {
-            Ok(patt) => Ok(patt),
-            _ => Err(Error::InvalidLHS(name))
-        }
+        self.extract_object(name).map_err(Error::Json).and_then(|o| o.into_assign_patt())
     }
 
     fn extract_stmt(&mut self, name: &'static str) -> Result<Stmt> {
@@ -159,6 +157,14 @@ impl ExtractNode for Object {
         })
     }
 
+    fn extract_prop_key(&mut self, name: &'static str) -> Result<PropKey> {
+        let key = self.extract_object(name).map_err(Error::Json)?;
+        if self.extract_bool("computed").map_err(Error::Json)? {
+            return Ok(PropKey::Computed(None, key.into_expr()?));
+        }
+        key.into_prop_key()
+    }
+
     fn extract_prop_list(&mut self, name: &'static str) -> Result<Vec<Prop>> {
         let list = self.extract_array(name).map_err(Error::Json)?;
         let objs = list.map(|v| v.into_object().map_err(Error::Json))?;
@@ -192,6 +198,23 @@ impl ExtractNode for Object {
                          .collect();
         Ok(Script {
             location: None,
+            hashbang: None,
+            dirs: prolog,
+            items: items
+        })
+    }
+
+    fn extract_module(&mut self, name: &'static str) -> Result<Module> {
+        let list = self.extract_array(name).map_err(Error::Json)?;
+        let objs = list.map(|v| v.into_object().map_err(Error::Json))?;
+        let mut list: Vec<ModItem> = objs.map(|o| o.into_mod_item())?;
+        let items = split_prefix(&mut list, |s| s.is_directive());
+        let prolog = list.iter()
+                         .filter_map(|s| s.to_directive())
+                         .collect();
+        Ok(Module {
+            location: None,
+            hashbang: None,
             dirs: prolog,
             items: items
         })
@@ -18,16 +18,19 @@ pub trait IntoObj {
 
 impl IntoObj for Object {
     fn into_prop(mut self) -> Result<Prop> {
-        let key = self.extract_object("key").map_err(Error::Json)?;
+        if self.tag()? == Tag::SpreadElement {
+            return Ok(Prop::Spread(None, self.extract_expr("argument")?));
+        }
+        let key = self.extract_prop_key("key")?;
         let mut val = self.extract_object("value").map_err(Error::Json)?;
         let kind = self.extract_string("kind").map_err(Error::Json)?;
         let val = match &kind[..] {
             "init" => {
                 if self.extract_bool("method").map_err(Error::Json)? {
-                    let fun = val.into_fun(key.into_prop_key()?)?;
+                    let fun = val.into_fun(key)?;
                     return Ok(Prop::Method(fun))
                 } else if self.extract_bool("shorthand").map_err(Error::Json)? {
-                    return Ok(Prop::Shorthand(key.into_id()?));
+                    return Ok(Prop::Shorthand(val.into_id()?));
                 } else {
                     PropVal::Init(val.into_expr()?)
                 }
@@ -44,13 +47,19 @@ impl IntoObj for Object {
             }
             _ => { return type_error("'init', 'get', or 'set'", Ty::String); }
         };
-        Ok(Prop::Regular(None, key.into_prop_key()?, val))
+        Ok(Prop::Regular(None, key, val))
     }
 
-    fn into_prop_key(self) -> Result<PropKey> {
-        if self.tag()? == Tag::Identifier {
-            let id = self.into_id()?;
-            return Ok(PropKey::Id(None, id.name.into_string()));
+    fn into_prop_key(mut self) -> Result<PropKey> {
+        match self.tag()? {
+            Tag::Identifier => {
+                let id = self.into_id()?;
+                return Ok(PropKey::Id(None, id.name.into_string()));
+            }
+            Tag::PrivateIdentifier => {
+                return Ok(PropKey::Private(None, self.extract_string("name").map_err(Error::Json)?));
+            }
+            _ => { }
         }
         match self.into_lit()? {
             Expr::Number(_, lit) => Ok(PropKey::Number(None, lit)),
@@ -1,16 +1,118 @@
-use easter::patt::Patt;
+use easter::patt::{Patt, CompoundPatt, PropPatt, RestPatt, AssignTarget};
 use easter::id::{Id, IdExt};
+use easter::cover::IntoAssignPatt;
 use unjson::ty::Object;
+use unjson::{ExtractField, Unjson};
 
+use tag::{Tag, TagOf};
 use id::IntoId;
-use result::Result;
+use node::ExtractNode;
+use expr::IntoExpr;
+use result::{Result, Map};
+use error::{Error, node_type_error};
 
 pub trait IntoPatt {
     fn into_patt(self) -> Result<Patt<Id>>;
+    fn into_prop_patt(self) -> Result<PropPatt<Id>>;
+    fn into_assign_patt(self) -> Result<Patt<AssignTarget>>;
 }
 
 impl IntoPatt for Object {
     fn into_patt(self) -> Result<Patt<Id>> {
-        self.into_id().map(|id| id.into_patt())
+        into_patt_with(self, &|obj: Object| {
+            let tag = obj.tag()?;
+            match tag {
+                Tag::Identifier => obj.into_id().map(|id| id.into_patt()),
+                _ => node_type_error("pattern", tag)
+            }
+        })
     }
+
+    fn into_prop_patt(self) -> Result<PropPatt<Id>> {
+        into_prop_patt_with(self, &|obj: Object| obj.into_patt())
+    }
+
+    fn into_assign_patt(self) -> Result<Patt<AssignTarget>> {
+        into_patt_with(self, &|obj: Object| {
+            match obj.into_expr()?.into_assign_patt() {
+                Ok(patt) => Ok(patt),
+                Err(_) => Err(Error::InvalidLHS("left"))
+            }
+        })
+    }
+}
+
+// Binding and assignment patterns share their structure and only
+// differ in how the leaves are converted.
+fn into_patt_with<T, F>(mut obj: Object, leaf: &F) -> Result<Patt<T>>
+    where F: Fn(Object) -> Result<Patt<T>>
+{
+    let tag = obj.tag()?;
+    Ok(match tag {
+        Tag::ObjectPattern => {
+            let list = obj.extract_array("properties").map_err(Error::Json)?;
+            let mut objs = list.map(|v| v.into_object().map_err(Error::Json))?;
+            let mut rest = None;
+            if let Some(mut last) = objs.pop() {
+                if last.tag()? == Tag::RestElement {
+                    let arg = last.extract_object("argument").map_err(Error::Json)?;
+                    rest = Some(Box::new(RestPatt {
+                        location: None,
+                        patt: leaf(arg)?
+                    }));
+                } else {
+                    objs.push(last);
+                }
+            }
+            let props = objs.map(|o| into_prop_patt_with(o, leaf))?;
+            Patt::Compound(CompoundPatt::Obj(None, props, rest))
+        }
+        Tag::ArrayPattern => {
+            let list = obj.extract_array("elements").map_err(Error::Json)?;
+            let mut objs = list.map(|v| v.into_object_opt().map_err(Error::Json))?;
+            let mut rest = None;
+            match objs.pop() {
+                Some(Some(mut last)) => {
+                    if last.tag()? == Tag::RestElement {
+                        let arg = last.extract_object("argument").map_err(Error::Json)?;
+                        rest = Some(Box::new(RestPatt {
+                            location: None,
+                            patt: into_patt_with(arg, leaf)?
+                        }));
+                    } else {
+                        objs.push(Some(last));
+                    }
+                }
+                Some(None) => { objs.push(None); }
+                None => { }
+            }
+            let elts = objs.map(|o| match o {
+                Some(o) => into_patt_with(o, leaf).map(Some),
+                None => Ok(None)
+            })?;
+            Patt::Compound(CompoundPatt::Arr(None, elts, rest))
+        }
+        Tag::AssignmentPattern => {
+            let left = obj.extract_object("left").map_err(Error::Json)?;
+            let right = obj.extract_expr("right")?;
+            Patt::Default(None, Box::new(into_patt_with(left, leaf)?), Box::new(right))
+        }
+        _ => { return leaf(obj); }
+    })
+}
+
+fn into_prop_patt_with<T, F>(mut obj: Object, leaf: &F) -> Result<PropPatt<T>>
+    where F: Fn(Object) -> Result<Patt<T>>
+{
+    let key = obj.extract_prop_key("key")?;
+    let mut val = obj.extract_object("value").map_err(Error::Json)?;
+    if obj.extract_bool("shorthand").map_err(Error::Json)? {
+        if val.tag()? == Tag::AssignmentPattern {
+            let id = val.extract_id("left")?;
+            let default = val.extract_expr("right")?;
+            return Ok(PropPatt::ShorthandDefault(None, id, default));
+        }
+        return Ok(PropPatt::Shorthand(val.into_id()?));
+    }
+    Ok(PropPatt::Regular(None, key, into_patt_with(val, leaf)?))
 }
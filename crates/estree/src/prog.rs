@@ -1,5 +1,5 @@
 use unjson::ty::Object;
-use easter::stmt::Script;
+use easter::stmt::{Script, Module};
 use result::Result;
 use node::ExtractNode;
 
@@ -12,3 +12,13 @@ impl IntoScript for Object {
         self.extract_script("body")
     }
 }
+
+pub trait IntoModule {
+    fn into_module(self) -> Result<Module>;
+}
+
+impl IntoModule for Object {
+    fn into_module(mut self) -> Result<Module> {
+        self.extract_module("body")
+    }
+}
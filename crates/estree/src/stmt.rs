@@ -1,15 +1,16 @@
-use easter::stmt::{Stmt, Block, ForHead, ForInHead, ForOfHead, StmtListItem, Case, Catch};
+use easter::stmt::{Stmt, Block, ForHead, ForInHead, ForOfHead, StmtListItem, ModItem, Case, Catch};
 use easter::decl::Decl;
 use easter::punc::Semi;
 use easter::patt::Patt;
-use easter::cover::IntoAssignPatt;
 use unjson::ty::Object;
 use unjson::{Unjson, ExtractField};
 
 use tag::{Tag, TagOf};
-use decl::IntoConst;
+use decl::{IntoConst, IntoImport, IntoExport};
 use expr::IntoExpr;
 use fun::IntoFun;
+use class::IntoClass;
+use patt::IntoPatt;
 use error::{Error, string_error, array_error, node_type_error};
 use result::Result;
 use node::ExtractNode;
@@ -65,13 +66,7 @@ impl IntoForInHead for Object {
                     (_, _, _) => { return string_error("var or let", kind); }
                 }
             }
-            _ => {
-                let expr = self.into_expr()?;
-                match expr.into_assign_patt() {
-                    Ok(patt) => ForInHead::Patt(patt),
-                    _ => { return Err(Error::InvalidLHS("left")); }
-                }
-            }
+            _ => ForInHead::Patt(self.into_assign_patt()?)
         })
     }
 }
@@ -98,13 +93,7 @@ impl IntoForOfHead for Object {
                     _ => { return string_error("var or let", kind); }
                 }
             },
-            _ => {
-                let expr = self.into_expr()?;
-                match expr.into_assign_patt() {
-                    Ok(patt) => ForOfHead::Patt(patt),
-                    _ => { return Err(Error::InvalidLHS("left")); }
-                }
-            }
+            _ => ForOfHead::Patt(self.into_assign_patt()?)
         })
     }
 }
@@ -112,6 +101,7 @@ impl IntoForOfHead for Object {
 pub trait IntoStmt {
     fn into_stmt(self) -> Result<Stmt>;
     fn into_stmt_list_item(self) -> Result<StmtListItem>;
+    fn into_mod_item(self) -> Result<ModItem>;
     fn into_case(self) -> Result<Case>;
     fn into_catch(self) -> Result<Catch>;
     fn into_block(self) -> Result<Block>;
@@ -121,11 +111,20 @@ fn into_stmt_list_item(mut this: Object, allow_decl: bool) -> Result<StmtListIte
     let tag = this.tag()?;
     Ok(StmtListItem::Stmt(match tag {
         Tag::FunctionDeclaration => {
+            let id = this.extract_id("id")?;
+            let fun = this.into_fun(id)?;
+            if !allow_decl {
+                Stmt::Fun(fun)
+            } else {
+                return Ok(StmtListItem::Decl(Decl::Fun(fun)));
+            }
+        }
+        Tag::ClassDeclaration => {
             if !allow_decl {
                 return node_type_error("statement", tag);
             }
             let id = this.extract_id("id")?;
-            return Ok(StmtListItem::Decl(Decl::Fun(this.into_fun(id)?)));
+            return Ok(StmtListItem::Decl(Decl::Class(this.into_class(id)?)));
         }
         Tag::VariableDeclaration => {
             let dtors = this.extract_dtor_list("declarations")?;
@@ -188,7 +187,13 @@ fn into_stmt_list_item(mut this: Object, allow_decl: bool) -> Result<StmtListIte
             let left = this.extract_object("left").map_err(Error::Json)?.into_for_of_head()?;
             let right = this.extract_expr("right")?;
             let body = this.extract_stmt("body")?;
-            Stmt::ForOf(None, Box::new(left), right, Box::new(body))
+            // Trees that predate `for await` have no "await" field.
+            let awaits = this.contains_key("await") && this.extract_bool("await").map_err(Error::Json)?;
+            if awaits {
+                Stmt::ForAwait(None, Box::new(left), right, Box::new(body))
+            } else {
+                Stmt::ForOf(None, Box::new(left), right, Box::new(body))
+            }
         }
         Tag::BlockStatement => {
             Stmt::Block(this.into_block()?)
@@ -252,6 +257,16 @@ impl IntoStmt for Object {
         into_stmt_list_item(self, true)
     }
 
+    fn into_mod_item(self) -> Result<ModItem> {
+        match self.tag()? {
+            Tag::ImportDeclaration => self.into_import().map(ModItem::Import),
+            Tag::ExportNamedDeclaration
+          | Tag::ExportDefaultDeclaration
+          | Tag::ExportAllDeclaration => self.into_export().map(ModItem::Export),
+            _ => self.into_stmt_list_item().map(ModItem::StmtListItem)
+        }
+    }
+
     fn into_case(mut self) -> Result<Case> {
         let test = self.extract_expr_opt("test")?;
         let body = self.extract_stmt_list("consequent")?;
@@ -259,7 +274,10 @@ impl IntoStmt for Object {
     }
 
     fn into_catch(mut self) -> Result<Catch> {
-        let param = self.extract_patt("param")?;
+        let param = match self.extract_object_opt("param").map_err(Error::Json)? {
+            Some(param) => Some(param.into_patt()?),
+            None => None
+        };
         let body = self.extract_block("body")?;
         Ok(Catch { location: None, param: param, body: body })
     }
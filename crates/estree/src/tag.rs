@@ -10,6 +10,7 @@ use error::Error;
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Tag {
     Identifier,
+    PrivateIdentifier,
     Literal,
 
     BinaryExpression,
@@ -22,17 +23,45 @@ pub enum Tag {
     NewExpression,
     ArrayExpression,
     FunctionExpression,
+    ArrowFunctionExpression,
     SequenceExpression,
     ObjectExpression,
     ConditionalExpression,
     ThisExpression,
+    YieldExpression,
+    AwaitExpression,
+    ClassExpression,
     MetaProperty,
+    ImportExpression,
+    Super,
+    TemplateLiteral,
+    TaggedTemplateExpression,
+    ChainExpression,
 
     SpreadElement,
     RestElement,
 
+    ObjectPattern,
+    ArrayPattern,
+    AssignmentPattern,
+
     FunctionDeclaration,
+    ClassDeclaration,
     VariableDeclaration,
+    ImportDeclaration,
+    ExportNamedDeclaration,
+    ExportDefaultDeclaration,
+    ExportAllDeclaration,
+
+    ImportSpecifier,
+    ImportDefaultSpecifier,
+    ImportNamespaceSpecifier,
+    ExportSpecifier,
+
+    ClassBody,
+    MethodDefinition,
+    PropertyDefinition,
+    StaticBlock,
 
     EmptyStatement,
     ExpressionStatement,
@@ -66,6 +95,7 @@ impl FromStr for Tag {
     fn from_str(s: &str) -> Result<Tag> {
         Ok(match s {
             "Identifier"            => Tag::Identifier,
+            "PrivateIdentifier"     => Tag::PrivateIdentifier,
             "Literal"               => Tag::Literal,
             "BinaryExpression"      => Tag::BinaryExpression,
             "AssignmentExpression"  => Tag::AssignmentExpression,
@@ -77,15 +107,40 @@ impl FromStr for Tag {
             "NewExpression"         => Tag::NewExpression,
             "ArrayExpression"       => Tag::ArrayExpression,
             "FunctionExpression"    => Tag::FunctionExpression,
+            "ArrowFunctionExpression" => Tag::ArrowFunctionExpression,
             "SequenceExpression"    => Tag::SequenceExpression,
             "ObjectExpression"      => Tag::ObjectExpression,
             "ConditionalExpression" => Tag::ConditionalExpression,
             "ThisExpression"        => Tag::ThisExpression,
+            "YieldExpression"       => Tag::YieldExpression,
+            "AwaitExpression"       => Tag::AwaitExpression,
+            "ClassExpression"       => Tag::ClassExpression,
             "MetaProperty"          => Tag::MetaProperty,
+            "ImportExpression"      => Tag::ImportExpression,
+            "Super"                 => Tag::Super,
+            "TemplateLiteral"       => Tag::TemplateLiteral,
+            "TaggedTemplateExpression" => Tag::TaggedTemplateExpression,
+            "ChainExpression"       => Tag::ChainExpression,
             "FunctionDeclaration"   => Tag::FunctionDeclaration,
+            "ClassDeclaration"      => Tag::ClassDeclaration,
+            "ClassBody"             => Tag::ClassBody,
+            "MethodDefinition"      => Tag::MethodDefinition,
+            "PropertyDefinition"    => Tag::PropertyDefinition,
+            "StaticBlock"           => Tag::StaticBlock,
             "VariableDeclaration"   => Tag::VariableDeclaration,
+            "ImportDeclaration"     => Tag::ImportDeclaration,
+            "ImportSpecifier"       => Tag::ImportSpecifier,
+            "ImportDefaultSpecifier" => Tag::ImportDefaultSpecifier,
+            "ImportNamespaceSpecifier" => Tag::ImportNamespaceSpecifier,
+            "ExportNamedDeclaration" => Tag::ExportNamedDeclaration,
+            "ExportDefaultDeclaration" => Tag::ExportDefaultDeclaration,
+            "ExportAllDeclaration"  => Tag::ExportAllDeclaration,
+            "ExportSpecifier"       => Tag::ExportSpecifier,
             "SpreadElement"         => Tag::SpreadElement,
             "RestElement"           => Tag::RestElement,
+            "ObjectPattern"         => Tag::ObjectPattern,
+            "ArrayPattern"          => Tag::ArrayPattern,
+            "AssignmentPattern"     => Tag::AssignmentPattern,
             "EmptyStatement"        => Tag::EmptyStatement,
             "ExpressionStatement"   => Tag::ExpressionStatement,
             "IfStatement"           => Tag::IfStatement,
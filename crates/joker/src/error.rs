@@ -7,8 +7,10 @@ pub enum Error {
     IncompleteWordEscape(Option<char>),
     UnterminatedComment,
     UnterminatedRegExp(Option<char>),
+    RegExpFlagEscape,
     MissingExponent(Option<char>),
     UnterminatedString(Option<char>),
+    UnterminatedTemplate,
     MissingBinaryDigits,
     MissingOctalDigits,
     MissingHexDigits,
@@ -17,6 +19,7 @@ pub enum Error {
     IllegalUnicode(u32),
     IdAfterNumber(char),
     DigitAfterNumber(char),
+    MisplacedSeparator,
     ReservedWordWithEscapes(Reserved)
 }
 
@@ -32,12 +35,18 @@ impl Display for Error {
             &Error::UnterminatedRegExp(_) => {
                 fmt.write_str("unterminated regexp literal")
             }
+            &Error::RegExpFlagEscape => {
+                fmt.write_str("escape sequence in regexp flags")
+            }
             &Error::MissingExponent(_) => {
                 fmt.write_str("missing exponent")
             }
             &Error::UnterminatedString(_) => {
                 fmt.write_str("unterminated string")
             }
+            &Error::UnterminatedTemplate => {
+                fmt.write_str("unterminated template literal")
+            }
             &Error::MissingBinaryDigits => {
                 fmt.write_str("missing binary digits")
             }
@@ -62,6 +71,9 @@ impl Display for Error {
             &Error::DigitAfterNumber(_) => {
                 fmt.write_str("numeric literal starts immediately after previous numeric literal")
             }
+            &Error::MisplacedSeparator => {
+                fmt.write_str("misplaced numeric separator")
+            }
             &Error::ReservedWordWithEscapes(ref word) => {
                 fmt.write_fmt(format_args!("reserved word with escapes: {:?}", word))
             }
@@ -1,14 +1,16 @@
 use std::char;
+use std::borrow::Cow;
 
 use track::*;
-use token::{Token, TokenData, Exp, CharCase, Sign, NumberSource, Radix, StringLiteral, RegExpLiteral};
-use word::{Map as WordMap, Word};
+use token::{Token, TokenData, Comment, CommentKind, Trivia, TriviaKind, SourceToken, Exp, CharCase, Sign, NumberSource, Radix, StringLiteral, RegExpLiteral, TemplateLiteral};
+use word::{Map as WordMap, Word, Name};
 
 use char::ESCharExt;
-use reader::Reader;
+use reader::{Input, Reader, StrReader};
 use error::Error;
 use result::Result;
 use std::collections::VecDeque;
+use std::mem::replace;
 
 fn add_digits(digits: Vec<u32>, radix: u32) -> u32 {
     let mut place = 1;
@@ -25,36 +27,101 @@ struct SpanTracker {
 }
 
 impl SpanTracker {
-    fn end<I>(&self, lexer: &Lexer<I>, value: TokenData) -> Token
-      where I: Iterator<Item=char>
+    fn end<'a, I>(&self, lexer: &Lexer<'a, I>, value: TokenData<'a>) -> Token<'a>
+      where I: Input<'a>
     {
         let end = lexer.posn();
         Token::new(self.start, end, value)
     }
 }
 
-pub struct Lexer<I> {
-    reader: Reader<I>,
-    lookahead: VecDeque<Token>,
+pub struct Lexer<'a, I> {
+    reader: I,
+    lookahead: VecDeque<Token<'a>>,
     wordmap: WordMap,
-    empty_line: bool
+    empty_line: bool,
+    hashbang: Option<String>,
+    html_comments: bool,
+    found_newline: bool,            // has the trivia skipped so far contained a newline?
+    comments: Option<Vec<Comment>>, // comments skipped so far, if they're being collected
+    ends: Vec<Posn>,                // ends of the most recently consumed tokens
+    raw: Option<String>,            // source read since the last token or trivia, if it's being kept
+    trivia: Vec<Trivia>,            // trivia since the last token
+    tokens: Vec<SourceToken<'a>>    // tokens lexed so far, if their source is being kept
 }
 
-impl<I> Lexer<I> where I: Iterator<Item=char> {
+impl<I> Lexer<'static, Reader<I>> where I: Iterator<Item=char> {
+    pub fn new(chars: I) -> Lexer<'static, Reader<I>> {
+        Lexer::from_input(Reader::new(chars))
+    }
+}
+
+impl<'a> Lexer<'a, StrReader<'a>> {
+    // Lexes a string in place. Keywords and punctuation are never copied,
+    // and identifiers, strings and numbers borrow their text from the input
+    // unless escapes or separators make it differ from the source.
+    pub fn from_slice(source: &'a str) -> Lexer<'a, StrReader<'a>> {
+        Lexer::from_input(StrReader::new(source))
+    }
+}
+
+impl<'a, I> Lexer<'a, I> where I: Input<'a> {
     // constructor
 
-    pub fn new(chars: I) -> Lexer<I> {
+    pub fn from_input(reader: I) -> Lexer<'a, I> {
         Lexer {
-            reader: Reader::new(chars),
+            reader: reader,
             lookahead: VecDeque::with_capacity(2),
             wordmap: WordMap::new(),
-            empty_line: true
+            empty_line: true,
+            hashbang: None,
+            html_comments: true,
+            found_newline: false,
+            comments: None,
+            ends: Vec::with_capacity(3),
+            raw: None,
+            trivia: Vec::new(),
+            tokens: Vec::new()
+        }
+    }
+
+    // Annex B `<!--` and `-->` comments, which modules may not contain.
+    pub fn allow_html_comments(&mut self, allow: bool) {
+        self.html_comments = allow;
+    }
+
+    pub fn collect_comments(&mut self) {
+        if self.comments.is_none() {
+            self.comments = Some(Vec::new());
+        }
+    }
+
+    // The comments collected so far, in source order.
+    pub fn take_comments(&mut self) -> Vec<Comment> {
+        match self.comments {
+            Some(ref mut comments) => replace(comments, Vec::new()),
+            None => Vec::new()
+        }
+    }
+
+    // Keeps every token with its source and leading trivia. Must be called
+    // before any tokens have been read.
+    pub fn collect_tokens(&mut self) {
+        debug_assert!(self.lookahead.is_empty() && self.posn().offset == 0);
+        if self.raw.is_none() {
+            self.raw = Some(String::new());
         }
     }
 
+    // The tokens lexed so far, in source order. Once the EOF token has
+    // been lexed, they print back to the whole source.
+    pub fn take_tokens(&mut self) -> Vec<SourceToken<'a>> {
+        replace(&mut self.tokens, Vec::new())
+    }
+
     // public methods
 
-    pub fn peek_token(&mut self, operator: bool) -> Result<&Token> {
+    pub fn peek_token(&mut self, operator: bool) -> Result<&Token<'a>> {
         if self.lookahead.is_empty() {
             let token = self.read_next_token(operator)?;
             self.lookahead.push_front(token);
@@ -62,7 +129,7 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         Ok(self.lookahead.front().unwrap())
     }
 
-    pub fn repeek_token(&mut self) -> &Token {
+    pub fn repeek_token(&mut self) -> &Token<'a> {
         self.lookahead.front().unwrap()
     }
 
@@ -71,28 +138,75 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         Ok(())
     }
 
-    pub fn reread_token(&mut self) -> Token {
-        self.lookahead.pop_front().unwrap()
+    pub fn reread_token(&mut self) -> Token<'a> {
+        let token = self.lookahead.pop_front().unwrap();
+        self.consume(token)
     }
 
-    pub fn read_token(&mut self, operator: bool) -> Result<Token> {
-        match self.lookahead.pop_front() {
-            Some(token) => Ok(token),
-            None => self.read_next_token(operator)
-        }
+    pub fn read_token(&mut self, operator: bool) -> Result<Token<'a>> {
+        let token = match self.lookahead.pop_front() {
+            Some(token) => token,
+            None => self.read_next_token(operator)?
+        };
+        Ok(self.consume(token))
     }
 
-    pub fn unread_token(&mut self, token: Token) {
+    pub fn unread_token(&mut self, token: Token<'a>) {
         debug_assert!(self.lookahead.len() < self.lookahead.capacity(), "Lookahead buffer is full");
+        self.ends.pop();
         self.lookahead.push_front(token)
     }
 
+    // Rescans a peeked `}` as the start of a TemplateMiddle or TemplateTail.
+    pub fn read_template_continuation(&mut self) -> Result<Token<'a>> {
+        let brace = self.read_token(true)?;
+        debug_assert!(brace.value == TokenData::RBrace && self.lookahead.is_empty());
+        self.ends.pop();
+        let span = SpanTracker { start: brace.location.start };
+        let token = self.read_template_chars(span, TokenData::TemplateTail, TokenData::TemplateMiddle)?;
+        if self.raw.is_some() {
+            let brace = self.tokens.pop().unwrap();
+            let source = brace.source + &self.take_raw();
+            self.tokens.push(SourceToken { leading: brace.leading, token: token.clone(), source: source });
+        }
+        Ok(self.consume(token))
+    }
+
+    // The `#!` line at the very start of the source, if any, once the
+    // first token has been read.
+    pub fn hashbang(&self) -> Option<&str> {
+        self.hashbang.as_ref().map(|s| &s[..])
+    }
+
     // source location
 
     pub fn posn(&self) -> Posn {
         self.reader.curr_posn()
     }
 
+    // Where the next token starts, past any whitespace and comments.
+    pub fn next_posn(&mut self) -> Result<Posn> {
+        if let Some(token) = self.lookahead.front() {
+            return Ok(token.location.start);
+        }
+        self.skip_trivia()?;
+        Ok(self.posn())
+    }
+
+    // Where the last token that was read ends.
+    pub fn prev_posn(&self) -> Posn {
+        self.ends.last().cloned().unwrap_or(Posn::origin())
+    }
+
+    fn consume(&mut self, token: Token<'a>) -> Token<'a> {
+        // Only as many tokens as fit in the lookahead buffer can be unread.
+        if self.ends.len() > 2 {
+            self.ends.remove(0);
+        }
+        self.ends.push(token.location.end);
+        token
+    }
+
     fn start(&self) -> SpanTracker {
         SpanTracker { start: self.posn() }
     }
@@ -100,7 +214,15 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
     // generic lexing utilities
 
     fn read(&mut self) -> char {
-        self.reader.next().unwrap()
+        let ch = self.reader.next_char().unwrap();
+        if let Some(ref mut raw) = self.raw {
+            raw.push(ch);
+        }
+        ch
+    }
+
+    fn take_raw(&mut self) -> String {
+        replace(self.raw.as_mut().unwrap(), String::new())
     }
 
     fn reread(&mut self, ch: char) -> char {
@@ -130,51 +252,34 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         (self.peek() == Some(ch)) && { self.skip(); true }
     }
 
+    // The chars skipped are left in `self.reader.last_run()`.
     fn skip_while<F>(&mut self, pred: &F)
       where F: Fn(char) -> bool
     {
-        self.skip_until(&|ch| !pred(ch))
+        self.reader.skip_while(pred);
+        if let Some(ref mut raw) = self.raw {
+            raw.push_str(self.reader.last_run());
+        }
     }
 
     fn skip_until<F>(&mut self, pred: &F)
       where F: Fn(char) -> bool
     {
-        loop {
-            match self.peek() {
-                Some(ch) if pred(ch) => return,
-                None => return,
-                _ => ()
-            }
-            self.skip();
-        }
+        self.skip_while(&|ch| !pred(ch))
     }
 
     fn read_into_until<F>(&mut self, s: &mut String, pred: &F)
       where F: Fn(char) -> bool
     {
-        loop {
-            match self.peek() {
-                Some(ch) if pred(ch) => return,
-                Some(ch) => { s.push(self.reread(ch)); }
-                None => return,
-            }
-        }
+        self.skip_until(pred);
+        s.push_str(self.reader.last_run());
     }
 
     fn read_into2_until<F>(&mut self, s1: &mut String, s2: &mut String, pred: &F)
       where F: Fn(char) -> bool
     {
-        loop {
-            match self.peek() {
-                Some(ch) if pred(ch) => return,
-                Some(ch) => {
-                    self.reread(ch);
-                    s1.push(ch);
-                    s2.push(ch);
-                }
-                None => return,
-            }
-        }
+        self.read_into_until(s1, pred);
+        s2.push_str(self.reader.last_run());
     }
 
     fn read_until_with<F, G>(&mut self, pred: &F, read: &mut G) -> Result<()>
@@ -203,31 +308,46 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         }
     }
 
-    fn read_newline_into(&mut self, s: &mut String) {
-        debug_assert!(self.peek().map_or(false, |ch| ch.is_es_newline()));
-        if self.peek2() == (Some('\r'), Some('\n')) {
-            s.push_str("\r\n");
-            self.skip2();
-            return;
-        }
-        s.push(self.read());
-    }
-
     fn skip_whitespace(&mut self) {
         self.skip_while(&|ch| ch.is_es_whitespace());
     }
 
-    fn skip_line_comment(&mut self, prefix_len: usize) {
+    fn skip_line_comment(&mut self, prefix_len: usize, kind: CommentKind) {
+        let start = self.posn();
         for _ in 0..prefix_len {
             self.skip();
         }
         self.skip_until(&|ch| ch.is_es_newline());
+        if self.comments.is_some() {
+            let text = self.reader.last_run().to_string();
+            self.push_comment(start, kind, text);
+        }
+    }
+
+    fn push_comment(&mut self, start: Posn, kind: CommentKind, text: String) {
+        let location = Span { start: start, end: self.posn() };
+        if let Some(ref mut comments) = self.comments {
+            comments.push(Comment { location: location, kind: kind, text: text });
+        }
+    }
+
+    fn read_hashbang(&mut self) {
+        self.skip2();
+        self.skip_until(&|ch| ch.is_es_newline());
+        self.hashbang = Some(self.reader.last_run().to_string());
     }
 
     fn skip_block_comment(&mut self) -> Result<bool> {
+        let start = self.posn();
         self.skip2();
         let mut found_newline = false;
+        let mut text = String::new();
+        let collect = self.comments.is_some();
         loop {
+            self.skip_until(&|ch| ch == '*' || ch.is_es_newline());
+            if collect {
+                text.push_str(self.reader.last_run());
+            }
             match self.peek2() {
                 (None, Some(_)) => unreachable!(),
                 (_, None)  => { return Err(Error::UnterminatedComment); }
@@ -236,20 +356,29 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
                     if ch.is_es_newline() {
                         found_newline = true;
                     }
+                    if collect {
+                        text.push(ch);
+                    }
                     self.skip();
                 }
             }
         }
+        if collect {
+            self.push_comment(start, CommentKind::Block, text);
+        }
         Ok(found_newline)
     }
 
-    fn read_regexp(&mut self) -> Result<Token> {
+    fn read_regexp(&mut self) -> Result<Token<'a>> {
         let span = self.start();
         let mut s = String::new();
         self.reread('/');
         self.read_until_with(&|ch| ch == '/', &mut |this| { this.read_regexp_char(&mut s) })?;
         self.reread('/');
         let flags = self.read_word_parts()?;
+        if flags.had_escape() {
+            return Err(Error::RegExpFlagEscape);
+        }
         Ok(span.end(self, TokenData::RegExp(RegExpLiteral {
             pattern: s,
             flags: flags.text.chars().collect()
@@ -290,13 +419,33 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         }
     }
 
-    fn read_decimal_digits(&mut self) -> String {
-        let mut s = String::new();
-        self.read_into_until(&mut s, &|ch| !ch.is_digit(10));
-        s
+    // Reads digits satisfying `pred`, skipping any `_` separators between
+    // them, and returns whether there were any separators. The digits are
+    // only copied out of the source if they had separators.
+    fn read_digits<F>(&mut self, pred: &F) -> Result<(Cow<'a, str>, bool)>
+      where F: Fn(char) -> bool
+    {
+        let start = self.posn();
+        self.skip_while(pred);
+        if self.peek() != Some('_') {
+            return Ok((self.reader.text_from(start), false));
+        }
+        let mut s = self.reader.last_run().to_string();
+        while self.peek() == Some('_') {
+            if !self.reader.peek(1).map_or(false, |ch| pred(ch)) {
+                return Err(Error::MisplacedSeparator);
+            }
+            self.skip();
+            self.read_into_until(&mut s, &|ch| !pred(ch));
+        }
+        Ok((Cow::Owned(s), true))
     }
 
-    fn read_exp_part(&mut self) -> Result<Option<Exp>> {
+    fn read_decimal_digits(&mut self) -> Result<Cow<'a, str>> {
+        Ok(self.read_digits(&|ch| ch.is_digit(10))?.0)
+    }
+
+    fn read_exp_part(&mut self) -> Result<Option<Exp<'a>>> {
         let e = match self.peek() {
             Some('e') => CharCase::LowerCase,
             Some('E') => CharCase::UpperCase,
@@ -313,53 +462,61 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
             None => { return Err(Error::MissingExponent(None)); }
             _ => ()
         }
-        Ok(Some(Exp { e: e, sign: sign, value: self.read_decimal_digits() }))
+        Ok(Some(Exp { e: e, sign: sign, value: self.read_decimal_digits()? }))
     }
 
-    fn read_decimal_int(&mut self) -> String {
-        let mut s = String::new();
-        self.read_into_until(&mut s, &|ch| !ch.is_digit(10));
-        s
+    fn read_decimal_int(&mut self) -> Result<Cow<'a, str>> {
+        let (s, separated) = self.read_digits(&|ch| ch.is_digit(10))?;
+        // Separators can't follow a leading zero, which also rules them
+        // out of legacy octal literals.
+        if separated && s.starts_with('0') {
+            return Err(Error::MisplacedSeparator);
+        }
+        Ok(s)
     }
 
-    fn read_radix_int<F, G>(&mut self, radix: u32, pred: &F, cons: &G, missing_digits: Error) -> Result<Token>
+    fn read_radix_int<F, G>(&mut self, pred: &F, radix: &G, missing_digits: Error) -> Result<Token<'a>>
       where F: Fn(char) -> bool,
-            G: Fn(CharCase, String) -> TokenData
+            G: Fn(CharCase) -> Radix
     {
         debug_assert!(self.peek() == Some('0'));
         debug_assert!(self.reader.peek(1).map_or(false, |ch| ch.is_alphabetic()));
         let span = self.start();
-        let mut s = String::new();
         self.skip();
         let flag = if self.read().is_lowercase() {
             CharCase::LowerCase
         } else {
             CharCase::UpperCase
         };
-        self.read_digit_into(&mut s, radix, pred, missing_digits)?;
-        self.read_into_until(&mut s, &|ch| !pred(ch));
-        Ok(span.end(self, cons(flag, s)))
+        let radix = radix(flag);
+        match self.peek() {
+            Some(ch) if pred(ch) => {}
+            Some(ch) => { return Err(Error::InvalidDigit(ch)); }
+            None => { return Err(missing_digits); }
+        }
+        let (s, _) = self.read_digits(pred)?;
+        let source = NumberSource::RadixInt(radix, s);
+        let value = if self.matches('n') {
+            source.into_bigint_token_data()
+        } else {
+            source.into_token_data()
+        };
+        Ok(span.end(self, value))
     }
 
-    fn read_hex_int(&mut self) -> Result<Token> {
-        self.read_radix_int(16, &|ch| ch.is_es_hex_digit(), &|cc, s| {
-            NumberSource::RadixInt(Radix::Hex(cc), s).into_token_data()
-        }, Error::MissingHexDigits)
+    fn read_hex_int(&mut self) -> Result<Token<'a>> {
+        self.read_radix_int(&|ch| ch.is_es_hex_digit(), &Radix::Hex, Error::MissingHexDigits)
     }
 
-    fn read_oct_int(&mut self) -> Result<Token> {
-        self.read_radix_int(8, &|ch| ch.is_es_oct_digit(), &|cc, s| {
-            NumberSource::RadixInt(Radix::Oct(Some(cc)), s).into_token_data()
-        }, Error::MissingOctalDigits)
+    fn read_oct_int(&mut self) -> Result<Token<'a>> {
+        self.read_radix_int(&|ch| ch.is_es_oct_digit(), &|cc| Radix::Oct(Some(cc)), Error::MissingOctalDigits)
     }
 
-    fn read_bin_int(&mut self) -> Result<Token> {
-        self.read_radix_int(2, &|ch| ch.is_es_bin_digit(), &|cc, s| {
-            NumberSource::RadixInt(Radix::Bin(cc), s).into_token_data()
-        }, Error::MissingBinaryDigits)
+    fn read_bin_int(&mut self) -> Result<Token<'a>> {
+        self.read_radix_int(&|ch| ch.is_es_bin_digit(), &Radix::Bin, Error::MissingBinaryDigits)
     }
 
-    fn read_number(&mut self) -> Result<Token> {
+    fn read_number(&mut self) -> Result<Token<'a>> {
         let result = match self.peek2() {
             (Some('0'), Some('x')) | (Some('0'), Some('X')) => self.read_hex_int(),
             (Some('0'), Some('o')) | (Some('0'), Some('O')) => self.read_oct_int(),
@@ -367,21 +524,23 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
             (Some('.'), _) => {
                 let span = self.start();
                 self.skip();
-                let frac = self.read_decimal_digits();
+                let frac = self.read_decimal_digits()?;
                 let exp = self.read_exp_part()?;
                 Ok(span.end(self, NumberSource::Float(None, Some(frac), exp).into_token_data()))
             }
             (Some(ch), _) => {
                 debug_assert!(ch.is_digit(10));
                 let span = self.start();
-                let s = self.read_decimal_int();
+                let s = self.read_decimal_int()?;
                 let value = if ch == '0' && s.len() > 1 && s.chars().skip(1).all(|ch| ch.is_es_oct_digit()) {
-                    NumberSource::RadixInt(Radix::Oct(None), s)
+                    NumberSource::RadixInt(Radix::Oct(None), s).into_token_data()
+                } else if (ch != '0' || s.len() == 1) && self.matches('n') {
+                    NumberSource::DecimalInt(s, None).into_bigint_token_data()
                 } else {
                     let (dot, frac) = if self.matches('.') {
                         (true, Some(match self.peek() {
-                            Some(ch) if ch.is_digit(10) => self.read_decimal_digits(),
-                            _ => String::from("")
+                            Some(ch) if ch.is_digit(10) => self.read_decimal_digits()?,
+                            _ => Cow::Borrowed("")
                         }))
                     } else {
                         (false, None)
@@ -391,9 +550,9 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
                         NumberSource::Float(Some(s), frac, exp)
                     } else {
                         NumberSource::DecimalInt(s, exp)
-                    }
+                    }.into_token_data()
                 };
-                Ok(span.end(self, value.into_token_data()))
+                Ok(span.end(self, value))
             }
             (None, _) => { panic!("read_number() called at EOF"); }
         }?;
@@ -405,36 +564,44 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         Ok(result)
     }
 
-    fn read_string(&mut self) -> Result<Token> {
+    fn read_string(&mut self) -> Result<Token<'a>> {
         debug_assert!(self.peek().is_some());
         let span = self.start();
-        let mut source = String::new();
-        let mut value = String::new();
         let quote = self.read();
-        source.push(quote);
+        let contents = self.posn();
+        // The value is only built up separately once there's an escape in
+        // it; until then it's just the source between the quotes.
+        let mut cooked: Option<String> = None;
         loop {
-            self.read_into2_until(&mut source, &mut value, &|ch| {
+            self.skip_until(&|ch| {
                 ch == quote ||
                 ch == '\\' ||
                 ch.is_es_newline()
             });
+            if let Some(ref mut cooked) = cooked {
+                cooked.push_str(self.reader.last_run());
+            }
             match self.peek() {
                 Some('\\') => {
-                    self.read_string_escape(&mut source, &mut value)?;
+                    if cooked.is_none() {
+                        cooked = Some(self.reader.text_from(contents).into_owned());
+                    }
+                    self.read_string_escape(cooked.as_mut().unwrap())?;
                 }
                 Some(ch) if ch.is_es_newline() => {
                     return Err(Error::UnterminatedString(Some(ch)));
                 }
-                Some(_) => {
-                    source.push(quote);
-                    self.skip();
-                    break;
-                }
+                Some(_) => { break; }
                 None => return Err(Error::UnterminatedString(None))
             }
         }
+        let value = match cooked {
+            Some(cooked) => Cow::Owned(cooked),
+            None => self.reader.text_from(contents)
+        };
+        self.skip();
         Ok(span.end(self, TokenData::String(StringLiteral {
-            source: Some(source),
+            source: Some(self.reader.text_from(span.start)),
             value: value
         })))
     }
@@ -448,6 +615,9 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
                 digits.push(this.read_hex_digit_into(s)?);
                 Ok(())
             })?;
+            if self.peek() != Some('}') {
+                return Err(Error::MissingHexDigits);
+            }
             s.push(self.reread('}'));
             Ok(add_digits(digits, 16))
         } else {
@@ -461,8 +631,8 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         }
     }
 
-    fn read_string_escape(&mut self, source: &mut String, value: &mut String) -> Result<()> {
-        source.push(self.reread('\\'));
+    fn read_string_escape(&mut self, value: &mut String) -> Result<()> {
+        self.reread('\\');
         match self.peek() {
             Some(ch) if ch.is_digit(8) => {
                 let mut code = 0;
@@ -473,7 +643,7 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
                             if new_code > 255 {
                                 break;
                             }
-                            source.push(self.reread(ch));
+                            self.skip();
                             code = new_code;
                         },
                         _ => { break; }
@@ -482,26 +652,32 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
                 value.push(char::from_u32(code).unwrap_or('?'));
             }
             Some(ch) if ch.is_es_single_escape_char() => {
-                source.push(self.reread(ch));
+                self.skip();
                 value.push(ch.unescape());
             }
             Some('x') => {
-                source.push(self.reread('x'));
+                self.skip();
+                let mut dummy = String::with_capacity(2);
                 let mut code = 0;
-                code += self.read_hex_digit_into(source)? << 4;
-                code += self.read_hex_digit_into(source)?;
+                code += self.read_hex_digit_into(&mut dummy)? << 4;
+                code += self.read_hex_digit_into(&mut dummy)?;
                 value.push(char::from_u32(code).unwrap_or('?'));
             }
             Some('u') => {
-                source.push(self.reread('u'));
-                let code = self.read_unicode_escape_seq(source)?;
+                self.skip();
+                let mut dummy = String::with_capacity(4);
+                let code = self.read_unicode_escape_seq(&mut dummy)?;
                 value.push(char::from_u32(code).unwrap_or('?'));
             }
             Some(ch) if ch.is_es_newline() => {
-                self.read_newline_into(source);
+                if self.peek2() == (Some('\r'), Some('\n')) {
+                    self.skip2();
+                } else {
+                    self.skip();
+                }
             }
             Some(ch) => {
-                source.push(self.reread(ch));
+                self.skip();
                 value.push(ch);
             }
             None => { } // error will be reported from caller
@@ -509,6 +685,100 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         Ok(())
     }
 
+    fn read_template(&mut self) -> Result<Token<'a>> {
+        let span = self.start();
+        self.reread('`');
+        self.read_template_chars(span, TokenData::NoSubstTemplate, TokenData::TemplateHead)
+    }
+
+    fn read_template_chars(&mut self, span: SpanTracker,
+                           end: fn(TemplateLiteral) -> TokenData<'a>,
+                           subst: fn(TemplateLiteral) -> TokenData<'a>) -> Result<Token<'a>> {
+        let mut raw = String::new();
+        let mut cooked = String::new();
+        let mut valid = true;
+        let cons = loop {
+            self.read_into2_until(&mut raw, &mut cooked, &|ch| {
+                ch == '`' || ch == '$' || ch == '\\' || ch == '\r'
+            });
+            match self.peek2() {
+                (Some('`'), _) => { self.skip(); break end; }
+                (Some('$'), Some('{')) => { self.skip2(); break subst; }
+                (Some('$'), _) => {
+                    raw.push(self.reread('$'));
+                    cooked.push('$');
+                }
+                (Some('\\'), _) => {
+                    valid = self.read_template_escape(&mut raw, &mut cooked) && valid;
+                }
+                (Some('\r'), _) => {
+                    self.skip();
+                    self.matches('\n');
+                    raw.push('\n');
+                    cooked.push('\n');
+                }
+                _ => { return Err(Error::UnterminatedTemplate); }
+            }
+        };
+        Ok(span.end(self, cons(TemplateLiteral {
+            cooked: if valid { Some(cooked) } else { None },
+            raw: raw
+        })))
+    }
+
+    // Returns false for escapes that are only legal in tagged templates. The
+    // characters consumed so far stay in the raw string either way.
+    fn read_template_escape(&mut self, raw: &mut String, cooked: &mut String) -> bool {
+        raw.push(self.reread('\\'));
+        match self.peek2() {
+            (Some('0'), Some(ch)) if ch.is_digit(10) => false,
+            (Some('0'), _) => {
+                raw.push(self.reread('0'));
+                cooked.push('\0');
+                true
+            }
+            (Some(ch), _) if ch.is_digit(10) => false,
+            (Some(ch), _) if ch.is_es_single_escape_char() => {
+                raw.push(self.reread(ch));
+                cooked.push(ch.unescape());
+                true
+            }
+            (Some('x'), _) => {
+                raw.push(self.reread('x'));
+                match (self.read_hex_digit_into(raw), self.read_hex_digit_into(raw)) {
+                    (Ok(hi), Ok(lo)) => {
+                        cooked.push(char::from_u32((hi << 4) + lo).unwrap());
+                        true
+                    }
+                    _ => false
+                }
+            }
+            (Some('u'), _) => {
+                raw.push(self.reread('u'));
+                match self.read_unicode_escape_seq(raw).ok().and_then(char::from_u32) {
+                    Some(ch) => { cooked.push(ch); true }
+                    None => false
+                }
+            }
+            (Some('\r'), _) => {
+                self.skip();
+                self.matches('\n');
+                raw.push('\n');
+                true
+            }
+            (Some(ch), _) if ch.is_es_newline() => {
+                raw.push(self.reread(ch));
+                true
+            }
+            (Some(ch), _) => {
+                raw.push(self.reread(ch));
+                cooked.push(ch);
+                true
+            }
+            (None, _) => true // error will be reported from caller
+        }
+    }
+
     fn read_digit_into<F>(&mut self, s: &mut String, radix: u32, pred: &F, missing_digits: Error) -> Result<u32>
       where F: Fn(char) -> bool
     {
@@ -529,21 +799,59 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
 
     fn read_word_parts(&mut self) -> Result<Word> {
         let mut s = Word::new();
-        self.read_until_with(&|ch| ch != '\\' && !ch.is_es_identifier_continue(), &mut |this| {
-            match this.read() {
-                '\\' => this.read_word_escape(&mut s),
-                ch => { s.text.push(ch); Ok(()) }
-            }
-        })?;
+        self.read_into_until(&mut s.text, &|ch| !ch.is_es_identifier_continue());
+        self.read_word_escapes(&mut s)?;
         Ok(s)
     }
 
-    fn read_word(&mut self) -> Result<Token> {
+    // Reads the rest of a word from the next escape sequence on, if any.
+    fn read_word_escapes(&mut self, s: &mut Word) -> Result<()> {
+        while self.matches('\\') {
+            self.read_word_escape(s)?;
+            self.read_into_until(&mut s.text, &|ch| !ch.is_es_identifier_continue());
+        }
+        Ok(())
+    }
+
+    fn read_word(&mut self) -> Result<Token<'a>> {
         debug_assert!(self.peek().map_or(false, |ch| ch == '\\' || ch.is_es_identifier_start()));
         let span = self.start();
-        let s = self.read_word_parts()?;
-        debug_assert!(s.text.len() > 0);
-        Ok(span.end(self, self.wordmap.tokenize(s)?))
+        self.skip_while(&|ch| ch.is_es_identifier_continue());
+        // Words without escapes are looked up without copying them, and
+        // identifiers are borrowed from the source if it's there to borrow.
+        let value = if self.peek() == Some('\\') {
+            let s = self.read_escaped_word()?;
+            self.wordmap.tokenize(s)?
+        } else {
+            debug_assert!(self.reader.last_run().len() > 0);
+            match self.wordmap.tokenize_str(self.reader.last_run()) {
+                Some(value) => value,
+                None => TokenData::Identifier(Name::String(self.reader.text_from(span.start)))
+            }
+        };
+        Ok(span.end(self, value))
+    }
+
+    // Reads the rest of a word from its first escape on, after the run of
+    // chars just skipped.
+    fn read_escaped_word(&mut self) -> Result<Word> {
+        let mut s = Word::new();
+        s.text.push_str(self.reader.last_run());
+        self.read_word_escapes(&mut s)?;
+        Ok(s)
+    }
+
+    fn read_private_name(&mut self) -> Result<Token<'a>> {
+        let span = self.start();
+        self.reread('#');
+        let start = self.posn();
+        self.skip_while(&|ch| ch.is_es_identifier_continue());
+        let name = if self.peek() == Some('\\') {
+            Cow::Owned(self.read_escaped_word()?.text)
+        } else {
+            self.reader.text_from(start)
+        };
+        Ok(span.end(self, TokenData::PrivateName(name)))
     }
 
     fn read_word_escape(&mut self, s: &mut Word) -> Result<()> {
@@ -560,69 +868,95 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
         }
     }
 
-    fn read_punc(&mut self, value: TokenData) -> Token {
+    fn read_punc(&mut self, value: TokenData<'a>) -> Token<'a> {
         let span = self.start();
         self.skip();
         span.end(self, value)
     }
 
-    fn read_punc2(&mut self, value: TokenData) -> Token {
+    fn read_punc2(&mut self, value: TokenData<'a>) -> Token<'a> {
         let span = self.start();
         self.skip2();
         span.end(self, value)
     }
 
-    fn read_punc3(&mut self, value: TokenData) -> Token {
+    fn read_punc3(&mut self, value: TokenData<'a>) -> Token<'a> {
         let span = self.start();
         self.skip2();
         self.skip();
         span.end(self, value)
     }
 
-    fn read_punc2_3(&mut self, ch: char, value2: TokenData, value3: TokenData) -> Token {
+    fn read_punc2_3(&mut self, ch: char, value2: TokenData<'a>, value3: TokenData<'a>) -> Token<'a> {
         let span = self.start();
         self.skip2();
         let value = if self.matches(ch) { value3 } else { value2 };
         span.end(self, value)
     }
 
-    fn read_next_token(&mut self, operator: bool) -> Result<Token> {
-        let mut pair;
-        let mut found_newline = false;
-
-        // Skip whitespace and comments.
+    // Skips whitespace and comments.
+    fn skip_trivia(&mut self) -> Result<()> {
         loop {
-            pair = self.peek2();
-            match pair {
-                (Some(ch), _) if ch.is_es_whitespace() => { self.skip_whitespace(); }
+            let start = self.posn();
+            let kind = match self.peek2() {
+                (Some(ch), _) if ch.is_es_whitespace() => {
+                    self.skip_whitespace();
+                    TriviaKind::Whitespace
+                }
                 (Some(ch), _) if ch.is_es_newline() => {
                     self.skip_newlines();
-                    found_newline = true;
+                    self.found_newline = true;
                     self.empty_line = true;
+                    TriviaKind::Newlines
+                }
+                (Some('/'), Some('/')) => {
+                    self.skip_line_comment(2, CommentKind::Line);
+                    TriviaKind::Comment(CommentKind::Line)
+                }
+                (Some('#'), Some('!')) if self.posn().offset == 0 => {
+                    self.read_hashbang();
+                    TriviaKind::Hashbang
                 }
-                (Some('/'), Some('/')) => { self.skip_line_comment(2); }
                 (Some('/'), Some('*')) => {
-                    found_newline = self.skip_block_comment()? || found_newline;
+                    if self.skip_block_comment()? {
+                        self.found_newline = true;
+                        self.empty_line = true;
+                    }
+                    TriviaKind::Comment(CommentKind::Block)
                 }
-                (Some('<'), Some('!')) => {
+                (Some('<'), Some('!')) if self.html_comments => {
                     if self.reader.peek(2) == Some('-') && self.reader.peek(3) == Some('-') {
-                        self.skip_line_comment(4);
+                        self.skip_line_comment(4, CommentKind::Html);
+                        TriviaKind::Comment(CommentKind::Html)
                     } else {
                         break;
                     }
                 }
-                (Some('-'), Some('-')) => {
+                (Some('-'), Some('-')) if self.html_comments => {
                     if self.empty_line && self.reader.peek(2) == Some('>') {
-                        self.skip_line_comment(3);
+                        self.skip_line_comment(3, CommentKind::Html);
+                        TriviaKind::Comment(CommentKind::Html)
                     } else {
                         break;
                     }
                 }
                 _ => { break; }
+            };
+            if self.raw.is_some() {
+                let location = Span { start: start, end: self.posn() };
+                let source = self.take_raw();
+                self.trivia.push(Trivia { location: location, kind: kind, source: source });
             }
         }
+        Ok(())
+    }
 
+    fn read_next_token(&mut self, operator: bool) -> Result<Token<'a>> {
+        self.skip_trivia()?;
+        self.reader.mark();
+        let found_newline = replace(&mut self.found_newline, false);
         self.empty_line = false;
+        let pair = self.peek2();
 
         let mut result = match pair {
             (Some('/'), _) if !operator                  => self.read_regexp(),
@@ -669,6 +1003,7 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
             (Some('='), Some('='))                       => {
                 Ok(self.read_punc2_3('=', TokenData::Eq, TokenData::StrictEq))
             }
+            (Some('='), Some('>'))                       => Ok(self.read_punc2(TokenData::Arrow)),
             (Some('='), _)                               => Ok(self.read_punc(TokenData::Assign)),
             (Some('+'), Some('+'))                       => Ok(self.read_punc2(TokenData::Inc)),
             (Some('+'), Some('='))                       => {
@@ -680,6 +1015,9 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
                 Ok(self.read_punc2(TokenData::MinusAssign))
             }
             (Some('-'), _)                               => Ok(self.read_punc(TokenData::Minus)),
+            (Some('*'), Some('*'))                       => {
+                Ok(self.read_punc2_3('=', TokenData::StarStar, TokenData::StarStarAssign))
+            }
             (Some('*'), Some('='))                       => {
                 Ok(self.read_punc2(TokenData::StarAssign))
             }
@@ -693,14 +1031,14 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
             }
             (Some('^'), _)                               => Ok(self.read_punc(TokenData::BitXor)),
             (Some('&'), Some('&'))                       => {
-                Ok(self.read_punc2(TokenData::LogicalAnd))
+                Ok(self.read_punc2_3('=', TokenData::LogicalAnd, TokenData::LogicalAndAssign))
             }
             (Some('&'), Some('='))                       => {
                 Ok(self.read_punc2(TokenData::BitAndAssign))
             }
             (Some('&'), _)                               => Ok(self.read_punc(TokenData::BitAnd)),
             (Some('|'), Some('|'))                       => {
-                Ok(self.read_punc2(TokenData::LogicalOr))
+                Ok(self.read_punc2_3('=', TokenData::LogicalOr, TokenData::LogicalOrAssign))
             }
             (Some('|'), Some('='))                       => {
                 Ok(self.read_punc2(TokenData::BitOrAssign))
@@ -711,11 +1049,25 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
                 Ok(self.read_punc2_3('=', TokenData::NEq, TokenData::StrictNEq))
             }
             (Some('!'), _)                               => Ok(self.read_punc(TokenData::Bang)),
+            (Some('?'), Some('?'))                       => {
+                Ok(self.read_punc2_3('=', TokenData::Coalesce, TokenData::CoalesceAssign))
+            }
+            // `a?.5:b` is a conditional expression.
+            (Some('?'), Some('.'))                       => {
+                Ok(match self.reader.peek(2) {
+                    Some(ch) if ch.is_digit(10) => self.read_punc(TokenData::Question),
+                    _ => self.read_punc2(TokenData::QuestionDot)
+                })
+            }
             (Some('?'), _)                               => Ok(self.read_punc(TokenData::Question)),
             (Some('"'), _) | (Some('\''), _)             => self.read_string(),
+            (Some('`'), _)                               => self.read_template(),
             (Some(ch), _) if ch.is_digit(10)             => self.read_number(),
             (Some(ch), _) if ch.is_es_identifier_start() => self.read_word(),
             (Some('\\'), _)                              => self.read_word(),
+            (Some('#'), Some(ch)) if ch == '\\' || ch.is_es_identifier_start() => {
+                self.read_private_name()
+            }
             (Some(ch), _)                                => Err(Error::IllegalChar(ch)),
             (None, _)                                    => {
                 let here = self.posn();
@@ -723,8 +1075,24 @@ impl<I> Lexer<I> where I: Iterator<Item=char> {
             }
         }?;
         result.newline = found_newline;
+        if self.raw.is_some() {
+            self.record(&result);
+        }
         Ok(result)
     }
+
+    fn record(&mut self, token: &Token<'a>) {
+        // The parser may look for more tokens after the end of the source.
+        if token.value == TokenData::EOF && self.tokens.last().map_or(false, |last| last.token.value == TokenData::EOF) {
+            return;
+        }
+        let source = self.take_raw();
+        self.tokens.push(SourceToken {
+            leading: replace(&mut self.trivia, Vec::new()),
+            token: token.clone(),
+            source: source
+        });
+    }
 }
 
 #[cfg(test)]
@@ -733,16 +1101,24 @@ mod tests {
     use test::{deserialize_lexer_tests, LexerTest};
     use lexer::Lexer;
     use result::Result;
-    use token::{Token, TokenData};
+    use token::{Token, TokenData, NumberLiteral, NumberSource, StringLiteral};
+    use word::Name;
     use std;
+    use std::borrow::Cow;
+    use std::f64;
 
-    fn lex2(source: &String, operator: bool) -> Result<(Token, Token)> {
+    fn lex2(source: &str, operator: bool) -> Result<(Token<'static>, Token<'static>)> {
         let chars = source.chars();
         let mut lexer = Lexer::new(chars);
         Ok((lexer.read_token(operator)?, lexer.read_token(operator)?))
     }
 
-    fn assert_test2(source: &str, expected: &std::result::Result<TokenData, String>, expected_next: TokenData, actual: Result<(Token, Token)>) {
+    fn lex2_slice<'a>(source: &'a str, operator: bool) -> Result<(Token<'a>, Token<'a>)> {
+        let mut lexer = Lexer::from_slice(source);
+        Ok((lexer.read_token(operator)?, lexer.read_token(operator)?))
+    }
+
+    fn assert_test2<'a>(source: &str, expected: &std::result::Result<TokenData<'static>, String>, expected_next: TokenData<'static>, actual: Result<(Token<'a>, Token<'a>)>) {
         match (expected, &actual) {
             (&Ok(ref expected), &Ok((Token { value: ref actual, .. }, Token { value: ref actual_next, .. }))) => {
                 if expected != actual || &expected_next != actual_next {
@@ -766,12 +1142,150 @@ mod tests {
     #[test]
     pub fn go() {
         let tests = deserialize_lexer_tests(include_str!("../tests/unit.json"));
+        let lexers: [for<'a> fn(&'a str, bool) -> Result<(Token<'a>, Token<'a>)>; 2] = [lex2, lex2_slice];
         for LexerTest { source, operator, expected } in tests {
-            assert_test2(&source[..], &expected, TokenData::EOF, lex2(&source, operator));
-            assert_test2(&source[..], &expected, TokenData::EOF, lex2(&format!("{} ", source), operator));
-            assert_test2(&source[..], &expected, TokenData::EOF, lex2(&format!(" {}", source), operator));
-            assert_test2(&source[..], &expected, TokenData::EOF, lex2(&format!(" {} ", source), operator));
-            assert_test2(&source[..], &expected, TokenData::Semi, lex2(&format!("{};", source), operator));
+            for lex in lexers.iter() {
+                assert_test2(&source[..], &expected, TokenData::EOF, lex(&source, operator));
+                assert_test2(&source[..], &expected, TokenData::EOF, lex(&format!("{} ", source), operator));
+                assert_test2(&source[..], &expected, TokenData::EOF, lex(&format!(" {}", source), operator));
+                assert_test2(&source[..], &expected, TokenData::EOF, lex(&format!(" {} ", source), operator));
+                assert_test2(&source[..], &expected, TokenData::Semi, lex(&format!("{};", source), operator));
+            }
+        }
+    }
+
+    #[test]
+    pub fn slice_matches_chars() {
+        let source = "#!/usr/bin/env node\r\nlet ä\\u0062 = 'x\\\r\ny' /* \u{2028} */ + `a\rb\\u{41}c`;\rif (x)\u{feff}// 😀\n  return 1_000 + 0x1F.toString()";
+        let mut chars = Lexer::new(source.chars());
+        let mut slice = Lexer::from_slice(source);
+        loop {
+            let token = chars.read_token(false).unwrap();
+            assert_eq!(token, slice.read_token(false).unwrap());
+            if token.value == TokenData::EOF {
+                break;
+            }
+        }
+        assert_eq!(chars.hashbang(), slice.hashbang());
+    }
+
+    #[test]
+    pub fn hashbang() {
+        let mut lexer = Lexer::new("#!/usr/bin/env node\nx".chars());
+        assert_eq!(TokenData::Identifier(Name::String("x".into())), lexer.read_token(false).unwrap().value);
+        assert_eq!(Some("/usr/bin/env node"), lexer.hashbang());
+
+        // Only the very start of the source can hold one.
+        let mut lexer = Lexer::new(" #!/usr/bin/env node\nx".chars());
+        assert!(lexer.read_token(false).is_err());
+        assert_eq!(None, lexer.hashbang());
+    }
+
+    fn is_borrowed(s: &Cow<str>) -> bool {
+        match *s {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false
+        }
+    }
+
+    #[test]
+    pub fn slice_borrows_token_text() {
+        let mut lexer = Lexer::from_slice("foo 'bar' 12.5 #baz 1_0 'b\\x61z'");
+        let mut next = || lexer.read_token(false).unwrap().value;
+        match next() {
+            TokenData::Identifier(Name::String(ref name)) => assert!(is_borrowed(name)),
+            other => panic!("expected identifier, got {:?}", other)
+        }
+        match next() {
+            TokenData::String(StringLiteral { source: Some(ref source), ref value }) => {
+                assert!(is_borrowed(source) && is_borrowed(value));
+                assert_eq!(("'bar'", "bar"), (&source[..], &value[..]));
+            }
+            other => panic!("expected string, got {:?}", other)
+        }
+        match next() {
+            TokenData::Number(NumberLiteral { source: Some(NumberSource::Float(Some(ref int), Some(ref frac), None)), .. }) => {
+                assert!(is_borrowed(int) && is_borrowed(frac));
+            }
+            other => panic!("expected float, got {:?}", other)
+        }
+        match next() {
+            TokenData::PrivateName(ref name) => assert!(is_borrowed(name)),
+            other => panic!("expected private name, got {:?}", other)
+        }
+
+        // Separators and escapes make the text differ from the source.
+        match next() {
+            TokenData::Number(NumberLiteral { source: Some(NumberSource::DecimalInt(ref int, None)), .. }) => {
+                assert!(!is_borrowed(int));
+                assert_eq!("10", &int[..]);
+            }
+            other => panic!("expected integer, got {:?}", other)
+        }
+        match next() {
+            TokenData::String(StringLiteral { source: Some(ref source), ref value }) => {
+                assert!(is_borrowed(source) && !is_borrowed(value));
+                assert_eq!("baz", &value[..]);
+            }
+            other => panic!("expected string, got {:?}", other)
+        }
+    }
+
+    fn number_value(source: &str) -> f64 {
+        match lex2(&source.to_string(), false) {
+            Ok((Token { value: TokenData::Number(NumberLiteral { value, .. }), .. }, _)) => value,
+            other => { panic!("expected number for {:?}, got {:?}", source, other); }
+        }
+    }
+
+    #[test]
+    pub fn number_values() {
+        let long_int = format!("1{}", "0".repeat(400));
+        let long_frac = format!("0.{}1e401", "0".repeat(400));
+        let long_hex = format!("0x{}", "F".repeat(300));
+        let long_tie = format!("9007199254740993.{}", "0".repeat(400));
+        let long_above_tie = format!("9007199254740993.{}1", "0".repeat(400));
+        let tests: Vec<(&str, f64)> = vec![
+            ("0",                                     0.0),
+            ("0.1",                                   0.1),
+            ("1_000_000.000_001",                     1000000.000001),
+            ("07",                                    7.0),
+            ("0999",                                  999.0),
+            ("12.",                                   12.0),
+            (".5e1",                                  5.0),
+            ("1e23",                                  1e23),
+            ("8.41e21",                               8.41e21),
+            ("9007199254740993",                      9007199254740992.0),
+            ("9007199254740995",                      9007199254740996.0),
+            ("9007199254740993.0000000000000000000000000001", 9007199254740994.0),
+            ("123456789012345678901234567890",        1.2345678901234568e29),
+            ("1.7976931348623157e308",                1.7976931348623157e308),
+            ("1.7976931348623158e308",                1.7976931348623157e308),
+            ("1.7976931348623159e308",                f64::INFINITY),
+            ("2.2250738585072011e-308",               2.225073858507201e-308),
+            ("4.9e-324",                              5e-324),
+            ("2.4703282292062328e-324",               5e-324),
+            ("2.4703282292062327e-324",               0.0),
+            ("1e400",                                 f64::INFINITY),
+            ("1e-400",                                0.0),
+            ("1e99999999999999999999",                f64::INFINITY),
+            ("1e-99999999999999999999",               0.0),
+            (&long_int[..],                           f64::INFINITY),
+            (&long_frac[..],                          1.0),
+            (&long_tie[..],                           9007199254740992.0),
+            (&long_above_tie[..],                     9007199254740994.0),
+            ("0x1FFFFFFFFFFFFF",                      9007199254740991.0),
+            ("0x20000000000001",                      9007199254740992.0),
+            ("0x20000000000003",                      9007199254740996.0),
+            ("0x200000000000011",                     144115188075855904.0),
+            ("0xFFFFFFFFFFFFFFFFFF",                  4722366482869645213696.0),
+            ("0b111111111111111111111111111111111111111111111111111111", 18014398509481984.0),
+            ("0o777777777777777777777",               9223372036854775808.0),
+            (&long_hex[..],                           f64::INFINITY),
+        ];
+        for (source, expected) in tests {
+            let actual = number_value(source);
+            assert!(actual.to_bits() == expected.to_bits(), "{}: expected {:e}, got {:e}", source, expected, actual);
         }
     }
 
@@ -11,8 +11,9 @@ extern crate serde_json;
 pub mod word;
 pub mod token;
 pub mod lexer;
+pub mod regexp;
 mod char;
-mod reader;
+pub mod reader;
 mod test;
 pub mod track;
 pub mod error;
@@ -1,10 +1,52 @@
 use track::Posn;
+use std::borrow::Cow;
 use std::collections::VecDeque;
 
+// The source text the lexer reads from. Text handed out with the lifetime
+// `'a` is borrowed from the source, if the input holds onto it.
+pub trait Input<'a> {
+    fn peek(&mut self, n: usize) -> Option<char>;
+
+    fn next_char(&mut self) -> Option<char>;
+
+    fn curr_posn(&self) -> Posn;
+
+    // Skips chars while `pred` holds. The text skipped is available from
+    // `last_run` until the next call.
+    fn skip_while<F>(&mut self, pred: &F) where F: Fn(char) -> bool;
+
+    fn last_run(&self) -> &str;
+
+    // Marks the start of a token, so that its text can be had from
+    // `text_from`.
+    fn mark(&mut self);
+
+    // The text from `start`, which can't be before the last mark, up to
+    // the current position.
+    fn text_from(&self, start: Posn) -> Cow<'a, str>;
+}
+
+fn advance(posn: &mut Posn, ch: char, next: Option<char>) {
+    if (ch == '\r' && next != Some('\n')) ||
+       ch == '\n' ||
+       ch == '\u{2028}' ||
+       ch == '\u{2029}' {
+        posn.line += 1;
+        posn.column = 0;
+    } else {
+        posn.column += 1;
+    }
+    posn.offset += 1;
+    posn.byte_offset += ch.len_utf8() as u32;
+}
+
 pub struct Reader<I> {
     chars: I,
     ahead: VecDeque<char>,
-    curr_posn: Posn
+    curr_posn: Posn,
+    text: String,    // the chars read since the last mark
+    mark: usize,     // the byte offset of the last mark
+    run_start: usize // where the last run starts in `text`
 }
 
 impl<I> Reader<I> where I: Iterator<Item=char> {
@@ -12,7 +54,10 @@ impl<I> Reader<I> where I: Iterator<Item=char> {
         Reader {
             chars: chars,
             ahead: VecDeque::with_capacity(4),
-            curr_posn: Posn::origin()
+            curr_posn: Posn::origin(),
+            text: String::new(),
+            mark: 0,
+            run_start: 0
         }
     }
 
@@ -42,18 +87,146 @@ impl<I> Iterator for Reader<I> where I: Iterator<Item=char> {
             self.chars.next()
         });
 
-        if (curr_char == Some('\r') && self.peek(0) != Some('\n')) ||
-           curr_char == Some('\n') ||
-           curr_char == Some('\u{2028}') ||
-           curr_char == Some('\u{2029}') {
-            self.curr_posn.line += 1;
-            self.curr_posn.column = 0;
-        } else {
-            self.curr_posn.column += 1;
+        match curr_char {
+            Some(ch) => {
+                let next = if ch == '\r' { self.peek(0) } else { None };
+                advance(&mut self.curr_posn, ch, next);
+                self.text.push(ch);
+            }
+            None => {
+                self.curr_posn.column += 1;
+                self.curr_posn.offset += 1;
+            }
+        }
+
+        curr_char
+    }
+}
+
+// Chars are kept in a buffer from one mark to the next, so the text of
+// a token is copied out of it.
+impl<'a, I> Input<'a> for Reader<I> where I: Iterator<Item=char> {
+    fn peek(&mut self, n: usize) -> Option<char> { Reader::peek(self, n) }
+
+    fn next_char(&mut self) -> Option<char> { self.next() }
+
+    fn curr_posn(&self) -> Posn { self.curr_posn }
+
+    fn skip_while<F>(&mut self, pred: &F) where F: Fn(char) -> bool {
+        self.run_start = self.text.len();
+        while let Some(ch) = self.peek(0) {
+            if !pred(ch) {
+                break;
+            }
+            self.next();
+        }
+    }
+
+    fn last_run(&self) -> &str { &self.text[self.run_start..] }
+
+    fn mark(&mut self) {
+        self.text.clear();
+        self.mark = self.curr_posn.byte_offset as usize;
+        self.run_start = 0;
+    }
+
+    fn text_from(&self, start: Posn) -> Cow<'a, str> {
+        Cow::Owned(self.text[start.byte_offset as usize - self.mark..].to_string())
+    }
+}
+
+// Reads a string directly, scanning ASCII a byte at a time and handing
+// out runs as slices of the input.
+pub struct StrReader<'a> {
+    source: &'a str,
+    curr_posn: Posn,
+    run_start: usize
+}
+
+impl<'a> StrReader<'a> {
+    pub fn new(source: &'a str) -> StrReader<'a> {
+        StrReader {
+            source: source,
+            curr_posn: Posn::origin(),
+            run_start: 0
         }
+    }
 
-        self.curr_posn.offset += 1;
+    fn index(&self) -> usize {
+        self.curr_posn.byte_offset as usize
+    }
 
+    fn char_at(&self, i: usize) -> Option<char> {
+        match self.source.as_bytes().get(i) {
+            Some(&b) if b < 0x80 => Some(b as char),
+            Some(_) => self.source[i..].chars().next(),
+            None => None
+        }
+    }
+}
+
+impl<'a> Input<'a> for StrReader<'a> {
+    fn peek(&mut self, n: usize) -> Option<char> {
+        let mut i = self.index();
+        for _ in 0..n {
+            match self.char_at(i) {
+                Some(ch) => { i += ch.len_utf8(); }
+                None => { return None; }
+            }
+        }
+        self.char_at(i)
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let curr_char = self.char_at(self.index());
+        match curr_char {
+            Some(ch) => {
+                let next = if ch == '\r' { self.char_at(self.index() + 1) } else { None };
+                advance(&mut self.curr_posn, ch, next);
+            }
+            None => {
+                self.curr_posn.column += 1;
+                self.curr_posn.offset += 1;
+            }
+        }
         curr_char
     }
-}
\ No newline at end of file
+
+    fn curr_posn(&self) -> Posn { self.curr_posn }
+
+    fn skip_while<F>(&mut self, pred: &F) where F: Fn(char) -> bool {
+        self.run_start = self.index();
+        let bytes = self.source.as_bytes();
+        loop {
+            let i = self.index();
+            match bytes.get(i) {
+                // Within a line, ASCII only moves the position along by one.
+                Some(&b) if b < 0x80 && b != b'\r' && b != b'\n' => {
+                    if !pred(b as char) {
+                        return;
+                    }
+                    self.curr_posn.offset += 1;
+                    self.curr_posn.byte_offset += 1;
+                    self.curr_posn.column += 1;
+                }
+                Some(_) => {
+                    match self.char_at(i) {
+                        Some(ch) if pred(ch) => { self.next_char(); }
+                        _ => { return; }
+                    }
+                }
+                None => { return; }
+            }
+        }
+    }
+
+    fn last_run(&self) -> &str {
+        &self.source[self.run_start..self.index()]
+    }
+
+    fn mark(&mut self) {}
+
+    fn text_from(&self, start: Posn) -> Cow<'a, str> {
+        Cow::Borrowed(&self.source[start.byte_offset as usize..self.index()])
+    }
+}
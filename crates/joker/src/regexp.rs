@@ -0,0 +1,1310 @@
+use std::cmp;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use char::ESCharExt;
+use token::RegExpLiteral;
+use track::{Posn, Span};
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct Flags {
+    pub has_indices: bool,  // d
+    pub global: bool,       // g
+    pub ignore_case: bool,  // i
+    pub multiline: bool,    // m
+    pub dot_all: bool,      // s
+    pub unicode: bool,      // u
+    pub unicode_sets: bool, // v
+    pub sticky: bool        // y
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RegExp {
+    pub flags: Flags,
+    pub pattern: Disjunction
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Disjunction {
+    pub location: Span,
+    pub alternatives: Vec<Alternative>
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Alternative {
+    pub location: Span,
+    pub terms: Vec<Term>
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Term {
+    Assertion(Span, Assertion),
+    Atom(Span, Atom),
+    Quantified(Span, Box<Term>, Quantifier)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Assertion {
+    LineStart,
+    LineEnd,
+    WordBoundary,
+    NonWordBoundary,
+    Lookahead(bool, Disjunction),  // negated?
+    Lookbehind(bool, Disjunction)  // negated?
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Quantifier {
+    pub min: u32,
+    pub max: Option<u32>,
+    pub greedy: bool
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Atom {
+    Char(u32), // a code point, or a lone surrogate outside unicode mode
+    Dot,
+    Escape(SetEscape),
+    Class(Class),
+    Group(Option<String>, Disjunction),
+    NonCapturing(Disjunction),
+    Backreference(u32),
+    NamedBackreference(String)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SetEscape {
+    Digit(bool), // negated?
+    Word(bool),  // negated?
+    Space(bool), // negated?
+    Property(bool, String, Option<String>) // negated?, name or lone value, value
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Class {
+    pub location: Span,
+    pub negated: bool,
+    pub contents: ClassContents
+}
+
+// Intersections and subtractions only occur in `v` mode.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ClassContents {
+    Union(Vec<ClassItem>),
+    Intersection(Vec<ClassItem>),
+    Subtraction(Vec<ClassItem>)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ClassItem {
+    Char(u32),
+    Range(u32, u32),
+    Escape(SetEscape),
+    Class(Class),         // `v` mode only
+    Strings(Vec<Vec<u32>>) // `\q{...}`, `v` mode only
+}
+
+impl ClassContents {
+    fn may_contain_strings(&self) -> bool {
+        match *self {
+            ClassContents::Union(ref items) => items.iter().any(ClassItem::may_contain_strings),
+            ClassContents::Intersection(ref items) => items.iter().all(ClassItem::may_contain_strings),
+            ClassContents::Subtraction(ref items) => items.first().map_or(false, ClassItem::may_contain_strings)
+        }
+    }
+}
+
+impl ClassItem {
+    fn may_contain_strings(&self) -> bool {
+        match *self {
+            ClassItem::Class(ref class) => !class.negated && class.contents.may_contain_strings(),
+            ClassItem::Strings(ref strings) => strings.iter().any(|s| s.len() != 1),
+            ClassItem::Escape(SetEscape::Property(false, ref name, None)) => STRING_PROPERTIES.contains(&&name[..]),
+            _ => false
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    InvalidFlag(Span, char),
+    DuplicateFlag(Span, char),
+    IncompatibleFlags(Span),
+    NothingToRepeat(Span),
+    QuantifierOutOfOrder(Span),
+    UnexpectedChar(Span, char),
+    UnmatchedParen(Span),
+    UnterminatedGroup(Span),
+    InvalidGroup(Span),
+    InvalidGroupName(Span),
+    DuplicateGroupName(Span, String),
+    InvalidEscape(Span),
+    InvalidBackreference(Span),
+    InvalidProperty(Span),
+    UnterminatedClass(Span),
+    InvalidClassRange(Span),
+    ClassRangeOutOfOrder(Span),
+    InvalidClassOperation(Span),
+    NegatedStrings(Span)
+}
+
+impl Display for Error {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self {
+            &Error::InvalidFlag(_, ch) => {
+                fmt.write_fmt(format_args!("invalid regexp flag: {:?}", ch))
+            }
+            &Error::DuplicateFlag(_, ch) => {
+                fmt.write_fmt(format_args!("duplicate regexp flag: {:?}", ch))
+            }
+            &Error::IncompatibleFlags(_) => {
+                fmt.write_str("regexp flags 'u' and 'v' can't be combined")
+            }
+            &Error::NothingToRepeat(_) => {
+                fmt.write_str("nothing to repeat")
+            }
+            &Error::QuantifierOutOfOrder(_) => {
+                fmt.write_str("numbers out of order in quantifier")
+            }
+            &Error::UnexpectedChar(_, ch) => {
+                fmt.write_fmt(format_args!("unexpected character in regexp: {:?}", ch))
+            }
+            &Error::UnmatchedParen(_) => {
+                fmt.write_str("unmatched ')'")
+            }
+            &Error::UnterminatedGroup(_) => {
+                fmt.write_str("unterminated group")
+            }
+            &Error::InvalidGroup(_) => {
+                fmt.write_str("invalid group")
+            }
+            &Error::InvalidGroupName(_) => {
+                fmt.write_str("invalid capture group name")
+            }
+            &Error::DuplicateGroupName(_, ref name) => {
+                fmt.write_fmt(format_args!("duplicate capture group name: {}", name))
+            }
+            &Error::InvalidEscape(_) => {
+                fmt.write_str("invalid escape")
+            }
+            &Error::InvalidBackreference(_) => {
+                fmt.write_str("invalid backreference")
+            }
+            &Error::InvalidProperty(_) => {
+                fmt.write_str("invalid property name")
+            }
+            &Error::UnterminatedClass(_) => {
+                fmt.write_str("unterminated character class")
+            }
+            &Error::InvalidClassRange(_) => {
+                fmt.write_str("invalid character class range")
+            }
+            &Error::ClassRangeOutOfOrder(_) => {
+                fmt.write_str("range out of order in character class")
+            }
+            &Error::InvalidClassOperation(_) => {
+                fmt.write_str("invalid set operation in character class")
+            }
+            &Error::NegatedStrings(_) => {
+                fmt.write_str("negated character class may contain strings")
+            }
+        }
+    }
+}
+
+type Result<T> = ::std::result::Result<T, Error>;
+
+// Parses the pattern and flags of a regexp literal that starts at `start`.
+// The Annex B grammar of web browsers is used if `annex_b` is set and the
+// literal has neither the `u` nor the `v` flag.
+pub fn parse(literal: &RegExpLiteral, start: Posn, annex_b: bool) -> Result<RegExp> {
+    let chars: Vec<char> = literal.pattern.chars().collect();
+    // The flags come after the pattern and both of its slashes.
+    let flags = parse_flags(&literal.flags, advance(advance(start, &chars), &['/', '/']))?;
+    let mut parser = PatternParser::new(chars, start, flags, annex_b);
+    let pattern = parser.pattern()?;
+    Ok(RegExp { flags: flags, pattern: pattern })
+}
+
+// Regexp literals never span lines.
+fn advance(posn: Posn, chars: &[char]) -> Posn {
+    Posn {
+        offset: posn.offset + chars.len() as u32,
+        byte_offset: posn.byte_offset + chars.iter().map(|ch| ch.len_utf8() as u32).sum::<u32>(),
+        line: posn.line,
+        column: posn.column + chars.len() as u32
+    }
+}
+
+fn parse_flags(chars: &[char], start: Posn) -> Result<Flags> {
+    let mut flags = Flags::default();
+    for (i, &ch) in chars.iter().enumerate() {
+        let span = Span { start: advance(start, &chars[..i]), end: advance(start, &chars[..i + 1]) };
+        {
+            let flag = match ch {
+                'd' => &mut flags.has_indices,
+                'g' => &mut flags.global,
+                'i' => &mut flags.ignore_case,
+                'm' => &mut flags.multiline,
+                's' => &mut flags.dot_all,
+                'u' => &mut flags.unicode,
+                'v' => &mut flags.unicode_sets,
+                'y' => &mut flags.sticky,
+                _ => { return Err(Error::InvalidFlag(span, ch)); }
+            };
+            if *flag {
+                return Err(Error::DuplicateFlag(span, ch));
+            }
+            *flag = true;
+        }
+        if flags.unicode && flags.unicode_sets {
+            return Err(Error::IncompatibleFlags(span));
+        }
+    }
+    Ok(flags)
+}
+
+// Capturing groups are counted up front, since a backreference can come
+// before the group it refers to.
+fn scan_groups(chars: &[char], sets: bool) -> (u32, bool) {
+    let mut count = 0;
+    let mut named = false;
+    let mut class_depth = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => { i += 1; }
+            '[' if sets || class_depth == 0 => { class_depth += 1; }
+            ']' if class_depth > 0 => { class_depth -= 1; }
+            '(' if class_depth == 0 => {
+                match (chars.get(i + 1), chars.get(i + 2), chars.get(i + 3)) {
+                    (Some(&'?'), Some(&'<'), Some(&'=')) | (Some(&'?'), Some(&'<'), Some(&'!')) => { }
+                    (Some(&'?'), Some(&'<'), _) => {
+                        count += 1;
+                        named = true;
+                    }
+                    (Some(&'?'), _, _) => { }
+                    _ => { count += 1; }
+                }
+            }
+            _ => { }
+        }
+        i += 1;
+    }
+    (count, named)
+}
+
+// Two groups may share a name only if they sit in different alternatives
+// of the same disjunction, so that at most one of them can participate.
+fn exclusive(a: &[(usize, usize)], b: &[(usize, usize)]) -> bool {
+    a.iter().zip(b).find(|&(x, y)| x != y).map_or(false, |(x, y)| x.0 == y.0)
+}
+
+fn is_syntax_char(ch: char) -> bool {
+    match ch {
+        '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' => true,
+        _ => false
+    }
+}
+
+fn is_class_set_syntax_char(ch: char) -> bool {
+    match ch {
+        '(' | ')' | '[' | ']' | '{' | '}' | '/' | '-' | '\\' | '|' => true,
+        _ => false
+    }
+}
+
+fn is_class_set_reserved_punctuator(ch: char) -> bool {
+    match ch {
+        '&' | '-' | '!' | '#' | '%' | ',' | ':' | ';' | '<' | '=' | '>' | '@' | '`' | '~' => true,
+        _ => false
+    }
+}
+
+// The first character of a reserved double punctuator such as `&&` or `!!`.
+fn is_class_set_double_punctuator(ch: char) -> bool {
+    match ch {
+        '&' | '!' | '#' | '$' | '%' | '*' | '+' | ',' | '.' | ':' | ';' | '<' | '=' | '>' | '?' | '@' | '^' | '`' | '~' => true,
+        _ => false
+    }
+}
+
+struct PatternParser {
+    chars: Vec<char>,
+    bytes: Vec<u32>,                               // UTF-8 length of the pattern up to each char
+    pos: usize,
+    start: Posn,
+    unicode: bool,                                 // `u` or `v` mode
+    sets: bool,                                    // `v` mode
+    annex_b: bool,
+    group_count: u32,
+    named_groups: bool,
+    groups: Vec<(String, Vec<(usize, usize)>)>,    // names and the alternatives they sit in
+    path: Vec<(usize, usize)>,                     // (disjunction, alternative) being parsed
+    disjunctions: usize,
+    references: Vec<(String, Span)>                // named backreferences to resolve
+}
+
+impl PatternParser {
+    fn new(chars: Vec<char>, start: Posn, flags: Flags, annex_b: bool) -> PatternParser {
+        let unicode = flags.unicode || flags.unicode_sets;
+        let (group_count, named_groups) = scan_groups(&chars, flags.unicode_sets);
+        let mut bytes = vec![0];
+        for ch in &chars {
+            let len = bytes[bytes.len() - 1] + ch.len_utf8() as u32;
+            bytes.push(len);
+        }
+        PatternParser {
+            chars: chars,
+            bytes: bytes,
+            pos: 0,
+            start: start,
+            unicode: unicode,
+            sets: flags.unicode_sets,
+            annex_b: annex_b && !unicode,
+            group_count: group_count,
+            named_groups: named_groups,
+            groups: Vec::new(),
+            path: Vec::new(),
+            disjunctions: 0,
+            references: Vec::new()
+        }
+    }
+
+    // source location
+
+    // Skips the opening slash of the literal.
+    fn posn(&self, i: usize) -> Posn {
+        Posn {
+            offset: self.start.offset + 1 + i as u32,
+            byte_offset: self.start.byte_offset + 1 + self.bytes[i],
+            line: self.start.line,
+            column: self.start.column + 1 + i as u32
+        }
+    }
+
+    fn span_from(&self, start: usize) -> Span {
+        Span { start: self.posn(start), end: self.posn(self.pos) }
+    }
+
+    fn span_at(&self, i: usize) -> Span {
+        Span { start: self.posn(i), end: self.posn(i + 1) }
+    }
+
+    // generic parsing utilities
+
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, n: usize) -> Option<char> {
+        self.chars.get(self.pos + n).cloned()
+    }
+
+    fn eat(&mut self, ch: char) -> bool {
+        if self.peek() == Some(ch) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn decimal(&self, pos: &mut usize) -> Option<u32> {
+        let start = *pos;
+        let mut value: u32 = 0;
+        while let Some(digit) = self.chars.get(*pos).and_then(|ch| ch.to_digit(10)) {
+            value = value.saturating_mul(10).saturating_add(digit);
+            *pos += 1;
+        }
+        if *pos == start { None } else { Some(value) }
+    }
+
+    fn hex_digits(&mut self, n: usize) -> Option<u32> {
+        let mut value = 0;
+        for i in 0..n {
+            match self.peek_at(i).and_then(|ch| ch.to_digit(16)) {
+                Some(digit) => { value = (value << 4) + digit; }
+                None => { return None; }
+            }
+        }
+        self.pos += n;
+        Some(value)
+    }
+
+    // patterns
+
+    fn pattern(&mut self) -> Result<Disjunction> {
+        let disjunction = self.disjunction()?;
+        // Only an unmatched `)` stops the outermost disjunction early.
+        if self.pos < self.chars.len() {
+            return Err(Error::UnmatchedParen(self.span_at(self.pos)));
+        }
+        for &(ref name, span) in &self.references {
+            if !self.groups.iter().any(|&(ref group, _)| group == name) {
+                return Err(Error::InvalidBackreference(span));
+            }
+        }
+        Ok(disjunction)
+    }
+
+    fn disjunction(&mut self) -> Result<Disjunction> {
+        let start = self.pos;
+        let id = self.disjunctions;
+        self.disjunctions += 1;
+        let mut alternatives = Vec::new();
+        loop {
+            self.path.push((id, alternatives.len()));
+            let alternative = self.alternative();
+            self.path.pop();
+            alternatives.push(alternative?);
+            if !self.eat('|') {
+                break;
+            }
+        }
+        Ok(Disjunction { location: self.span_from(start), alternatives: alternatives })
+    }
+
+    fn alternative(&mut self) -> Result<Alternative> {
+        let start = self.pos;
+        let mut terms = Vec::new();
+        loop {
+            match self.peek() {
+                None | Some('|') | Some(')') => { break; }
+                _ => { terms.push(self.term()?); }
+            }
+        }
+        Ok(Alternative { location: self.span_from(start), terms: terms })
+    }
+
+    fn term(&mut self) -> Result<Term> {
+        let start = self.pos;
+        let (term, quantifiable) = if let Some((behind, negated)) = self.lookaround() {
+            self.pos += if behind { 4 } else { 3 };
+            let disjunction = self.disjunction()?;
+            if !self.eat(')') {
+                return Err(Error::UnterminatedGroup(self.span_from(start)));
+            }
+            let assertion = if behind {
+                Assertion::Lookbehind(negated, disjunction)
+            } else {
+                Assertion::Lookahead(negated, disjunction)
+            };
+            // Annex B lets lookaheads be quantified.
+            (Term::Assertion(self.span_from(start), assertion), !behind && self.annex_b)
+        } else if let Some(assertion) = self.simple_assertion() {
+            (Term::Assertion(self.span_from(start), assertion), false)
+        } else {
+            let atom = self.atom()?;
+            (Term::Atom(self.span_from(start), atom), true)
+        };
+        let quantifier_start = self.pos;
+        match self.quantifier()? {
+            Some(_) if !quantifiable => Err(Error::NothingToRepeat(self.span_from(quantifier_start))),
+            Some(quantifier) => Ok(Term::Quantified(self.span_from(start), Box::new(term), quantifier)),
+            None => Ok(term)
+        }
+    }
+
+    // Returns whether a lookaround is a lookbehind, and whether it's negated.
+    fn lookaround(&self) -> Option<(bool, bool)> {
+        if self.peek() != Some('(') || self.peek_at(1) != Some('?') {
+            return None;
+        }
+        match (self.peek_at(2), self.peek_at(3)) {
+            (Some('='), _) => Some((false, false)),
+            (Some('!'), _) => Some((false, true)),
+            (Some('<'), Some('=')) => Some((true, false)),
+            (Some('<'), Some('!')) => Some((true, true)),
+            _ => None
+        }
+    }
+
+    fn simple_assertion(&mut self) -> Option<Assertion> {
+        let (assertion, len) = match (self.peek(), self.peek_at(1)) {
+            (Some('^'), _) => (Assertion::LineStart, 1),
+            (Some('$'), _) => (Assertion::LineEnd, 1),
+            (Some('\\'), Some('b')) => (Assertion::WordBoundary, 2),
+            (Some('\\'), Some('B')) => (Assertion::NonWordBoundary, 2),
+            _ => { return None; }
+        };
+        self.pos += len;
+        Some(assertion)
+    }
+
+    fn quantifier(&mut self) -> Result<Option<Quantifier>> {
+        let start = self.pos;
+        let (min, max) = match self.peek() {
+            Some('*') => { self.pos += 1; (0, None) }
+            Some('+') => { self.pos += 1; (1, None) }
+            Some('?') => { self.pos += 1; (0, Some(1)) }
+            Some('{') => {
+                match self.braced_quantifier() {
+                    Some((min, max, end)) => {
+                        self.pos = end;
+                        if max.map_or(false, |max| max < min) {
+                            return Err(Error::QuantifierOutOfOrder(self.span_from(start)));
+                        }
+                        (min, max)
+                    }
+                    None => { return Ok(None); }
+                }
+            }
+            _ => { return Ok(None); }
+        };
+        let greedy = !self.eat('?');
+        Ok(Some(Quantifier { min: min, max: max, greedy: greedy }))
+    }
+
+    // Scans a `{n}`, `{n,}` or `{n,m}` quantifier without consuming it, and
+    // returns its bounds and where it ends.
+    fn braced_quantifier(&self) -> Option<(u32, Option<u32>, usize)> {
+        let mut pos = self.pos + 1;
+        let min = self.decimal(&mut pos)?;
+        let max = if self.chars.get(pos) == Some(&',') {
+            pos += 1;
+            if self.chars.get(pos) == Some(&'}') {
+                None
+            } else {
+                Some(self.decimal(&mut pos)?)
+            }
+        } else {
+            Some(min)
+        };
+        if self.chars.get(pos) != Some(&'}') {
+            return None;
+        }
+        Some((min, max, pos + 1))
+    }
+
+    // atoms
+
+    fn atom(&mut self) -> Result<Atom> {
+        let start = self.pos;
+        let ch = self.peek().unwrap();
+        match ch {
+            '.' => {
+                self.pos += 1;
+                Ok(Atom::Dot)
+            }
+            '(' => self.group(),
+            '[' => self.class().map(Atom::Class),
+            '\\' => self.atom_escape(),
+            '*' | '+' | '?' => Err(Error::NothingToRepeat(self.span_at(start))),
+            '{' if self.braced_quantifier().is_some() => {
+                let (_, _, end) = self.braced_quantifier().unwrap();
+                self.pos = end;
+                Err(Error::NothingToRepeat(self.span_from(start)))
+            }
+            // Annex B treats lone brackets as literal characters.
+            '{' | '}' | ']' if !self.annex_b => Err(Error::UnexpectedChar(self.span_at(start), ch)),
+            _ => {
+                self.pos += 1;
+                Ok(Atom::Char(ch as u32))
+            }
+        }
+    }
+
+    fn group(&mut self) -> Result<Atom> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut name = None;
+        let capturing = if self.eat('?') {
+            match self.peek() {
+                Some(':') => {
+                    self.pos += 1;
+                    false
+                }
+                Some('<') => {
+                    self.pos += 1;
+                    name = Some(self.group_declaration()?);
+                    true
+                }
+                _ => { return Err(Error::InvalidGroup(self.span_from(start))); }
+            }
+        } else {
+            true
+        };
+        let disjunction = self.disjunction()?;
+        if !self.eat(')') {
+            return Err(Error::UnterminatedGroup(self.span_from(start)));
+        }
+        Ok(if capturing {
+            Atom::Group(name, disjunction)
+        } else {
+            Atom::NonCapturing(disjunction)
+        })
+    }
+
+    fn group_declaration(&mut self) -> Result<String> {
+        let start = self.pos;
+        let name = self.group_name()?;
+        if self.groups.iter().any(|&(ref other, ref path)| *other == name && !exclusive(path, &self.path)) {
+            return Err(Error::DuplicateGroupName(self.span_from(start), name));
+        }
+        self.groups.push((name.clone(), self.path.clone()));
+        Ok(name)
+    }
+
+    // Reads a group name up to and including the closing `>`.
+    fn group_name(&mut self) -> Result<String> {
+        let start = self.pos;
+        let mut name = String::new();
+        loop {
+            let ch = match self.peek() {
+                Some('>') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    if !self.eat('u') {
+                        return Err(Error::InvalidGroupName(self.span_from(start)));
+                    }
+                    match self.unicode_escape(true).and_then(::std::char::from_u32) {
+                        Some(ch) => ch,
+                        None => { return Err(Error::InvalidGroupName(self.span_from(start))); }
+                    }
+                }
+                Some(ch) => {
+                    self.pos += 1;
+                    ch
+                }
+                None => { return Err(Error::InvalidGroupName(self.span_from(start))); }
+            };
+            let valid = if name.is_empty() {
+                ch.is_es_identifier_start()
+            } else {
+                ch.is_es_identifier_continue() || ch == '\u{200C}' || ch == '\u{200D}'
+            };
+            if !valid {
+                return Err(Error::InvalidGroupName(self.span_from(start)));
+            }
+            name.push(ch);
+        }
+        if name.is_empty() {
+            return Err(Error::InvalidGroupName(self.span_from(start)));
+        }
+        Ok(name)
+    }
+
+    // escapes
+
+    fn atom_escape(&mut self) -> Result<Atom> {
+        let start = self.pos;
+        self.pos += 1;
+        match self.peek() {
+            None => Err(Error::InvalidEscape(self.span_from(start))),
+            Some(ch) if ch.is_digit(10) && ch != '0' => {
+                let mut end = self.pos;
+                let n = self.decimal(&mut end).unwrap();
+                if n <= self.group_count {
+                    self.pos = end;
+                    return Ok(Atom::Backreference(n));
+                }
+                // Annex B reads it as a legacy octal or identity escape instead.
+                if !self.annex_b {
+                    self.pos = end;
+                    return Err(Error::InvalidBackreference(self.span_from(start)));
+                }
+                self.character_escape(start, false).map(Atom::Char)
+            }
+            Some('k') if self.unicode || self.named_groups || !self.annex_b => {
+                self.pos += 1;
+                if !self.eat('<') {
+                    return Err(Error::InvalidEscape(self.span_from(start)));
+                }
+                let name = self.group_name()?;
+                self.references.push((name.clone(), self.span_from(start)));
+                Ok(Atom::NamedBackreference(name))
+            }
+            Some(_) => {
+                match self.set_escape()? {
+                    Some(escape) => Ok(Atom::Escape(escape)),
+                    None => self.character_escape(start, false).map(Atom::Char)
+                }
+            }
+        }
+    }
+
+    // Reads the rest of a character escape whose backslash is at `start`.
+    fn character_escape(&mut self, start: usize, in_class: bool) -> Result<u32> {
+        let ch = match self.peek() {
+            Some(ch) => ch,
+            None => { return Err(Error::InvalidEscape(self.span_from(start))); }
+        };
+        self.pos += 1;
+        Ok(match ch {
+            'f' => 0x0c,
+            'n' => 0x0a,
+            'r' => 0x0d,
+            't' => 0x09,
+            'v' => 0x0b,
+            'c' => {
+                match self.peek() {
+                    Some(letter) if letter.is_ascii_alphabetic() => {
+                        self.pos += 1;
+                        letter as u32 % 32
+                    }
+                    Some(ch) if in_class && self.annex_b && (ch.is_digit(10) || ch == '_') => {
+                        self.pos += 1;
+                        ch as u32 % 32
+                    }
+                    // Annex B reads the backslash by itself, and the `c` after it.
+                    _ if self.annex_b => {
+                        self.pos -= 1;
+                        '\\' as u32
+                    }
+                    _ => { return Err(Error::InvalidEscape(self.span_from(start))); }
+                }
+            }
+            '0' if !self.peek().map_or(false, |ch| ch.is_digit(10)) => 0,
+            _ if self.annex_b && ch.is_digit(8) => self.legacy_octal(ch),
+            'x' => {
+                match self.hex_digits(2) {
+                    Some(value) => value,
+                    None if self.annex_b => 'x' as u32,
+                    None => { return Err(Error::InvalidEscape(self.span_from(start))); }
+                }
+            }
+            'u' => {
+                let unicode = self.unicode;
+                match self.unicode_escape(unicode) {
+                    Some(value) => value,
+                    None if self.annex_b => 'u' as u32,
+                    None => { return Err(Error::InvalidEscape(self.span_from(start))); }
+                }
+            }
+            '-' if in_class && self.unicode => '-' as u32,
+            _ if self.identity_escape(ch, in_class) => ch as u32,
+            _ => { return Err(Error::InvalidEscape(self.span_from(start))); }
+        })
+    }
+
+    fn identity_escape(&self, ch: char, in_class: bool) -> bool {
+        if self.unicode {
+            is_syntax_char(ch) || ch == '/' || (in_class && self.sets && is_class_set_reserved_punctuator(ch))
+        } else if self.annex_b {
+            ch != 'c' && (ch != 'k' || !self.named_groups)
+        } else {
+            ch == '$' || !ch.is_es_identifier_continue()
+        }
+    }
+
+    fn legacy_octal(&mut self, first: char) -> u32 {
+        let mut value = first.to_digit(8).unwrap();
+        let more = if value <= 3 { 2 } else { 1 };
+        for _ in 0..more {
+            match self.peek().and_then(|ch| ch.to_digit(8)) {
+                Some(digit) => {
+                    value = (value << 3) + digit;
+                    self.pos += 1;
+                }
+                None => { break; }
+            }
+        }
+        value
+    }
+
+    // Reads the rest of a `\u` escape. Braces and surrogate pairs are only
+    // recognized in unicode mode. A malformed escape consumes nothing.
+    fn unicode_escape(&mut self, unicode: bool) -> Option<u32> {
+        let start = self.pos;
+        let value = if unicode && self.eat('{') {
+            let digits = self.pos;
+            let mut value = 0;
+            while let Some(digit) = self.peek().and_then(|ch| ch.to_digit(16)) {
+                value = cmp::min((value << 4) + digit, 0x110000);
+                self.pos += 1;
+            }
+            if self.pos == digits || value > 0x10ffff || !self.eat('}') {
+                self.pos = start;
+                return None;
+            }
+            value
+        } else {
+            self.hex_digits(4)?
+        };
+        if unicode && value >= 0xd800 && value <= 0xdbff && self.peek() == Some('\\') && self.peek_at(1) == Some('u') {
+            let lead_end = self.pos;
+            self.pos += 2;
+            match self.hex_digits(4) {
+                Some(trail) if trail >= 0xdc00 && trail <= 0xdfff => {
+                    return Some(0x10000 + ((value - 0xd800) << 10) + (trail - 0xdc00));
+                }
+                _ => { self.pos = lead_end; }
+            }
+        }
+        Some(value)
+    }
+
+    // Reads a `\d`, `\w`, `\s` or unicode property escape, if there is one
+    // after the backslash.
+    fn set_escape(&mut self) -> Result<Option<SetEscape>> {
+        let escape = match self.peek() {
+            Some('d') => SetEscape::Digit(false),
+            Some('D') => SetEscape::Digit(true),
+            Some('w') => SetEscape::Word(false),
+            Some('W') => SetEscape::Word(true),
+            Some('s') => SetEscape::Space(false),
+            Some('S') => SetEscape::Space(true),
+            Some('p') | Some('P') if self.unicode => { return self.property().map(Some); }
+            _ => { return Ok(None); }
+        };
+        self.pos += 1;
+        Ok(Some(escape))
+    }
+
+    fn property(&mut self) -> Result<SetEscape> {
+        let start = self.pos - 1;
+        let negated = self.peek() == Some('P');
+        self.pos += 1;
+        if !self.eat('{') {
+            return Err(Error::InvalidProperty(self.span_from(start)));
+        }
+        let body_start = self.pos;
+        while self.peek().map_or(false, |ch| ch != '}') {
+            self.pos += 1;
+        }
+        if !self.eat('}') {
+            return Err(Error::InvalidProperty(self.span_from(start)));
+        }
+        let body: String = self.chars[body_start..self.pos - 1].iter().cloned().collect();
+        let span = self.span_from(start);
+        let (name, value) = match body.find('=') {
+            Some(i) => (&body[..i], Some(&body[i + 1..])),
+            None => (&body[..], None)
+        };
+        let valid = match value {
+            Some(value) => {
+                match name {
+                    "General_Category" | "gc" => GENERAL_CATEGORIES.contains(&value),
+                    "Script" | "sc" | "Script_Extensions" | "scx" => SCRIPTS.contains(&value),
+                    _ => false
+                }
+            }
+            None => {
+                GENERAL_CATEGORIES.contains(&name) ||
+                BINARY_PROPERTIES.contains(&name) ||
+                (self.sets && STRING_PROPERTIES.contains(&name))
+            }
+        };
+        if !valid {
+            return Err(Error::InvalidProperty(span));
+        }
+        if negated && value.is_none() && STRING_PROPERTIES.contains(&name) {
+            return Err(Error::NegatedStrings(span));
+        }
+        Ok(SetEscape::Property(negated, name.to_string(), value.map(String::from)))
+    }
+
+    // classes
+
+    fn class(&mut self) -> Result<Class> {
+        if self.sets {
+            return self.class_set();
+        }
+        let start = self.pos;
+        self.pos += 1;
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        loop {
+            match self.peek() {
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                None => { return Err(Error::UnterminatedClass(self.span_from(start))); }
+                _ => { }
+            }
+            let item_start = self.pos;
+            let first = self.class_atom(start)?;
+            if self.peek() != Some('-') || self.peek_at(1).map_or(true, |ch| ch == ']') {
+                items.push(first);
+                continue;
+            }
+            self.pos += 1;
+            let second = self.class_atom(start)?;
+            match (first, second) {
+                (ClassItem::Char(from), ClassItem::Char(to)) => {
+                    if from > to {
+                        return Err(Error::ClassRangeOutOfOrder(self.span_from(item_start)));
+                    }
+                    items.push(ClassItem::Range(from, to));
+                }
+                // Annex B reads a range with a class escape as a union.
+                (first, second) => {
+                    if !self.annex_b {
+                        return Err(Error::InvalidClassRange(self.span_from(item_start)));
+                    }
+                    items.push(first);
+                    items.push(ClassItem::Char('-' as u32));
+                    items.push(second);
+                }
+            }
+        }
+        Ok(Class {
+            location: self.span_from(start),
+            negated: negated,
+            contents: ClassContents::Union(items)
+        })
+    }
+
+    fn class_atom(&mut self, class_start: usize) -> Result<ClassItem> {
+        let start = self.pos;
+        match self.peek() {
+            Some('\\') => {
+                self.pos += 1;
+                if let Some(escape) = self.set_escape()? {
+                    return Ok(ClassItem::Escape(escape));
+                }
+                if self.eat('b') {
+                    return Ok(ClassItem::Char(0x08));
+                }
+                self.character_escape(start, true).map(ClassItem::Char)
+            }
+            Some(ch) => {
+                self.pos += 1;
+                Ok(ClassItem::Char(ch as u32))
+            }
+            None => Err(Error::UnterminatedClass(self.span_from(class_start)))
+        }
+    }
+
+    fn class_set(&mut self) -> Result<Class> {
+        let start = self.pos;
+        self.pos += 1;
+        let negated = self.eat('^');
+        let contents = if self.peek() == Some(']') {
+            ClassContents::Union(Vec::new())
+        } else {
+            let first_start = self.pos;
+            let first = self.class_set_operand(start)?;
+            match (self.peek(), self.peek_at(1)) {
+                (Some('&'), Some('&')) => ClassContents::Intersection(self.class_set_operands(start, first, '&')?),
+                (Some('-'), Some('-')) => ClassContents::Subtraction(self.class_set_operands(start, first, '-')?),
+                _ => ClassContents::Union(self.class_set_union(start, first, first_start)?)
+            }
+        };
+        match self.peek() {
+            Some(']') => { self.pos += 1; }
+            Some(_) => { return Err(Error::InvalidClassOperation(self.span_at(self.pos))); }
+            None => { return Err(Error::UnterminatedClass(self.span_from(start))); }
+        }
+        if negated && contents.may_contain_strings() {
+            return Err(Error::NegatedStrings(self.span_from(start)));
+        }
+        Ok(Class {
+            location: self.span_from(start),
+            negated: negated,
+            contents: contents
+        })
+    }
+
+    fn class_set_operands(&mut self, class_start: usize, first: ClassItem, op: char) -> Result<Vec<ClassItem>> {
+        let mut operands = vec![first];
+        while self.peek() == Some(op) && self.peek_at(1) == Some(op) {
+            self.pos += 2;
+            if op == '&' && self.peek() == Some('&') {
+                return Err(Error::InvalidClassOperation(self.span_at(self.pos)));
+            }
+            operands.push(self.class_set_operand(class_start)?);
+        }
+        Ok(operands)
+    }
+
+    fn class_set_union(&mut self, class_start: usize, first: ClassItem, first_start: usize) -> Result<Vec<ClassItem>> {
+        let mut items = Vec::new();
+        let mut next = Some((first, first_start));
+        loop {
+            let (item, item_start) = match next.take() {
+                Some(item) => item,
+                None => {
+                    match (self.peek(), self.peek_at(1)) {
+                        (Some(']'), _) | (None, _) => { break; }
+                        (Some('&'), Some('&')) | (Some('-'), Some('-')) => {
+                            return Err(Error::InvalidClassOperation(self.span_at(self.pos)));
+                        }
+                        _ => { }
+                    }
+                    let item_start = self.pos;
+                    (self.class_set_operand(class_start)?, item_start)
+                }
+            };
+            if self.peek() != Some('-') || self.peek_at(1) == Some('-') {
+                items.push(item);
+                continue;
+            }
+            self.pos += 1;
+            let second = self.class_set_operand(class_start)?;
+            match (item, second) {
+                (ClassItem::Char(from), ClassItem::Char(to)) => {
+                    if from > to {
+                        return Err(Error::ClassRangeOutOfOrder(self.span_from(item_start)));
+                    }
+                    items.push(ClassItem::Range(from, to));
+                }
+                _ => { return Err(Error::InvalidClassRange(self.span_from(item_start))); }
+            }
+        }
+        Ok(items)
+    }
+
+    fn class_set_operand(&mut self, class_start: usize) -> Result<ClassItem> {
+        let start = self.pos;
+        match (self.peek(), self.peek_at(1)) {
+            (Some('['), _) => self.class_set().map(ClassItem::Class),
+            (Some('\\'), Some('q')) => {
+                self.pos += 2;
+                self.class_strings(start)
+            }
+            (Some('\\'), _) => {
+                self.pos += 1;
+                match self.set_escape()? {
+                    Some(escape) => Ok(ClassItem::Escape(escape)),
+                    None => {
+                        self.pos = start;
+                        self.class_set_char(class_start).map(ClassItem::Char)
+                    }
+                }
+            }
+            _ => self.class_set_char(class_start).map(ClassItem::Char)
+        }
+    }
+
+    fn class_set_char(&mut self, class_start: usize) -> Result<u32> {
+        let start = self.pos;
+        match (self.peek(), self.peek_at(1)) {
+            (Some('\\'), _) => {
+                self.pos += 1;
+                if self.eat('b') {
+                    return Ok(0x08);
+                }
+                self.character_escape(start, true)
+            }
+            (Some(ch), Some(next)) if ch == next && is_class_set_double_punctuator(ch) => {
+                self.pos += 2;
+                Err(Error::InvalidClassOperation(self.span_from(start)))
+            }
+            (Some(ch), _) if is_class_set_syntax_char(ch) => Err(Error::UnexpectedChar(self.span_at(start), ch)),
+            (Some(ch), _) => {
+                self.pos += 1;
+                Ok(ch as u32)
+            }
+            (None, _) => Err(Error::UnterminatedClass(self.span_from(class_start)))
+        }
+    }
+
+    // Reads the braces of a `\q{...}` escape.
+    fn class_strings(&mut self, start: usize) -> Result<ClassItem> {
+        if !self.eat('{') {
+            return Err(Error::InvalidEscape(self.span_from(start)));
+        }
+        let mut strings = vec![Vec::new()];
+        loop {
+            match self.peek() {
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('|') => {
+                    self.pos += 1;
+                    strings.push(Vec::new());
+                }
+                _ => {
+                    let ch = self.class_set_char(start)?;
+                    strings.last_mut().unwrap().push(ch);
+                }
+            }
+        }
+        Ok(ClassItem::Strings(strings))
+    }
+}
+
+static GENERAL_CATEGORIES: &'static [&'static str] = &[
+    "C", "Other", "Cc", "Control", "cntrl", "Cf", "Format", "Cn", "Unassigned",
+    "Co", "Private_Use", "Cs", "Surrogate", "L", "Letter", "LC", "Cased_Letter",
+    "Ll", "Lowercase_Letter", "Lm", "Modifier_Letter", "Lo", "Other_Letter",
+    "Lt", "Titlecase_Letter", "Lu", "Uppercase_Letter", "M", "Mark", "Combining_Mark",
+    "Mc", "Spacing_Mark", "Me", "Enclosing_Mark", "Mn", "Nonspacing_Mark",
+    "N", "Number", "Nd", "Decimal_Number", "digit", "Nl", "Letter_Number",
+    "No", "Other_Number", "P", "Punctuation", "punct", "Pc", "Connector_Punctuation",
+    "Pd", "Dash_Punctuation", "Pe", "Close_Punctuation", "Pf", "Final_Punctuation",
+    "Pi", "Initial_Punctuation", "Po", "Other_Punctuation", "Ps", "Open_Punctuation",
+    "S", "Symbol", "Sc", "Currency_Symbol", "Sk", "Modifier_Symbol", "Sm", "Math_Symbol",
+    "So", "Other_Symbol", "Z", "Separator", "Zl", "Line_Separator",
+    "Zp", "Paragraph_Separator", "Zs", "Space_Separator"
+];
+
+static BINARY_PROPERTIES: &'static [&'static str] = &[
+    "ASCII", "ASCII_Hex_Digit", "AHex", "Alphabetic", "Alpha", "Any", "Assigned",
+    "Bidi_Control", "Bidi_C", "Bidi_Mirrored", "Bidi_M", "Case_Ignorable", "CI", "Cased",
+    "Changes_When_Casefolded", "CWCF", "Changes_When_Casemapped", "CWCM",
+    "Changes_When_Lowercased", "CWL", "Changes_When_NFKC_Casefolded", "CWKCF",
+    "Changes_When_Titlecased", "CWT", "Changes_When_Uppercased", "CWU", "Dash",
+    "Default_Ignorable_Code_Point", "DI", "Deprecated", "Dep", "Diacritic", "Dia",
+    "Emoji", "Emoji_Component", "EComp", "Emoji_Modifier", "EMod",
+    "Emoji_Modifier_Base", "EBase", "Emoji_Presentation", "EPres",
+    "Extended_Pictographic", "ExtPict", "Extender", "Ext", "Grapheme_Base", "Gr_Base",
+    "Grapheme_Extend", "Gr_Ext", "Hex_Digit", "Hex", "IDS_Binary_Operator", "IDSB",
+    "IDS_Trinary_Operator", "IDST", "ID_Continue", "IDC", "ID_Start", "IDS",
+    "Ideographic", "Ideo", "Join_Control", "Join_C", "Logical_Order_Exception", "LOE",
+    "Lowercase", "Lower", "Math", "Noncharacter_Code_Point", "NChar",
+    "Pattern_Syntax", "Pat_Syn", "Pattern_White_Space", "Pat_WS", "Quotation_Mark", "QMark",
+    "Radical", "Regional_Indicator", "RI", "Sentence_Terminal", "STerm",
+    "Soft_Dotted", "SD", "Terminal_Punctuation", "Term", "Unified_Ideograph", "UIdeo",
+    "Uppercase", "Upper", "Variation_Selector", "VS", "White_Space", "space",
+    "XID_Continue", "XIDC", "XID_Start", "XIDS"
+];
+
+// Properties of strings, which only `v` mode supports.
+static STRING_PROPERTIES: &'static [&'static str] = &[
+    "Basic_Emoji", "Emoji_Keycap_Sequence", "RGI_Emoji_Modifier_Sequence",
+    "RGI_Emoji_Flag_Sequence", "RGI_Emoji_Tag_Sequence", "RGI_Emoji_ZWJ_Sequence", "RGI_Emoji"
+];
+
+static SCRIPTS: &'static [&'static str] = &[
+    "Adlm", "Adlam", "Aghb", "Caucasian_Albanian", "Ahom", "Arab", "Arabic",
+    "Armi", "Imperial_Aramaic", "Armn", "Armenian", "Avst", "Avestan", "Bali", "Balinese",
+    "Bamu", "Bamum", "Bass", "Bassa_Vah", "Batk", "Batak", "Beng", "Bengali",
+    "Bhks", "Bhaiksuki", "Bopo", "Bopomofo", "Brah", "Brahmi", "Brai", "Braille",
+    "Bugi", "Buginese", "Buhd", "Buhid", "Cakm", "Chakma", "Cans", "Canadian_Aboriginal",
+    "Cari", "Carian", "Cham", "Cher", "Cherokee", "Chrs", "Chorasmian",
+    "Copt", "Coptic", "Qaac", "Cpmn", "Cypro_Minoan", "Cprt", "Cypriot",
+    "Cyrl", "Cyrillic", "Deva", "Devanagari", "Diak", "Dives_Akuru", "Dogr", "Dogra",
+    "Dsrt", "Deseret", "Dupl", "Duployan", "Egyp", "Egyptian_Hieroglyphs",
+    "Elba", "Elbasan", "Elym", "Elymaic", "Ethi", "Ethiopic", "Gara", "Garay",
+    "Geor", "Georgian", "Glag", "Glagolitic", "Gong", "Gunjala_Gondi",
+    "Gonm", "Masaram_Gondi", "Goth", "Gothic", "Gran", "Grantha", "Grek", "Greek",
+    "Gujr", "Gujarati", "Gukh", "Gurung_Khema", "Guru", "Gurmukhi", "Hang", "Hangul",
+    "Hani", "Han", "Hano", "Hanunoo", "Hatr", "Hatran", "Hebr", "Hebrew",
+    "Hira", "Hiragana", "Hluw", "Anatolian_Hieroglyphs", "Hmng", "Pahawh_Hmong",
+    "Hmnp", "Nyiakeng_Puachue_Hmong", "Hrkt", "Katakana_Or_Hiragana",
+    "Hung", "Old_Hungarian", "Ital", "Old_Italic", "Java", "Javanese",
+    "Kali", "Kayah_Li", "Kana", "Katakana", "Kawi", "Khar", "Kharoshthi",
+    "Khmr", "Khmer", "Khoj", "Khojki", "Kits", "Khitan_Small_Script", "Knda", "Kannada",
+    "Krai", "Kirat_Rai", "Kthi", "Kaithi", "Lana", "Tai_Tham", "Laoo", "Lao",
+    "Latn", "Latin", "Lepc", "Lepcha", "Limb", "Limbu", "Lina", "Linear_A",
+    "Linb", "Linear_B", "Lisu", "Lyci", "Lycian", "Lydi", "Lydian",
+    "Mahj", "Mahajani", "Maka", "Makasar", "Mand", "Mandaic", "Mani", "Manichaean",
+    "Marc", "Marchen", "Medf", "Medefaidrin", "Mend", "Mende_Kikakui",
+    "Merc", "Meroitic_Cursive", "Mero", "Meroitic_Hieroglyphs", "Mlym", "Malayalam",
+    "Modi", "Mong", "Mongolian", "Mroo", "Mro", "Mtei", "Meetei_Mayek",
+    "Mult", "Multani", "Mymr", "Myanmar", "Nagm", "Nag_Mundari", "Nand", "Nandinagari",
+    "Narb", "Old_North_Arabian", "Nbat", "Nabataean", "Newa", "Nkoo", "Nko",
+    "Nshu", "Nushu", "Ogam", "Ogham", "Olck", "Ol_Chiki", "Onao", "Ol_Onal",
+    "Orkh", "Old_Turkic", "Orya", "Oriya", "Osge", "Osage", "Osma", "Osmanya",
+    "Ougr", "Old_Uyghur", "Palm", "Palmyrene", "Pauc", "Pau_Cin_Hau",
+    "Perm", "Old_Permic", "Phag", "Phags_Pa", "Phli", "Inscriptional_Pahlavi",
+    "Phlp", "Psalter_Pahlavi", "Phnx", "Phoenician", "Plrd", "Miao",
+    "Prti", "Inscriptional_Parthian", "Rjng", "Rejang", "Rohg", "Hanifi_Rohingya",
+    "Runr", "Runic", "Samr", "Samaritan", "Sarb", "Old_South_Arabian",
+    "Saur", "Saurashtra", "Sgnw", "SignWriting", "Shaw", "Shavian", "Shrd", "Sharada",
+    "Sidd", "Siddham", "Sind", "Khudawadi", "Sinh", "Sinhala", "Sogd", "Sogdian",
+    "Sogo", "Old_Sogdian", "Sora", "Sora_Sompeng", "Soyo", "Soyombo",
+    "Sund", "Sundanese", "Sunu", "Sunuwar", "Sylo", "Syloti_Nagri", "Syrc", "Syriac",
+    "Tagb", "Tagbanwa", "Takr", "Takri", "Tale", "Tai_Le", "Talu", "New_Tai_Lue",
+    "Taml", "Tamil", "Tang", "Tangut", "Tavt", "Tai_Viet", "Telu", "Telugu",
+    "Tfng", "Tifinagh", "Tglg", "Tagalog", "Thaa", "Thaana", "Thai",
+    "Tibt", "Tibetan", "Tirh", "Tirhuta", "Tnsa", "Tangsa", "Todr", "Todhri", "Toto",
+    "Tutg", "Tulu_Tigalari", "Ugar", "Ugaritic", "Vaii", "Vai", "Vith", "Vithkuqi",
+    "Wara", "Warang_Citi", "Wcho", "Wancho", "Xpeo", "Old_Persian", "Xsux", "Cuneiform",
+    "Yezi", "Yezidi", "Yiii", "Yi", "Zanb", "Zanabazar_Square",
+    "Zinh", "Inherited", "Qaai", "Zyyy", "Common", "Zzzz", "Unknown"
+];
+
+#[cfg(test)]
+mod tests {
+
+    use regexp::{parse, Error, RegExp, Term, Atom};
+    use token::RegExpLiteral;
+    use track::{Posn, Span};
+
+    fn parse_str(source: &str, annex_b: bool) -> Result<RegExp, Error> {
+        let end = source.rfind('/').unwrap();
+        let literal = RegExpLiteral {
+            pattern: source[1..end].to_string(),
+            flags: source[end + 1..].chars().collect()
+        };
+        parse(&literal, Posn { offset: 0, byte_offset: 0, line: 1, column: 1 }, annex_b)
+    }
+
+    #[test]
+    pub fn valid_patterns() {
+        let tests: Vec<(&str, bool)> = vec![
+            ("/a|b*?c{2,3}/dgimsy",            false),
+            ("/(?<x>a)\\k<x>(?<=a)(?<!b)/",     false),
+            ("/\\k<x>(?<x>a)/",                 false),
+            ("/(?<a>x)|(?<a>y)/",               false),
+            ("/(a)\\1/",                        false),
+            ("/\\p{L}\\P{Script=Greek}/u",      false),
+            ("/\\u{1F600}\\ud83d\\ude00/u",     false),
+            ("/[\\-]/u",                        false),
+            ("/[\\p{L}--\\p{N}]/v",             false),
+            ("/[[a-z]&&[aeiou]]/v",             false),
+            ("/[\\q{ab|c}\\p{RGI_Emoji}]/v",    false),
+            ("/{}]/",                           true),
+            ("/\\1\\8\\012\\c\\z\\k/",          true),
+            ("/[\\c_\\d-z]/",                   true),
+            ("/(?=a)*/",                        true),
+            ("/\\u12\\x1/",                     true),
+        ];
+        for (source, annex_b) in tests {
+            if let Err(err) = parse_str(source, annex_b) {
+                panic!("{}: unexpected error {:?}", source, err);
+            }
+        }
+    }
+
+    fn span(start: u32, end: u32) -> Span {
+        Span {
+            start: Posn { offset: start - 1, byte_offset: start - 1, line: 1, column: start },
+            end: Posn { offset: end - 1, byte_offset: end - 1, line: 1, column: end }
+        }
+    }
+
+    #[test]
+    pub fn invalid_patterns() {
+        let tests: Vec<(&str, bool, Error)> = vec![
+            ("/a/x",              false, Error::InvalidFlag(span(4, 5), 'x')),
+            ("/a/gg",             false, Error::DuplicateFlag(span(5, 6), 'g')),
+            ("/a/uv",             false, Error::IncompatibleFlags(span(5, 6))),
+            ("/a**/",             false, Error::NothingToRepeat(span(4, 5))),
+            ("/a{2,1}/",          true,  Error::QuantifierOutOfOrder(span(3, 8))),
+            ("/(/",               false, Error::UnterminatedGroup(span(2, 3))),
+            ("/a)/",              false, Error::UnmatchedParen(span(3, 4))),
+            ("/{/",               false, Error::UnexpectedChar(span(2, 3), '{')),
+            ("/(?<=a)*/",         true,  Error::NothingToRepeat(span(8, 9))),
+            ("/(?<a>x)(?<a>y)/",  false, Error::DuplicateGroupName(span(12, 14), "a".to_string())),
+            ("/\\k<y>/",          false, Error::InvalidBackreference(span(2, 7))),
+            ("/\\1/",             false, Error::InvalidBackreference(span(2, 4))),
+            ("/\\z/u",            false, Error::InvalidEscape(span(2, 4))),
+            ("/\\-/u",            false, Error::InvalidEscape(span(2, 4))),
+            ("/[b-a]/",           false, Error::ClassRangeOutOfOrder(span(3, 6))),
+            ("/[\\d-z]/",         false, Error::InvalidClassRange(span(3, 7))),
+            ("/[a/",              false, Error::UnterminatedClass(span(2, 4))),
+            ("/\\p{Foo}/u",       false, Error::InvalidProperty(span(2, 9))),
+            ("/\\p{RGI_Emoji}/u", false, Error::InvalidProperty(span(2, 15))),
+            ("/\\P{RGI_Emoji}/v", false, Error::NegatedStrings(span(2, 15))),
+            ("/[^\\q{ab}]/v",     false, Error::NegatedStrings(span(2, 11))),
+            ("/[a&&b--c]/v",      false, Error::InvalidClassOperation(span(7, 8))),
+            ("/[(]/v",            false, Error::UnexpectedChar(span(3, 4), '(')),
+        ];
+        for (source, annex_b, expected) in tests {
+            assert_eq!(Err(expected), parse_str(source, annex_b), "{}", source);
+        }
+    }
+
+    #[test]
+    pub fn annex_b_fallbacks() {
+        let expected = vec![Atom::Char('\\' as u32), Atom::Char('c' as u32), Atom::Char(0o12), Atom::Char('8' as u32)];
+        let pattern = parse_str("/\\c\\12\\8/", true).unwrap().pattern;
+        let actual: Vec<Atom> = pattern.alternatives[0].terms.iter().map(|term| {
+            match *term {
+                Term::Atom(_, ref atom) => atom.clone(),
+                ref other => { panic!("expected atom, got {:?}", other); }
+            }
+        }).collect();
+        assert_eq!(expected, actual);
+    }
+
+}
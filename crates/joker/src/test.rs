@@ -11,7 +11,7 @@ use std::fmt;
 pub struct LexerTest {
     pub source: String,
     pub operator: bool,
-    pub expected: Result<TokenData, String>
+    pub expected: Result<TokenData<'static>, String>
 }
 
 impl Deserialize for LexerTest {
@@ -21,7 +21,7 @@ impl Deserialize for LexerTest {
             pub source: String,
             pub context: HashSet<String>,
             pub error: Option<String>,
-            pub expected: Option<TokenData>
+            pub expected: Option<TokenData<'static>>
         }
 
         let repr: Repr = Deserialize::deserialize(deserializer)?;
@@ -43,8 +43,8 @@ impl Deserialize for LexerTest {
     }
 }
 
-impl Deserialize for Name {
-    fn deserialize<D: Deserializer>(deserializer: D) -> Result<Name, D::Error> {
+impl Deserialize for Name<'static> {
+    fn deserialize<D: Deserializer>(deserializer: D) -> Result<Name<'static>, D::Error> {
         let word: String = Deserialize::deserialize(deserializer)?;
         Ok(Name::from(word))
     }
@@ -63,8 +63,8 @@ impl Deserialize for CharCase {
     }
 }
 
-impl Deserialize for Exp {
-    fn deserialize<D: Deserializer>(deserializer: D) -> Result<Exp, D::Error> {
+impl Deserialize for Exp<'static> {
+    fn deserialize<D: Deserializer>(deserializer: D) -> Result<Exp<'static>, D::Error> {
         let (e, sign, value) = Deserialize::deserialize(deserializer)?;
         Ok(Exp {
             e: e,
@@ -76,18 +76,18 @@ impl Deserialize for Exp {
 
 static KNOWN_TOKEN_VARIANTS: [&'static str; 4] = ["Reserved", "LBrace", "RBrace", "..."];
 
-impl Deserialize for TokenData {
-    fn deserialize<D: Deserializer>(deserializer: D) -> Result<TokenData, D::Error> {
+impl Deserialize for TokenData<'static> {
+    fn deserialize<D: Deserializer>(deserializer: D) -> Result<TokenData<'static>, D::Error> {
         struct TokenVisitor;
 
         impl Visitor for TokenVisitor {
-            type Value = TokenData;
+            type Value = TokenData<'static>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("a nonempty sequence")
             }
 
-            fn visit_seq<V: SeqVisitor>(self, mut visitor: V) -> Result<TokenData, V::Error> {
+            fn visit_seq<V: SeqVisitor>(self, mut visitor: V) -> Result<TokenData<'static>, V::Error> {
                 let ty: String = visitor.visit()?.unwrap();
 
                 let inner = SeqVisitorDeserializer::new(visitor);
@@ -118,6 +118,7 @@ impl Deserialize for TokenData {
                     "Plus"          => TokenData::Plus,
                     "Minus"         => TokenData::Minus,
                     "Star"          => TokenData::Star,
+                    "StarStar"      => TokenData::StarStar,
                     "Mod"           => TokenData::Mod,
                     "Slash"         => TokenData::Slash,
                     "Inc"           => TokenData::Inc,
@@ -132,12 +133,15 @@ impl Deserialize for TokenData {
                     "Tilde"         => TokenData::Tilde,
                     "LogicalAnd"    => TokenData::LogicalAnd,
                     "LogicalOr"     => TokenData::LogicalOr,
+                    "Coalesce"      => TokenData::Coalesce,
                     "Question"      => TokenData::Question,
+                    "QuestionDot"   => TokenData::QuestionDot,
                     "Colon"         => TokenData::Colon,
                     "Assign"        => TokenData::Assign,
                     "PlusAssign"    => TokenData::PlusAssign,
                     "MinusAssign"   => TokenData::MinusAssign,
                     "StarAssign"    => TokenData::StarAssign,
+                    "StarStarAssign" => TokenData::StarStarAssign,
                     "SlashAssign"   => TokenData::SlashAssign,
                     "ModAssign"     => TokenData::ModAssign,
                     "LShiftAssign"  => TokenData::LShiftAssign,
@@ -146,6 +150,9 @@ impl Deserialize for TokenData {
                     "BitAndAssign"  => TokenData::BitAndAssign,
                     "BitOrAssign"   => TokenData::BitOrAssign,
                     "BitXorAssign"  => TokenData::BitXorAssign,
+                    "LogicalAndAssign" => TokenData::LogicalAndAssign,
+                    "LogicalOrAssign" => TokenData::LogicalOrAssign,
+                    "CoalesceAssign" => TokenData::CoalesceAssign,
                     "Arrow"         => TokenData::Arrow,
                     "EOF"           => TokenData::EOF,
                     "DecimalInt"    => {
@@ -164,6 +171,22 @@ impl Deserialize for TokenData {
                         let (flag, value) = Deserialize::deserialize(inner)?;
                         NumberSource::RadixInt(Radix::Hex(flag), value).into_token_data()
                     }
+                    "DecimalBigInt" => {
+                        let (value,) = Deserialize::deserialize(inner)?;
+                        NumberSource::DecimalInt(value, None).into_bigint_token_data()
+                    }
+                    "BinaryBigInt"  => {
+                        let (flag, value) = Deserialize::deserialize(inner)?;
+                        NumberSource::RadixInt(Radix::Bin(flag), value).into_bigint_token_data()
+                    }
+                    "OctalBigInt"   => {
+                        let (flag, value) = Deserialize::deserialize(inner)?;
+                        NumberSource::RadixInt(Radix::Oct(Some(flag)), value).into_bigint_token_data()
+                    }
+                    "HexBigInt"     => {
+                        let (flag, value) = Deserialize::deserialize(inner)?;
+                        NumberSource::RadixInt(Radix::Hex(flag), value).into_bigint_token_data()
+                    }
                     "Float"         => {
                         let (int, frac, exp) = Deserialize::deserialize(inner)?;
                         NumberSource::Float(int, frac, exp).into_token_data()
@@ -171,8 +194,8 @@ impl Deserialize for TokenData {
                     "String"        => {
                         let (value,): (String,) = Deserialize::deserialize(inner)?;
                         TokenData::String(StringLiteral {
-                            source: Some(format!("{:?}", Value::String(value.clone()))),
-                            value: value
+                            source: Some(format!("{:?}", Value::String(value.clone())).into()),
+                            value: value.into()
                         })
                     }
                     "RegExp"        => {
@@ -182,10 +205,22 @@ impl Deserialize for TokenData {
                             flags: flags.chars().collect()
                         })
                     }
+                    "NoSubstTemplate" => {
+                        let (cooked, raw) = Deserialize::deserialize(inner)?;
+                        TokenData::NoSubstTemplate(TemplateLiteral { cooked: cooked, raw: raw })
+                    }
+                    "TemplateHead"  => {
+                        let (cooked, raw) = Deserialize::deserialize(inner)?;
+                        TokenData::TemplateHead(TemplateLiteral { cooked: cooked, raw: raw })
+                    }
                     "Identifier"    => {
                         let (name,) = Deserialize::deserialize(inner)?;
                         TokenData::Identifier(name)
                     },
+                    "PrivateName"   => {
+                        let (name,) = Deserialize::deserialize(inner)?;
+                        TokenData::PrivateName(name)
+                    },
                     other => { return Err(V::Error::unknown_variant(other, &KNOWN_TOKEN_VARIANTS)); }
                 })
             }
@@ -1,27 +1,87 @@
 use std::fmt;
-use std::fmt::{Debug, Formatter};
+use std::f64;
+use std::borrow::Cow;
+use std::fmt::{Debug, Display, Formatter};
 use track::{Span, Posn, Untrack};
 use word::{Reserved, Name};
 
+// Tokens lexed from a string borrow their text from it for `'a`.
 #[derive(Debug, PartialEq, Clone)]
-pub struct Token {
+pub struct Token<'a> {
     pub location: Span,
     pub newline: bool,    // was there a newline between the preceding token and this one?
-    pub value: TokenData
+    pub value: TokenData<'a>
 }
 
-impl Token {
-    pub fn new(start: Posn, end: Posn, value: TokenData) -> Token {
+impl<'a> Token<'a> {
+    pub fn new(start: Posn, end: Posn, value: TokenData<'a>) -> Token<'a> {
         Token {
             location: Span { start: start, end: end },
             newline: false,
             value: value
         }
     }
+
+    pub fn into_owned(self) -> Token<'static> {
+        Token {
+            location: self.location,
+            newline: self.newline,
+            value: self.value.into_owned()
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CommentKind {
+    Line,  // `// ...`
+    Block, // `/* ... */`
+    Html   // Annex B `<!-- ...` or `--> ...`
+}
+
+// The text of a comment doesn't include its delimiters.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Comment {
+    pub location: Span,
+    pub kind: CommentKind,
+    pub text: String
 }
 
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TriviaKind {
+    Whitespace,
+    Newlines,
+    Comment(CommentKind),
+    Hashbang
+}
+
+// A run of source text between two tokens.
 #[derive(Debug, PartialEq, Clone)]
-pub enum TokenData {
+pub struct Trivia {
+    pub location: Span,
+    pub kind: TriviaKind,
+    pub source: String
+}
+
+// A token with its exact source text and the trivia that comes before it,
+// so that a whole token stream prints back to the original source.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SourceToken<'a> {
+    pub leading: Vec<Trivia>,
+    pub token: Token<'a>,
+    pub source: String
+}
+
+impl<'a> Display for SourceToken<'a> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        for trivia in &self.leading {
+            fmt.write_str(&trivia.source)?;
+        }
+        fmt.write_str(&self.source)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TokenData<'a> {
     Reserved(Reserved),
 
     // 11.7 Punctuators
@@ -46,6 +106,7 @@ pub enum TokenData {
     Plus,
     Minus,
     Star,
+    StarStar,
     Mod,
     Slash,
     Inc,
@@ -60,12 +121,15 @@ pub enum TokenData {
     Tilde,
     LogicalAnd,
     LogicalOr,
+    Coalesce,
     Question,
+    QuestionDot,
     Colon,
     Assign,
     PlusAssign,
     MinusAssign,
     StarAssign,
+    StarStarAssign,
     SlashAssign,
     ModAssign,
     LShiftAssign,
@@ -74,24 +138,67 @@ pub enum TokenData {
     BitAndAssign,
     BitOrAssign,
     BitXorAssign,
+    LogicalAndAssign,
+    LogicalOrAssign,
+    CoalesceAssign,
     Arrow,
 
-    Number(NumberLiteral),
-    String(StringLiteral),
+    Number(NumberLiteral<'a>),
+    BigInt(BigIntLiteral<'a>),
+    String(StringLiteral<'a>),
     RegExp(RegExpLiteral),
 
-    Identifier(Name),
+    // 11.8.6 Template Literal Lexical Components
+    NoSubstTemplate(TemplateLiteral),
+    TemplateHead(TemplateLiteral),
+    TemplateMiddle(TemplateLiteral),
+    TemplateTail(TemplateLiteral),
+
+    Identifier(Name<'a>),
+    // Stored without the leading `#`.
+    PrivateName(Cow<'a, str>),
 
     EOF
 }
 
-impl TokenData {
+macro_rules! owned_token_data {
+    ($value:expr, [ $( $variant:ident ),* ]) => {
+        match $value {
+            TokenData::Reserved(word)        => TokenData::Reserved(word),
+            TokenData::Number(literal)       => TokenData::Number(literal.into_owned()),
+            TokenData::BigInt(literal)       => TokenData::BigInt(literal.into_owned()),
+            TokenData::String(literal)       => TokenData::String(literal.into_owned()),
+            TokenData::RegExp(literal)       => TokenData::RegExp(literal),
+            TokenData::NoSubstTemplate(tmpl) => TokenData::NoSubstTemplate(tmpl),
+            TokenData::TemplateHead(tmpl)    => TokenData::TemplateHead(tmpl),
+            TokenData::TemplateMiddle(tmpl)  => TokenData::TemplateMiddle(tmpl),
+            TokenData::TemplateTail(tmpl)    => TokenData::TemplateTail(tmpl),
+            TokenData::Identifier(name)      => TokenData::Identifier(name.into_owned()),
+            TokenData::PrivateName(name)     => TokenData::PrivateName(Cow::Owned(name.into_owned())),
+            $( TokenData::$variant => TokenData::$variant ),*
+        }
+    };
+}
+
+impl<'a> TokenData<'a> {
     pub fn is_string(&self) -> bool {
         match *self {
             TokenData::String(_) => true,
             _ => false
         }
     }
+
+    pub fn into_owned(self) -> TokenData<'static> {
+        owned_token_data!(self, [
+            LBrace, RBrace, LParen, RParen, LBrack, RBrack, Dot, Ellipsis, Semi, Comma,
+            LAngle, RAngle, LEq, GEq, Eq, NEq, StrictEq, StrictNEq, Plus, Minus, Star,
+            StarStar, Mod, Slash, Inc, Dec, LShift, RShift, URShift, BitAnd, BitOr, BitXor,
+            Bang, Tilde, LogicalAnd, LogicalOr, Coalesce, Question, QuestionDot, Colon,
+            Assign, PlusAssign, MinusAssign, StarAssign, StarStarAssign, SlashAssign,
+            ModAssign, LShiftAssign, RShiftAssign, URShiftAssign, BitAndAssign, BitOrAssign,
+            BitXorAssign, LogicalAndAssign, LogicalOrAssign, CoalesceAssign, Arrow, EOF
+        ])
+    }
 }
 
 #[derive(Clone)]
@@ -133,16 +240,50 @@ impl PartialEq for RegExpLiteral {
 }
 
 #[derive(Clone)]
-pub struct StringLiteral {
-    pub source: Option<String>,
-    pub value: String
+pub struct StringLiteral<'a> {
+    pub source: Option<Cow<'a, str>>,
+    pub value: Cow<'a, str>
+}
+
+impl<'a> StringLiteral<'a> {
+    pub fn into_owned(self) -> StringLiteral<'static> {
+        StringLiteral {
+            source: self.source.map(|source| Cow::Owned(source.into_owned())),
+            value: Cow::Owned(self.value.into_owned())
+        }
+    }
+
+    // Legacy octal escapes like `\07`, and `\8` or `\9`, which strict
+    // mode code can't contain. `\0` on its own is a regular escape.
+    pub fn has_legacy_escape(&self) -> bool {
+        let source = match self.source {
+            Some(ref source) => source,
+            None => { return false; }
+        };
+        let mut chars = source.chars().peekable();
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                continue;
+            }
+            match chars.next() {
+                Some('0') => {
+                    if chars.peek().map_or(false, |ch| ch.is_digit(10)) {
+                        return true;
+                    }
+                }
+                Some(ch) if ch.is_digit(10) => { return true; }
+                _ => { }
+            }
+        }
+        false
+    }
 }
 
-impl Untrack for StringLiteral {
+impl<'a> Untrack for StringLiteral<'a> {
     fn untrack(&mut self) {}
 }
 
-impl Debug for StringLiteral {
+impl<'a> Debug for StringLiteral<'a> {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
         fmt.debug_struct("StringLiteral")
             .field("value", &self.value)
@@ -150,23 +291,44 @@ impl Debug for StringLiteral {
     }
 }
 
-impl PartialEq for StringLiteral {
+impl<'a> PartialEq for StringLiteral<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.value == other.value
     }
 }
 
+// The cooked value is `None` when the literal contains an invalid escape,
+// which is only allowed in tagged templates.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TemplateLiteral {
+    pub cooked: Option<String>,
+    pub raw: String
+}
+
+impl Untrack for TemplateLiteral {
+    fn untrack(&mut self) {}
+}
+
 #[derive(Clone)]
-pub struct NumberLiteral {
-    pub source: Option<NumberSource>,
+pub struct NumberLiteral<'a> {
+    pub source: Option<NumberSource<'a>>,
     pub value: f64
 }
 
-impl Untrack for NumberLiteral {
+impl<'a> NumberLiteral<'a> {
+    pub fn into_owned(self) -> NumberLiteral<'static> {
+        NumberLiteral {
+            source: self.source.map(NumberSource::into_owned),
+            value: self.value
+        }
+    }
+}
+
+impl<'a> Untrack for NumberLiteral<'a> {
     fn untrack(&mut self) {}
 }
 
-impl Debug for NumberLiteral {
+impl<'a> Debug for NumberLiteral<'a> {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
         fmt.debug_struct("NumberLiteral")
             .field("value", &self.value)
@@ -174,17 +336,76 @@ impl Debug for NumberLiteral {
     }
 }
 
-impl PartialEq for NumberLiteral {
+impl<'a> PartialEq for NumberLiteral<'a> {
     fn eq(&self, other: &Self) -> bool {
         self.value == other.value
     }
 }
 
+// The value of a BigInt is kept as an exact string of decimal digits,
+// since it generally doesn't fit in any primitive type.
+#[derive(Clone)]
+pub struct BigIntLiteral<'a> {
+    pub source: Option<NumberSource<'a>>,
+    pub value: String
+}
+
+impl<'a> BigIntLiteral<'a> {
+    pub fn into_owned(self) -> BigIntLiteral<'static> {
+        BigIntLiteral {
+            source: self.source.map(NumberSource::into_owned),
+            value: self.value
+        }
+    }
+}
+
+impl<'a> Untrack for BigIntLiteral<'a> {
+    fn untrack(&mut self) {}
+}
+
+impl<'a> Debug for BigIntLiteral<'a> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("BigIntLiteral")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a> PartialEq for BigIntLiteral<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+// Converts a nonempty string of digits in the given radix to decimal.
+pub fn radix_to_decimal(digits: &str, radix: u32) -> String {
+    const BASE: u64 = 1_000_000_000;
+
+    // Little-endian limbs of nine decimal digits each.
+    let mut limbs: Vec<u64> = vec![0];
+    for ch in digits.chars() {
+        let mut carry = ch.to_digit(radix).unwrap() as u64;
+        for limb in limbs.iter_mut() {
+            let x = *limb * (radix as u64) + carry;
+            *limb = x % BASE;
+            carry = x / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+    let mut result = limbs.pop().unwrap().to_string();
+    for limb in limbs.iter().rev() {
+        result.push_str(&format!("{:09}", limb));
+    }
+    result
+}
+
 #[derive(Debug, PartialEq, Clone)]
-pub enum NumberSource {
-    DecimalInt(String, Option<Exp>),
-    RadixInt(Radix, String),
-    Float(Option<String>, Option<String>, Option<Exp>)
+pub enum NumberSource<'a> {
+    DecimalInt(Cow<'a, str>, Option<Exp<'a>>),
+    RadixInt(Radix, Cow<'a, str>),
+    Float(Option<Cow<'a, str>>, Option<Cow<'a, str>>, Option<Exp<'a>>)
 }
 
 fn format_sign(sign: &Option<Sign>) -> String {
@@ -194,53 +415,132 @@ fn format_sign(sign: &Option<Sign>) -> String {
     }.to_string()
 }
 
-fn format_int(src: &Option<String>) -> String {
+fn format_int<'a>(src: &'a Option<Cow<str>>) -> &'a str {
     match *src {
-        None        => "".to_string(),
-        Some(ref s) => s.to_string()
+        None        => "",
+        Some(ref s) => &s[..]
+    }
+}
+
+fn format_exp(exp: &Option<Exp>) -> String {
+    match *exp {
+        None => "".to_string(),
+        Some(Exp { ref sign, ref value, .. }) => format!("e{}{}", format_sign(sign), value)
     }
 }
 
-impl NumberSource {
+// Computes the value of an integer in a power-of-two radix, rounding to
+// nearest with ties to even. Only the leading 53 significant bits, the
+// bit after them and whether any later bit is set affect the result.
+fn radix_value(src: &str, radix: u32) -> f64 {
+    debug_assert!(radix.is_power_of_two());
+    let digit_bits = radix.trailing_zeros();
+    let mut mantissa: u64 = 0;
+    let mut len: u64 = 0;
+    let mut round = false;
+    let mut sticky = false;
+    for ch in src.chars() {
+        let digit = ch.to_digit(radix).unwrap();
+        for i in (0..digit_bits).rev() {
+            let bit = (digit >> i) & 1 == 1;
+            if len == 0 && !bit {
+                continue;
+            }
+            if len < 53 {
+                mantissa = (mantissa << 1) | (bit as u64);
+            } else if len == 53 {
+                round = bit;
+            } else {
+                sticky = sticky || bit;
+            }
+            len += 1;
+        }
+    }
+    if len <= 53 {
+        return mantissa as f64;
+    }
+    if round && (sticky || mantissa & 1 == 1) {
+        // This may carry into bit 53, which is still exactly representable.
+        mantissa += 1;
+    }
+    let shift = len - 53;
+    if shift > 1023 {
+        return f64::INFINITY;
+    }
+    (mantissa as f64) * 2f64.powi(shift as i32)
+}
+
+fn owned(s: Option<Cow<str>>) -> Option<Cow<'static, str>> {
+    s.map(|s| Cow::Owned(s.into_owned()))
+}
+
+impl<'a> NumberSource<'a> {
+    // Decimal literals go through the standard library's float parser,
+    // which is correctly rounded for inputs of any length.
     pub fn value(&self) -> f64 {
         match *self {
             NumberSource::DecimalInt(ref mantissa, None) => {
-                let i: i64 = mantissa.parse().unwrap();
-                i as f64
+                mantissa.parse().unwrap()
             }
-            NumberSource::DecimalInt(ref mantissa, Some(Exp { ref sign, ref value, .. })) => {
-                let mantissa: i64 = mantissa.parse().unwrap();
-                let mantissa: f64 = mantissa as f64;
-                let exp: i32 = value.parse().unwrap();
-                mantissa * (10 as f64).powi(if *sign == Some(Sign::Minus) { -exp } else { exp })
+            NumberSource::DecimalInt(ref mantissa, ref exp) => {
+                format!("{}{}", mantissa, format_exp(exp)).parse().unwrap()
             }
             NumberSource::RadixInt(ref radix, ref src) => {
-                let i = i64::from_str_radix(&src[..], radix.value()).unwrap();
-                i as f64
+                radix_value(&src[..], radix.value())
             }
-            NumberSource::Float(ref ip, ref fp, None) => {
-                format!("{}.{}", format_int(ip), format_int(fp)).parse().unwrap()
+            NumberSource::Float(ref ip, ref fp, ref exp) => {
+                format!("{}.{}{}", format_int(ip), format_int(fp), format_exp(exp)).parse().unwrap()
             }
-            NumberSource::Float(ref ip, ref fp, Some(Exp { ref sign, ref value, .. })) => {
-                format!("{}.{}e{}{}", format_int(ip), format_int(fp), format_sign(sign), value).parse().unwrap()
+        }
+    }
+
+    pub fn into_owned(self) -> NumberSource<'static> {
+        match self {
+            NumberSource::DecimalInt(src, exp) => {
+                NumberSource::DecimalInt(Cow::Owned(src.into_owned()), exp.map(Exp::into_owned))
             }
+            NumberSource::RadixInt(radix, src) => NumberSource::RadixInt(radix, Cow::Owned(src.into_owned())),
+            NumberSource::Float(ip, fp, exp) => NumberSource::Float(owned(ip), owned(fp), exp.map(Exp::into_owned))
         }
     }
 
-    pub fn into_token_data(self) -> TokenData {
+    pub fn into_token_data(self) -> TokenData<'a> {
         let value = self.value();
         TokenData::Number(NumberLiteral {
             source: Some(self),
             value: value
         })
     }
+
+    // Only integers without an exponent can have the BigInt suffix.
+    pub fn into_bigint_token_data(self) -> TokenData<'a> {
+        let value = match self {
+            NumberSource::DecimalInt(ref src, None) => radix_to_decimal(src, 10),
+            NumberSource::RadixInt(ref radix, ref src) => radix_to_decimal(src, radix.value()),
+            _ => { panic!("BigInt literal with a fraction or exponent"); }
+        };
+        TokenData::BigInt(BigIntLiteral {
+            source: Some(self),
+            value: value
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct Exp {
+pub struct Exp<'a> {
     pub e: CharCase,
     pub sign: Option<Sign>,
-    pub value: String
+    pub value: Cow<'a, str>
+}
+
+impl<'a> Exp<'a> {
+    pub fn into_owned(self) -> Exp<'static> {
+        Exp {
+            e: self.e,
+            sign: self.sign,
+            value: Cow::Owned(self.value.into_owned())
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
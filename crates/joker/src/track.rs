@@ -1,8 +1,12 @@
 use std::fmt::{Debug, Formatter, Result};
+use char::ESCharExt;
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+// Offsets and columns count chars, except `byte_offset`, which indexes
+// the UTF-8 source.
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Posn {
     pub offset: u32,
+    pub byte_offset: u32,
     pub line: u32,
     pub column: u32
 }
@@ -11,6 +15,7 @@ impl Posn {
     pub fn origin() -> Posn {
         Posn {
             offset: 0,
+            byte_offset: 0,
             line: 0,
             column: 0
         }
@@ -23,12 +28,19 @@ impl Debug for Posn {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Span {
     pub start: Posn,
     pub end: Posn
 }
 
+impl Span {
+    // The text of the span in the source it was parsed from.
+    pub fn source<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start.byte_offset as usize..self.end.byte_offset as usize]
+    }
+}
+
 impl Debug for Span {
     fn fmt(&self, fmt: &mut Formatter) -> Result {
         fmt.write_fmt(format_args!("{:?}..{:?}", self.start, self.end))
@@ -59,6 +71,14 @@ pub trait Untrack {
     fn untrack(&mut self);
 }
 
+impl Untrack for bool {
+    fn untrack(&mut self) { }
+}
+
+impl Untrack for String {
+    fn untrack(&mut self) { }
+}
+
 impl<T> Untrack for Box<T>
   where T: Untrack
 {
@@ -103,3 +123,139 @@ pub fn span<T, U>(left: &T, right: &U) -> Option<Span>
         _ => None
     }
 }
+
+#[derive(Clone, Copy)]
+struct LineStart {
+    offset: u32,
+    byte_offset: u32
+}
+
+// Converts positions to and from the zero-based lines and UTF-16 columns
+// that source maps and editors use. Lines break wherever the lexer's do.
+pub struct LineIndex<'a> {
+    source: &'a str,
+    lines: Vec<LineStart>
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> LineIndex<'a> {
+        let mut lines = vec![LineStart { offset: 0, byte_offset: 0 }];
+        let mut chars = source.char_indices().enumerate().peekable();
+        while let Some((offset, (byte_offset, ch))) = chars.next() {
+            let newline = match ch {
+                '\r' => chars.peek().map_or(true, |&(_, (_, next))| next != '\n'),
+                '\n' | '\u{2028}' | '\u{2029}' => true,
+                _ => false
+            };
+            if newline {
+                lines.push(LineStart {
+                    offset: offset as u32 + 1,
+                    byte_offset: (byte_offset + ch.len_utf8()) as u32
+                });
+            }
+        }
+        LineIndex { source: source, lines: lines }
+    }
+
+    // The line and UTF-16 column of a position in the source, or `None`
+    // for a position that can't be in it.
+    pub fn utf16_posn(&self, posn: Posn) -> Option<(u32, u32)> {
+        let start = match self.lines.get(posn.line as usize) {
+            Some(start) => start.byte_offset as usize,
+            None => { return None; }
+        };
+        let prefix = match self.source.get(start..posn.byte_offset as usize) {
+            Some(prefix) => prefix,
+            None => { return None; }
+        };
+        Some((posn.line, prefix.chars().map(|ch| ch.len_utf16() as u32).sum()))
+    }
+
+    // The position at a line and UTF-16 column, unless that's past the end
+    // of the line or in the middle of a surrogate pair.
+    pub fn from_utf16(&self, line: u32, column: u32) -> Option<Posn> {
+        let start = match self.lines.get(line as usize) {
+            Some(&start) => start,
+            None => { return None; }
+        };
+        let end = self.lines.get(line as usize + 1).map_or(self.source.len(), |next| next.byte_offset as usize);
+        let mut posn = Posn {
+            offset: start.offset,
+            byte_offset: start.byte_offset,
+            line: line,
+            column: 0
+        };
+        let mut units = 0;
+        for ch in self.source[start.byte_offset as usize..end].chars() {
+            if units >= column || ch.is_es_newline() {
+                break;
+            }
+            units += ch.len_utf16() as u32;
+            posn.offset += 1;
+            posn.byte_offset += ch.len_utf8() as u32;
+            posn.column += 1;
+        }
+        if units == column { Some(posn) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use track::{LineIndex, Posn};
+    use lexer::Lexer;
+    use token::TokenData;
+
+    #[test]
+    pub fn spans_slice_source() {
+        let source = "\u{feff}ä = '😀' +\r\n  `€`;";
+        let mut lexer = Lexer::new(source.chars());
+        let mut texts = Vec::new();
+        loop {
+            let token = lexer.read_token(true).unwrap();
+            if token.value == TokenData::EOF {
+                assert_eq!(source.len() as u32, token.location.end.byte_offset);
+                break;
+            }
+            texts.push(token.location.source(source));
+        }
+        assert_eq!(vec!["ä", "=", "'😀'", "+", "`€`", ";"], texts);
+    }
+
+    #[test]
+    pub fn utf16_positions() {
+        let source = "'😀'b\r\nc\u{2028}'€'d\re";
+        let index = LineIndex::new(source);
+        let mut lexer = Lexer::new(source.chars());
+        let mut posns = Vec::new();
+        loop {
+            let token = lexer.read_token(true).unwrap();
+            if token.value == TokenData::EOF {
+                break;
+            }
+            posns.push(token.location.start);
+        }
+        let utf16: Vec<(u32, u32)> = posns.iter().map(|&posn| index.utf16_posn(posn).unwrap()).collect();
+        assert_eq!(vec![(0, 0), (0, 4), (1, 0), (2, 0), (2, 3), (3, 0)], utf16);
+        for (&posn, &(line, column)) in posns.iter().zip(utf16.iter()) {
+            assert_eq!(Some(posn), index.from_utf16(line, column));
+        }
+
+        let end_of_b = Posn { offset: 4, byte_offset: 7, line: 0, column: 4 };
+        assert_eq!(Some((0, 5)), index.utf16_posn(end_of_b));
+        assert_eq!(Some(end_of_b), index.from_utf16(0, 5));
+        assert_eq!(None, index.from_utf16(0, 2));
+        assert_eq!(None, index.from_utf16(0, 6));
+        assert_eq!(None, index.from_utf16(4, 0));
+    }
+
+    #[test]
+    pub fn utf16_positions_out_of_range() {
+        let index = LineIndex::new("'😀'\nb");
+        assert_eq!(None, index.utf16_posn(Posn { offset: 0, byte_offset: 0, line: 2, column: 0 }));
+        assert_eq!(None, index.utf16_posn(Posn { offset: 9, byte_offset: 9, line: 1, column: 3 }));
+        assert_eq!(None, index.utf16_posn(Posn { offset: 2, byte_offset: 3, line: 0, column: 2 }));
+        assert_eq!(None, index.utf16_posn(Posn { offset: 0, byte_offset: 0, line: 1, column: 0 }));
+    }
+
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::convert::{AsRef, From};
 use token::TokenData;
@@ -130,6 +131,7 @@ impl Reserved {
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Atom {
     Arguments,
+    As,
     Async,
     Await,
     Eval,
@@ -138,6 +140,7 @@ pub enum Atom {
     Implements,
     Interface,
     Let,
+    Meta,
     Of,
     Package,
     Private,
@@ -150,12 +153,12 @@ pub enum Atom {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-pub enum Name {
+pub enum Name<'a> {
     Atom(Atom),
-    String(String)
+    String(Cow<'a, str>)
 }
 
-impl Name {
+impl<'a> Name<'a> {
     pub fn atom(&self) -> Option<Atom> {
         match *self {
             Name::Atom(ref atom) => Some(*atom),
@@ -166,12 +169,19 @@ impl Name {
     pub fn into_string(self) -> String {
         match self {
             Name::Atom(atom) => atom.name().to_string(),
-            Name::String(s)  => s
+            Name::String(s)  => s.into_owned()
+        }
+    }
+
+    pub fn into_owned(self) -> Name<'static> {
+        match self {
+            Name::Atom(atom) => Name::Atom(atom),
+            Name::String(s)  => Name::String(Cow::Owned(s.into_owned()))
         }
     }
 }
 
-impl AsRef<str> for Name {
+impl<'a> AsRef<str> for Name<'a> {
     fn as_ref(&self) -> &str {
         match self {
             &Name::Atom(ref atom) => atom.name(),
@@ -180,10 +190,11 @@ impl AsRef<str> for Name {
     }
 }
 
-impl From<String> for Name {
-    fn from(s: String) -> Name {
+impl<'a> From<String> for Name<'a> {
+    fn from(s: String) -> Name<'a> {
         match &s[..] {
             "arguments"  => Name::Atom(Atom::Arguments),
+            "as"         => Name::Atom(Atom::As),
             "await"      => Name::Atom(Atom::Await),
             "eval"       => Name::Atom(Atom::Eval),
             "async"      => Name::Atom(Atom::Async),
@@ -192,6 +203,7 @@ impl From<String> for Name {
             "implements" => Name::Atom(Atom::Implements),
             "interface"  => Name::Atom(Atom::Interface),
             "let"        => Name::Atom(Atom::Let),
+            "meta"       => Name::Atom(Atom::Meta),
             "of"         => Name::Atom(Atom::Of),
             "package"    => Name::Atom(Atom::Package),
             "private"    => Name::Atom(Atom::Private),
@@ -201,7 +213,7 @@ impl From<String> for Name {
             "static"     => Name::Atom(Atom::Static),
             "target"     => Name::Atom(Atom::Target),
             "yield"      => Name::Atom(Atom::Yield),
-            _            => Name::String(s)
+            _            => Name::String(Cow::Owned(s))
         }
     }
 }
@@ -210,6 +222,7 @@ impl Atom {
     pub fn name(self) -> &'static str {
         match self {
             Atom::Arguments  => "arguments",
+            Atom::As         => "as",
             Atom::Await      => "await",
             Atom::Eval       => "eval",
             Atom::Async      => "async",
@@ -218,6 +231,7 @@ impl Atom {
             Atom::Implements => "implements",
             Atom::Interface  => "interface",
             Atom::Let        => "let",
+            Atom::Meta       => "meta",
             Atom::Of         => "of",
             Atom::Package    => "package",
             Atom::Private    => "private",
@@ -288,19 +302,29 @@ impl Map {
                 ("public",     Public),     ("static",     Static),
 
                 // Purely contextual identifier names
-                ("async",      Async),      ("from",       From),       ("of",       Of),
-                ("get",        Get),        ("set",        Set),        ("target",   Target)
+                ("as",         As),         ("async",      Async),      ("from",     From),
+                ("get",        Get),        ("of",         Of),         ("set",      Set),
+                ("meta",       Meta),       ("target",     Target)
             ])
         }
     }
 
-    pub fn tokenize(&self, s: Word) -> Result<TokenData> {
+    // Reserved and contextual words, for a word without escapes. Other
+    // identifiers are left to the caller, so it can avoid copying them.
+    pub fn tokenize_str<'a>(&self, s: &str) -> Option<TokenData<'a>> {
+        match self.reserved.get(s) {
+            Some(&word) => Some(TokenData::Reserved(word)),
+            None => self.contextual.get(s).map(|&atom| TokenData::Identifier(Name::Atom(atom)))
+        }
+    }
+
+    pub fn tokenize<'a>(&self, s: Word) -> Result<TokenData<'a>> {
         Ok(match self.reserved.get(&s.text[..]) {
             Some(&word) if !s.had_escape() => TokenData::Reserved(word),
             Some(&word) => return Err(Error::ReservedWordWithEscapes(word)),
             None => match self.contextual.get(&s.text[..]) {
                 Some(&atom) if !s.had_escape() => TokenData::Identifier(Name::Atom(atom)),
-                _ => TokenData::Identifier(Name::String(s.text))
+                _ => TokenData::Identifier(Name::String(Cow::Owned(s.text)))
             }
         })
     }
@@ -6,7 +6,7 @@ pub trait AtomExt {
     fn is_illegal_strict_binding(&self) -> bool;
 }
 
-impl AtomExt for Name {
+impl<'a> AtomExt for Name<'a> {
     fn is_strict_reserved(&self) -> TriState {
         match self {
             &Name::Atom(ref atom) => atom.is_strict_reserved(),
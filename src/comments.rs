@@ -0,0 +1,608 @@
+//! Attaching collected comments to the nodes they describe.
+//!
+//! Like Esprima's `attachComment`, each comment becomes a leading comment
+//! of the node that follows it, a trailing comment of the node it follows
+//! on the same line, or an inner comment of a node with nothing else in
+//! it to attach to. Comments attach to statements, declarations and
+//! expressions, and to the patterns, properties, class elements, cases,
+//! catch clauses and declarators inside them.
+
+use std::collections::HashMap;
+use joker::token::Comment;
+use joker::track::{Span, TrackingRef};
+use easter::stmt::{Stmt, Block, StmtListItem, ForHead, ForInHead, ForOfHead, Case, Catch, Body, ModItem, Script, Module};
+use easter::expr::{Expr, ExprListItem, Template};
+use easter::decl::{Decl, Dtor, ConstDtor, Import, Export};
+use easter::patt::{Patt, RestPatt, CompoundPatt, PropPatt, AssignTarget};
+use easter::fun::{Fun, Params, Arrow, ArrowBody};
+use easter::class::{Class, ClassElement};
+use easter::obj::{Prop, PropKey, PropVal};
+use easter::id::Id;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Attached {
+    pub leading: Vec<Comment>,
+    pub trailing: Vec<Comment>,
+    pub inner: Vec<Comment>
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Attachments {
+    nodes: HashMap<Span, Attached>,
+    pub dangling: Vec<Comment> // comments of a script or module without any items
+}
+
+impl Attachments {
+    // Nodes with the same span, like an expression statement without a
+    // semicolon and its expression, share their comments.
+    pub fn get<T: TrackingRef>(&self, node: &T) -> Option<&Attached> {
+        node.tracking_ref().as_ref().and_then(|location| self.nodes.get(location))
+    }
+
+    fn place(&mut self, nodes: &[Node], owner: Option<Span>, comment: Comment) {
+        let start = comment.location.start;
+        let end = comment.location.end;
+        if let Some(node) = nodes.iter().find(|node| node.location.start.offset <= start.offset && end.offset <= node.location.end.offset) {
+            return self.place(&node.children, Some(node.location), comment);
+        }
+        let preceding = nodes.iter().filter(|node| node.location.end.offset <= start.offset).max_by_key(|node| node.location.end.offset);
+        let following = nodes.iter().filter(|node| node.location.start.offset >= end.offset).min_by_key(|node| node.location.start.offset);
+        match (preceding, following) {
+            (Some(preceding), following) if following.map_or(true, |following| {
+                preceding.location.end.line == start.line && following.location.start.line != end.line
+            }) => {
+                self.entry(preceding.location).trailing.push(comment);
+            }
+            (_, Some(following)) => {
+                self.entry(following.location).leading.push(comment);
+            }
+            (_, None) => {
+                match owner {
+                    Some(location) => { self.entry(location).inner.push(comment); }
+                    None => { self.dangling.push(comment); }
+                }
+            }
+        }
+    }
+
+    fn entry(&mut self, location: Span) -> &mut Attached {
+        self.nodes.entry(location).or_insert_with(Attached::default)
+    }
+}
+
+pub fn attach_script(script: &Script, comments: Vec<Comment>) -> Attachments {
+    attach(script, comments)
+}
+
+pub fn attach_module(module: &Module, comments: Vec<Comment>) -> Attachments {
+    attach(module, comments)
+}
+
+fn attach<T: Walk>(body: &Body<T>, comments: Vec<Comment>) -> Attachments {
+    let mut tree = Tree { levels: vec![Vec::new()] };
+    body.items.walk(&mut tree);
+    let nodes = tree.levels.pop().unwrap();
+    let mut attachments = Attachments::default();
+    for comment in comments {
+        attachments.place(&nodes, None, comment);
+    }
+    attachments
+}
+
+struct Node {
+    location: Span,
+    children: Vec<Node>
+}
+
+// The nested spans of the nodes that comments can attach to.
+struct Tree {
+    levels: Vec<Vec<Node>>
+}
+
+impl Tree {
+    fn node<T, F>(&mut self, node: &T, walk: F)
+      where T: TrackingRef,
+            F: FnOnce(&mut Tree)
+    {
+        let location = match *node.tracking_ref() {
+            Some(location) => location,
+            None => { return walk(self); }
+        };
+        self.levels.push(Vec::new());
+        walk(self);
+        let children = self.levels.pop().unwrap();
+        self.levels.last_mut().unwrap().push(Node { location: location, children: children });
+    }
+}
+
+trait Walk {
+    fn walk(&self, tree: &mut Tree);
+}
+
+impl<T: Walk> Walk for Vec<T> {
+    fn walk(&self, tree: &mut Tree) {
+        for item in self {
+            item.walk(tree);
+        }
+    }
+}
+
+impl<T: Walk> Walk for Option<T> {
+    fn walk(&self, tree: &mut Tree) {
+        if let Some(ref item) = *self {
+            item.walk(tree);
+        }
+    }
+}
+
+impl<T: Walk> Walk for Box<T> {
+    fn walk(&self, tree: &mut Tree) {
+        (**self).walk(tree);
+    }
+}
+
+impl<T: Walk> Walk for Body<T> {
+    fn walk(&self, tree: &mut Tree) {
+        self.items.walk(tree);
+    }
+}
+
+impl Walk for ModItem {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            ModItem::Import(ref import) => import.walk(tree),
+            ModItem::Export(ref export) => export.walk(tree),
+            ModItem::StmtListItem(ref item) => item.walk(tree)
+        }
+    }
+}
+
+impl Walk for StmtListItem {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            StmtListItem::Decl(ref decl) => decl.walk(tree),
+            StmtListItem::Stmt(ref stmt) => stmt.walk(tree)
+        }
+    }
+}
+
+impl Walk for Import {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |_| { });
+    }
+}
+
+impl Walk for Export {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            Export::Decl(ref decl) => decl.walk(tree),
+            Export::DefaultFun(ref fun) => tree.node(fun, |tree| fun.walk(tree)),
+            Export::DefaultClass(ref class) => tree.node(class, |tree| class.walk(tree)),
+            _ => tree.node(self, |tree| {
+                match *self {
+                    Export::Var(_, ref dtors, _) => dtors.walk(tree),
+                    Export::DefaultExpr(_, ref expr, _) => expr.walk(tree),
+                    _ => { }
+                }
+            })
+        }
+    }
+}
+
+impl Walk for Decl {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            match *self {
+                Decl::Fun(ref fun) => fun.walk(tree),
+                Decl::Class(ref class) => class.walk(tree),
+                Decl::Let(_, ref dtors, _) => dtors.walk(tree),
+                Decl::Const(_, ref dtors, _) => dtors.walk(tree)
+            }
+        });
+    }
+}
+
+impl Walk for Dtor {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            match *self {
+                Dtor::Simple(_, ref id, ref init) => {
+                    id.walk(tree);
+                    init.walk(tree);
+                }
+                Dtor::Compound(_, ref patt, ref init) => {
+                    patt.walk(tree);
+                    init.walk(tree);
+                }
+            }
+        });
+    }
+}
+
+impl Walk for ConstDtor {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            self.patt.walk(tree);
+            self.value.walk(tree);
+        });
+    }
+}
+
+impl Walk for Stmt {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            match *self {
+                Stmt::Empty(_)
+              | Stmt::Break(..)
+              | Stmt::Cont(..)
+              | Stmt::Debugger(..) => { }
+                Stmt::Block(ref block) => block.walk(tree),
+                Stmt::Var(_, ref dtors, _) => dtors.walk(tree),
+                Stmt::Expr(_, ref expr, _)
+              | Stmt::Throw(_, ref expr, _) => expr.walk(tree),
+                Stmt::If(_, ref test, ref cons, ref alt) => {
+                    test.walk(tree);
+                    cons.walk(tree);
+                    alt.walk(tree);
+                }
+                Stmt::Label(_, _, ref body) => body.walk(tree),
+                Stmt::With(_, ref expr, ref body)
+              | Stmt::While(_, ref expr, ref body) => {
+                    expr.walk(tree);
+                    body.walk(tree);
+                }
+                Stmt::Switch(_, ref disc, ref cases) => {
+                    disc.walk(tree);
+                    cases.walk(tree);
+                }
+                Stmt::Return(_, ref arg, _) => arg.walk(tree),
+                Stmt::Try(_, ref body, ref catch, ref finally) => {
+                    body.walk(tree);
+                    catch.walk(tree);
+                    finally.walk(tree);
+                }
+                Stmt::DoWhile(_, ref body, ref test, _) => {
+                    body.walk(tree);
+                    test.walk(tree);
+                }
+                Stmt::For(_, ref head, ref test, ref update, ref body) => {
+                    head.walk(tree);
+                    test.walk(tree);
+                    update.walk(tree);
+                    body.walk(tree);
+                }
+                Stmt::ForIn(_, ref head, ref obj, ref body) => {
+                    head.walk(tree);
+                    obj.walk(tree);
+                    body.walk(tree);
+                }
+                Stmt::ForOf(_, ref head, ref iter, ref body)
+              | Stmt::ForAwait(_, ref head, ref iter, ref body) => {
+                    head.walk(tree);
+                    iter.walk(tree);
+                    body.walk(tree);
+                }
+                Stmt::Fun(ref fun) => fun.walk(tree)
+            }
+        });
+    }
+}
+
+impl Walk for Block {
+    fn walk(&self, tree: &mut Tree) {
+        self.items.walk(tree);
+    }
+}
+
+impl Walk for Case {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            self.test.walk(tree);
+            self.body.walk(tree);
+        });
+    }
+}
+
+impl Walk for Catch {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            self.param.walk(tree);
+            self.body.walk(tree);
+        });
+    }
+}
+
+impl Walk for ForHead {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            ForHead::Var(_, ref dtors) | ForHead::Let(_, ref dtors) => dtors.walk(tree),
+            ForHead::Const(_, ref dtors) => dtors.walk(tree),
+            ForHead::Expr(_, ref expr) => expr.walk(tree)
+        }
+    }
+}
+
+impl Walk for ForInHead {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            ForInHead::VarInit(_, ref id, ref init) => {
+                id.walk(tree);
+                init.walk(tree);
+            }
+            ForInHead::Var(_, ref patt)
+          | ForInHead::Let(_, ref patt)
+          | ForInHead::Const(_, ref patt) => patt.walk(tree),
+            ForInHead::Patt(ref patt) => patt.walk(tree)
+        }
+    }
+}
+
+impl Walk for ForOfHead {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            ForOfHead::Var(_, ref patt)
+          | ForOfHead::Let(_, ref patt)
+          | ForOfHead::Const(_, ref patt) => patt.walk(tree),
+            ForOfHead::Patt(ref patt) => patt.walk(tree)
+        }
+    }
+}
+
+impl Walk for Expr {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            match *self {
+                Expr::This(_)
+              | Expr::Id(_)
+              | Expr::PrivateName(..)
+              | Expr::NewTarget(_)
+              | Expr::ImportMeta(_)
+              | Expr::Super(_)
+              | Expr::True(_)
+              | Expr::False(_)
+              | Expr::Null(_)
+              | Expr::Number(..)
+              | Expr::BigInt(..)
+              | Expr::RegExp(..)
+              | Expr::String(..) => { }
+                Expr::Arr(_, ref elts) => elts.walk(tree),
+                Expr::Obj(_, ref props) => props.walk(tree),
+                Expr::Fun(ref fun) => fun.walk(tree),
+                Expr::Arrow(ref arrow) => arrow.walk(tree),
+                Expr::Class(ref class) => class.walk(tree),
+                Expr::Seq(_, ref exprs) => exprs.walk(tree),
+                Expr::Unop(_, _, ref arg)
+              | Expr::Yield(_, Some(ref arg))
+              | Expr::YieldDelegate(_, ref arg)
+              | Expr::Await(_, ref arg)
+              | Expr::OptionalChain(_, ref arg) => arg.walk(tree),
+                Expr::Yield(_, None) => { }
+                Expr::Binop(_, _, ref left, ref right)
+              | Expr::Logop(_, _, ref left, ref right)
+              | Expr::Brack(_, ref left, ref right)
+              | Expr::OptionalBrack(_, ref left, ref right) => {
+                    left.walk(tree);
+                    right.walk(tree);
+                }
+                Expr::PreInc(_, ref target)
+              | Expr::PostInc(_, ref target)
+              | Expr::PreDec(_, ref target)
+              | Expr::PostDec(_, ref target) => target.walk(tree),
+                Expr::Assign(_, ref patt, ref value) => {
+                    patt.walk(tree);
+                    value.walk(tree);
+                }
+                Expr::BinAssign(_, _, ref target, ref value) => {
+                    target.walk(tree);
+                    value.walk(tree);
+                }
+                Expr::Cond(_, ref test, ref cons, ref alt) => {
+                    test.walk(tree);
+                    cons.walk(tree);
+                    alt.walk(tree);
+                }
+                Expr::Call(_, ref callee, ref args)
+              | Expr::OptionalCall(_, ref callee, ref args) => {
+                    callee.walk(tree);
+                    args.walk(tree);
+                }
+                Expr::New(_, ref callee, ref args) => {
+                    callee.walk(tree);
+                    args.walk(tree);
+                }
+                Expr::Dot(_, ref obj, _)
+              | Expr::PrivateDot(_, ref obj, _)
+              | Expr::OptionalDot(_, ref obj, _)
+              | Expr::OptionalPrivateDot(_, ref obj, _) => obj.walk(tree),
+                Expr::ImportCall(_, ref specifier, ref options) => {
+                    specifier.walk(tree);
+                    options.walk(tree);
+                }
+                Expr::Template(ref template) => template.walk(tree),
+                Expr::TaggedTemplate(_, ref tag, ref template) => {
+                    tag.walk(tree);
+                    template.walk(tree);
+                }
+            }
+        });
+    }
+}
+
+impl Walk for ExprListItem {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            ExprListItem::Expr(ref expr) => expr.walk(tree),
+            ExprListItem::Spread(_, ref expr) => expr.walk(tree)
+        }
+    }
+}
+
+impl Walk for Template {
+    fn walk(&self, tree: &mut Tree) {
+        self.exprs.walk(tree);
+    }
+}
+
+impl Walk for Id {
+    fn walk(&self, _: &mut Tree) { }
+}
+
+impl Walk for AssignTarget {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            AssignTarget::Id(_) => { }
+            AssignTarget::Dot(_, ref obj, _)
+          | AssignTarget::PrivateDot(_, ref obj, _) => obj.walk(tree),
+            AssignTarget::Brack(_, ref obj, ref prop) => {
+                obj.walk(tree);
+                prop.walk(tree);
+            }
+        }
+    }
+}
+
+impl<T: Walk + TrackingRef> Walk for Patt<T> {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            match *self {
+                Patt::Simple(ref target) => target.walk(tree),
+                Patt::Compound(ref patt) => patt.walk(tree),
+                Patt::Default(_, ref patt, ref value) => {
+                    patt.walk(tree);
+                    value.walk(tree);
+                }
+            }
+        });
+    }
+}
+
+impl<T: Walk + TrackingRef> Walk for CompoundPatt<T> {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            CompoundPatt::Arr(_, ref elts, ref rest) => {
+                elts.walk(tree);
+                rest.walk(tree);
+            }
+            CompoundPatt::Obj(_, ref props, ref rest) => {
+                props.walk(tree);
+                rest.walk(tree);
+            }
+        }
+    }
+}
+
+impl<T: Walk + TrackingRef> Walk for PropPatt<T> {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            match *self {
+                PropPatt::Regular(_, ref key, ref patt) => {
+                    key.walk(tree);
+                    patt.walk(tree);
+                }
+                PropPatt::Shorthand(_) => { }
+                PropPatt::ShorthandDefault(_, _, ref value) => value.walk(tree)
+            }
+        });
+    }
+}
+
+impl<T: Walk + TrackingRef> Walk for RestPatt<T> {
+    fn walk(&self, tree: &mut Tree) {
+        self.patt.walk(tree);
+    }
+}
+
+impl<T: Walk> Walk for Fun<T> {
+    fn walk(&self, tree: &mut Tree) {
+        self.id.walk(tree);
+        self.params.walk(tree);
+        self.body.walk(tree);
+    }
+}
+
+impl Walk for Params {
+    fn walk(&self, tree: &mut Tree) {
+        self.list.walk(tree);
+        self.rest.walk(tree);
+    }
+}
+
+impl Walk for Arrow {
+    fn walk(&self, tree: &mut Tree) {
+        self.params.walk(tree);
+        match self.body {
+            ArrowBody::Expr(ref expr) => expr.walk(tree),
+            ArrowBody::Body(ref body) => body.walk(tree)
+        }
+    }
+}
+
+impl<T: Walk> Walk for Class<T> {
+    fn walk(&self, tree: &mut Tree) {
+        self.id.walk(tree);
+        self.extends.walk(tree);
+        self.body.walk(tree);
+    }
+}
+
+impl Walk for ClassElement {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            match *self {
+                ClassElement::Method(_, _, ref fun) => fun.walk(tree),
+                ClassElement::Getter(_, _, ref key, ref body) => {
+                    key.walk(tree);
+                    body.walk(tree);
+                }
+                ClassElement::Setter(_, _, ref key, ref param, ref body) => {
+                    key.walk(tree);
+                    param.walk(tree);
+                    body.walk(tree);
+                }
+                ClassElement::Field(_, _, ref key, ref value) => {
+                    key.walk(tree);
+                    value.walk(tree);
+                }
+                ClassElement::StaticBlock(_, ref items) => items.walk(tree)
+            }
+        });
+    }
+}
+
+impl Walk for Prop {
+    fn walk(&self, tree: &mut Tree) {
+        tree.node(self, |tree| {
+            match *self {
+                Prop::Regular(_, ref key, ref value) => {
+                    key.walk(tree);
+                    value.walk(tree);
+                }
+                Prop::Method(ref fun) => fun.walk(tree),
+                Prop::Shorthand(_) => { }
+                Prop::Spread(_, ref expr) => expr.walk(tree),
+                Prop::CoverInit(_, _, ref value) => value.walk(tree)
+            }
+        });
+    }
+}
+
+impl Walk for PropKey {
+    fn walk(&self, tree: &mut Tree) {
+        if let PropKey::Computed(_, ref expr) = *self {
+            expr.walk(tree);
+        }
+    }
+}
+
+impl Walk for PropVal {
+    fn walk(&self, tree: &mut Tree) {
+        match *self {
+            PropVal::Init(ref expr) => expr.walk(tree),
+            PropVal::Get(_, ref body) => body.walk(tree),
+            PropVal::Set(_, ref param, ref body) => {
+                param.walk(tree);
+                body.walk(tree);
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@ use std::rc::Rc;
 use std::mem::replace;
 use joker::word::Name;
 use joker::track::span;
+use joker::reader::Input;
 use easter::stmt::Stmt;
 use easter::id::Id;
 use result::Result;
@@ -14,9 +15,13 @@ pub trait WithContext {
       where F: FnOnce(&mut Self) -> Result<Stmt>;
     fn allow_in<F, T>(&mut self, allow_in: bool, parse: F) -> Result<T>
       where F: FnOnce(&mut Self) -> Result<T>;
+    fn in_generator<F, T>(&mut self, generator: bool, parse: F) -> Result<T>
+      where F: FnOnce(&mut Self) -> Result<T>;
+    fn in_async<F, T>(&mut self, asynchronous: bool, parse: F) -> Result<T>
+      where F: FnOnce(&mut Self) -> Result<T>;
 }
 
-impl<I: Iterator<Item=char>> WithContext for Parser<I> {
+impl<'a, I: Input<'a>> WithContext for Parser<'a, I> {
     fn with_labels<F>(&mut self, mut labels: Vec<Id>, label_type: LabelType, op: F) -> Result<Stmt>
       where F: FnOnce(&mut Self) -> Result<Stmt>
     {
@@ -47,6 +52,24 @@ impl<I: Iterator<Item=char>> WithContext for Parser<I> {
         replace(&mut self.context.allow_in, allow_in);
         result
     }
+
+    fn in_generator<F, T>(&mut self, generator: bool, parse: F) -> Result<T>
+      where F: FnOnce(&mut Self) -> Result<T>
+    {
+        let generator = replace(&mut self.context.generator, generator);
+        let result = parse(self);
+        replace(&mut self.context.generator, generator);
+        result
+    }
+
+    fn in_async<F, T>(&mut self, asynchronous: bool, parse: F) -> Result<T>
+      where F: FnOnce(&mut Self) -> Result<T>
+    {
+        let asynchronous = replace(&mut self.context.asynchronous, asynchronous);
+        let result = parse(self);
+        replace(&mut self.context.asynchronous, asynchronous);
+        result
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
@@ -73,7 +96,14 @@ pub struct Context {
     pub iteration: bool,
     pub switch: bool,
     pub allow_in: bool,
-    pub labels: HashMap<Rc<Name>, LabelType>
+    pub generator: bool,
+    pub asynchronous: bool,
+    pub parameters: bool,
+    pub super_property: bool,
+    pub super_call: bool,
+    pub initializer: bool,  // in a class field initializer or static block
+    pub static_block: bool,
+    pub labels: HashMap<Rc<Name<'static>>, LabelType>
 }
 
 impl Context {
@@ -84,6 +114,13 @@ impl Context {
             iteration: false,
             switch: false,
             allow_in: true,
+            generator: false,
+            asynchronous: false,
+            parameters: false,
+            super_property: false,
+            super_call: false,
+            initializer: false,
+            static_block: false,
             labels: HashMap::new()
         }
     }
@@ -95,7 +132,33 @@ impl Context {
             iteration: false,
             switch: false,
             allow_in: true,
+            generator: false,
+            asynchronous: false,
+            parameters: false,
+            super_property: false,
+            super_call: false,
+            initializer: false,
+            static_block: false,
             labels: HashMap::new()
         }
     }
+
+    pub fn new_method(&self, super_call: bool) -> Context {
+        Context {
+            super_property: true,
+            super_call: super_call,
+            .. self.new_function()
+        }
+    }
+
+    // Arrow functions see the `super` and `arguments` bindings of their
+    // enclosing function.
+    pub fn new_arrow(&self) -> Context {
+        Context {
+            super_property: self.super_property,
+            super_call: self.super_call,
+            initializer: self.initializer,
+            .. self.new_function()
+        }
+    }
 }
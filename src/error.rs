@@ -3,35 +3,66 @@ use joker::token::{Token, StringLiteral};
 use joker::track::*;
 use joker::word::Atom;
 use easter::id::Id;
+use easter::obj::DotKey;
 use easter::decl::{Import, Export};
-use easter::patt::CompoundPatt;
+use easter::patt::Patt;
 use easter::cover;
 use result::Result;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Error {
-    UnexpectedToken(Token),
-    FailedASI(Token),
+    UnexpectedToken(Token<'static>),
+    FailedASI(Token<'static>),
     LexError(joker::error::Error),
+    InvalidRegExp(joker::regexp::Error),
     TopLevelReturn(Span),
-    IllegalBreak(Token),
-    IllegalContinue(Token),
+    IllegalBreak(Token<'static>),
+    IllegalContinue(Token<'static>),
     InvalidLabel(Id),
     InvalidLabelType(Id),
     ContextualKeyword(Span, Atom),
     IllegalStrictBinding(Span, Atom),
-    UnexpectedDirective(Option<Span>, StringLiteral),
+    UnexpectedDirective(Option<Span>, StringLiteral<'static>),
     UnexpectedModule(Option<Span>),
     ImportInScript(Import),
     ExportInScript(Export),
+    ImportMetaInScript(Option<Span>),
+    DuplicateImport(Id),
+    DuplicateParam(Id),
+    DuplicateExport(Option<Span>, String),
+    DuplicateImportAttribute(Option<Span>, String),
+    UnresolvedExport(DotKey),
     ForOfLetExpr(Span),
-    DuplicateDefault(Token),
-    StrictWith(Token),
-    ThrowArgument(Token),
-    OrphanTry(Token),
+    ForAwaitWithoutOf(Span),
+    DuplicateDefault(Token<'static>),
+    StrictWith(Token<'static>),
+    ThrowArgument(Token<'static>),
+    OrphanTry(Token<'static>),
     InvalidLHS(Option<Span>, cover::Error),
+    InvalidParams(Option<Span>, cover::Error),
+    InvalidTemplateEscape(Span),
+    IllegalSuper(Token<'static>),
+    IllegalYield(Token<'static>),
+    IllegalAwait(Token<'static>),
+    IllegalArguments(Span),
+    DuplicateConstructor(Option<Span>),
+    SpecialConstructor(Option<Span>),
+    StaticPrototype(Option<Span>),
     UnsupportedFeature(&'static str),
-    CompoundParamWithUseStrict(CompoundPatt<Id>)
+    CoverInitializedName(Span),
+    DuplicateProto(Option<Span>),
+    CommaAfterRest(Span),
+    MixedCoalesce(Option<Span>),
+    UnaryBeforeExp(Option<Span>),
+    DuplicatePrivateName(Option<Span>, String),
+    UndeclaredPrivateName(DotKey),
+    PrivateConstructor(Option<Span>),
+    DeletePrivateName(Option<Span>),
+    MisplacedPrivateName(Option<Span>),
+    LegacyEscape(Option<Span>),
+    FunctionStatement(Option<Span>),
+    LabelledFunction(Option<Span>),
+    NonSimpleParamWithUseStrict(Patt<Id>)
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -1,13 +1,19 @@
 use joker::track::{Span, span};
-use joker::token::Token;
 use easter::punc::Unop;
-use easter::expr::{Expr, ExprListItem};
+use easter::expr::{Expr, ExprListItem, Template};
 use easter::obj::DotKey;
+use easter::fun::Params;
 
 pub enum Prefix {
     Unop(Unop),
     Inc(Span),
-    Dec(Span)
+    Dec(Span),
+    Await(Span)
+}
+
+pub enum Parenthesized {
+    Expr(Expr),
+    Params(Params)
 }
 
 pub enum Postfix {
@@ -16,19 +22,27 @@ pub enum Postfix {
 }
 
 pub enum Deref {
-    Brack(Expr, Token),
-    Dot(DotKey)
+    Brack(Expr, Span), // the closing bracket
+    Dot(DotKey),
+    Private(DotKey),
+    Template(Template)
 }
 
 impl Deref {
     pub fn append_to(self, expr: Expr) -> Expr {
         match self {
             Deref::Brack(deref, end) => {
-                Expr::Brack(span(&expr, &Some(end.location)), Box::new(expr), Box::new(deref))
+                Expr::Brack(span(&expr, &Some(end)), Box::new(expr), Box::new(deref))
             }
             Deref::Dot(key) => {
                 Expr::Dot(span(&expr, &key), Box::new(expr), key)
             }
+            Deref::Private(key) => {
+                Expr::PrivateDot(span(&expr, &key), Box::new(expr), key)
+            }
+            Deref::Template(tmpl) => {
+                Expr::TaggedTemplate(span(&expr, &tmpl), Box::new(expr), tmpl)
+            }
         }
     }
 }
@@ -40,16 +54,16 @@ pub enum Suffix {
 
 pub struct Arguments {
     pub args: Vec<ExprListItem>,
-    pub end: Token
+    pub end: Span // the closing paren
 }
 
 impl Arguments {
     pub fn append_to(self, expr: Expr) -> Expr {
-        Expr::Call(span(&expr, &Some(self.end.location)), Box::new(expr), self.args)
+        Expr::Call(span(&expr, &Some(self.end)), Box::new(expr), self.args)
     }
 
-    pub fn append_to_new(self, new: Token, expr: Expr) -> Expr {
-        Expr::New(span(&Some(new.location), &Some(self.end.location)), Box::new(expr), Some(self.args))
+    pub fn append_to_new(self, new: Span, expr: Expr) -> Expr {
+        Expr::New(span(&Some(new), &Some(self.end)), Box::new(expr), Some(self.args))
     }
 }
 
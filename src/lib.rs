@@ -17,6 +17,7 @@ extern crate joker;
 
 pub mod error;
 pub mod result;
+pub mod comments;
 mod context;
 mod tokens;
 mod atom;
@@ -29,23 +30,31 @@ mod stack;
 // type Parser<I: Iterator<Item=char>> = parser::Parser<I>;
 
 use easter::stmt::{Script, Module};
-use parser::Parser;
 use result::Result;
 
-pub use parser::Program;
+pub use parser::{Parser, Program};
 
 pub fn script(s: &str) -> Result<Script> {
-    Parser::from_chars(s.chars()).script(false)
+    Parser::from_slice(s).script(false)
 }
 
 pub fn strict(s: &str) -> Result<Script> {
-    Parser::from_chars(s.chars()).script(true)
+    Parser::from_slice(s).script(true)
+}
+
+// Like `script`, but accepts function declarations as the bodies of
+// `if` statements and labels in sloppy mode code, as web browsers do.
+// The rest of the Annex B legacy syntax is accepted by `script` too.
+pub fn web_script(s: &str) -> Result<Script> {
+    let mut parser = Parser::from_slice(s);
+    parser.web_compat = true;
+    parser.script(false)
 }
 
 pub fn module(s: &str) -> Result<Module> {
-    Parser::from_chars(s.chars()).module()
+    Parser::from_slice(s).module()
 }
 
 pub fn program(s: &str) -> Result<Program> {
-    Parser::from_chars(s.chars()).program()
+    Parser::from_slice(s).program()
 }
@@ -1,19 +1,23 @@
 use joker::track::*;
-use joker::token::{Token, TokenData};
+use joker::token::{Token, TokenData, StringLiteral, Comment, SourceToken};
 use joker::word::{Atom, Name, Reserved};
 use joker::lexer::Lexer;
+use joker::reader::{Input, Reader, StrReader};
+use joker::regexp;
 use easter::stmt::{Stmt, Block, StmtListItem, ForHead, ForInHead, ForOfHead, Case, Catch, Script, Dir, ModItem, Module};
-use easter::expr::{Expr, ExprListItem};
-use easter::decl::{Decl, Dtor, ConstDtor, DtorExt};
-use easter::patt::{Patt, RestPatt, CompoundPatt};
-use easter::fun::{Fun, Params};
+use easter::expr::{Expr, ExprListItem, Template};
+use easter::decl::{Decl, Dtor, ConstDtor, DtorExt, Import, ImportSpec, ImportAttribute, Export, ExportSpec};
+use easter::patt::{Patt, RestPatt, CompoundPatt, PropPatt};
+use easter::fun::{Fun, Params, Arrow, ArrowBody};
+use easter::class::{Class, ClassElement};
 use easter::obj::{PropKey, PropVal, Prop, DotKey};
 use easter::id::{Id, IdExt};
-use easter::punc::{Unop, UnopTag, ToOp, Op};
-use easter::cover::{IntoAssignTarget, IntoAssignPatt};
+use easter::punc::{Unop, UnopTag, BinopTag, ToOp, Op};
+use easter::cover::{self, IntoAssignTarget, IntoAssignPatt, IntoParams};
 
 use std::rc::Rc;
 use std::mem::replace;
+use std::collections::{HashMap, HashSet};
 use context::{Context, LabelType, WithContext, Goal};
 use tokens::{First, Follows, HasLabelType};
 use atom::AtomExt;
@@ -22,18 +26,154 @@ use result::Result;
 use error::{Error, Check};
 use track::{SpanTracker, Tracking};
 use state::State;
-use expr::{Deref, Suffix, Arguments, Prefix, Postfix};
+use expr::{Deref, Suffix, Arguments, Prefix, Postfix, Parenthesized};
 use stack::{Stack, Infix};
 
 use tristate::TriState;
 pub use tristate::TriState as Strict;
 
-pub struct Parser<I> {
+pub struct Parser<'a, I> {
     pub goal: Goal,
     pub validate: bool,       // should we do strict mode validation as eagerly as possible?
+    pub web_compat: bool,     // should sloppy scripts accept functions as `if` and label bodies?
     pub deferred: Vec<Check>, // strict mode checks that haven't been performed yet
-    pub lexer: Lexer<I>,
-    pub context: Context
+    pub lexer: Lexer<'a, I>,
+    pub context: Context,
+    cover: Cover,             // early errors pending on a cover grammar
+    classes: Vec<ClassScope>  // private names of the enclosing class bodies
+}
+
+// Some early errors in object and array literals depend on whether the
+// literal is later reinterpreted as a pattern, so they are held back
+// until that is known.
+struct Cover {
+    expr_error: Option<Error>,   // raised unless the literal becomes a pattern
+    patt_error: Option<Error>,   // raised if the literal becomes a pattern
+    params_error: Option<Error>, // raised if the enclosing parentheses become arrow parameters
+    parens: Vec<Span>            // parenthesized expressions, which can't be parameter bindings
+}
+
+impl Cover {
+    fn new() -> Cover {
+        Cover { expr_error: None, patt_error: None, params_error: None, parens: Vec::new() }
+    }
+
+    fn defer_expr_error(&mut self, error: Error) {
+        if self.expr_error.is_none() {
+            self.expr_error = Some(error);
+        }
+    }
+
+    fn defer_patt_error(&mut self, error: Error) {
+        if self.patt_error.is_none() {
+            self.patt_error = Some(error);
+        }
+    }
+
+    fn defer_params_error(&mut self, error: Error) {
+        if self.params_error.is_none() {
+            self.params_error = Some(error);
+        }
+    }
+
+    fn merge(&mut self, other: Cover) {
+        if let Some(error) = other.expr_error {
+            self.defer_expr_error(error);
+        }
+        if let Some(error) = other.patt_error {
+            self.defer_patt_error(error);
+        }
+        if let Some(error) = other.params_error {
+            self.defer_params_error(error);
+        }
+        self.parens.extend(other.parens);
+    }
+
+    // However a literal is settled, it may still be part of enclosing
+    // arrow parameters, so what they need is passed on to `outer`.
+    fn into_expr(self, outer: &mut Cover) -> Result<()> {
+        if let Some(error) = self.params_error {
+            outer.defer_params_error(error);
+        }
+        outer.parens.extend(self.parens);
+        match self.expr_error {
+            Some(error) => Err(error),
+            None => Ok(())
+        }
+    }
+
+    fn into_patt(self, outer: &mut Cover) -> Result<()> {
+        if let Some(error) = self.params_error {
+            outer.defer_params_error(error);
+        }
+        outer.parens.extend(self.parens);
+        match self.patt_error {
+            Some(error) => Err(error),
+            None => Ok(())
+        }
+    }
+
+    fn into_params(self, params: &Params) -> Result<()> {
+        if let Some(error) = self.patt_error.or(self.params_error) {
+            return Err(error);
+        }
+        let patts = params.list.iter().chain(params.rest.iter().map(|rest| &rest.patt));
+        for patt in patts {
+            if let Some(location) = parenthesized_binding(patt, &self.parens) {
+                return Err(Error::InvalidParams(params.location, cover::Error::InvalidBinding(Some(location))));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Private names can be used before they're declared in a class body, so
+// references are collected and only resolved once the body is complete.
+struct ClassScope {
+    declared: HashMap<String, PrivateKind>,
+    unresolved: Vec<DotKey>
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PrivateKind {
+    Getter(bool),
+    Setter(bool),
+    Accessor,
+    Other
+}
+
+impl ClassScope {
+    fn new() -> ClassScope {
+        ClassScope { declared: HashMap::new(), unresolved: Vec::new() }
+    }
+
+    fn declare(&mut self, element: &ClassElement) -> Result<()> {
+        let (location, name, kind) = match *element {
+            ClassElement::Method(_, _, Fun { id: PropKey::Private(location, ref name), .. })
+          | ClassElement::Field(_, _, PropKey::Private(location, ref name), _) => {
+                (location, name, PrivateKind::Other)
+            }
+            ClassElement::Getter(_, is_static, PropKey::Private(location, ref name), _) => {
+                (location, name, PrivateKind::Getter(is_static))
+            }
+            ClassElement::Setter(_, is_static, PropKey::Private(location, ref name), _, _) => {
+                (location, name, PrivateKind::Setter(is_static))
+            }
+            _ => { return Ok(()); }
+        };
+        if name == "constructor" {
+            return Err(Error::PrivateConstructor(location));
+        }
+        // A getter and setter of the same placement may share a name.
+        let kind = match (self.declared.get(name).cloned(), kind) {
+            (None, kind) => kind,
+            (Some(PrivateKind::Getter(a)), PrivateKind::Setter(b))
+          | (Some(PrivateKind::Setter(a)), PrivateKind::Getter(b)) if a == b => PrivateKind::Accessor,
+            _ => { return Err(Error::DuplicatePrivateName(location, name.clone())); }
+        };
+        self.declared.insert(name.clone(), kind);
+        Ok(())
+    }
 }
 
 enum ProgramItems {
@@ -75,7 +215,7 @@ impl Untrack for Program {
 }
 
 fn unexpected_module(module: Module) -> Error {
-    let Module { location, dirs, items } = module;
+    let Module { location, dirs, items, .. } = module;
 
     // If there's a "use module" pragma, blame that.
     if let Some(Dir { location, string, .. }) = dirs.into_iter().find(|dir| dir.pragma() == "use module") {
@@ -98,6 +238,80 @@ fn unexpected_module(module: Module) -> Error {
     return Error::UnexpectedModule(location);
 }
 
+fn labelled_function(stmt: &Stmt) -> Option<Option<Span>> {
+    match *stmt {
+        Stmt::Label(_, _, ref body) => labelled_function(body),
+        Stmt::Fun(ref fun) => Some(fun.location),
+        _ => None
+    }
+}
+
+// Finds a part of a binding pattern that was written in parentheses.
+fn parenthesized_binding(patt: &Patt<Id>, parens: &[Span]) -> Option<Span> {
+    if let Some(location) = *patt.tracking_ref() {
+        if parens.contains(&location) {
+            return Some(location);
+        }
+    }
+    match *patt {
+        Patt::Simple(_) => None,
+        Patt::Default(_, ref patt, _) => parenthesized_binding(patt, parens),
+        Patt::Compound(CompoundPatt::Arr(_, ref elts, ref rest)) => {
+            elts.iter()
+                .filter_map(|elt| elt.as_ref())
+                .chain(rest.iter().map(|rest| &rest.patt))
+                .filter_map(|elt| parenthesized_binding(elt, parens))
+                .next()
+        }
+        Patt::Compound(CompoundPatt::Obj(_, ref props, ref rest)) => {
+            props.iter()
+                .filter_map(|prop| match *prop {
+                    PropPatt::Regular(_, _, ref patt) => Some(patt),
+                    _ => None
+                })
+                .chain(rest.iter().map(|rest| &rest.patt))
+                .filter_map(|patt| parenthesized_binding(patt, parens))
+                .next()
+        }
+    }
+}
+
+fn is_private_reference(expr: &Expr) -> bool {
+    match *expr {
+        Expr::PrivateDot(..) | Expr::OptionalPrivateDot(..) => true,
+        Expr::OptionalChain(_, ref chain) => is_private_reference(chain),
+        _ => false
+    }
+}
+
+// Local exports may refer to declarations that follow them, so they
+// can only be resolved once the whole module has been parsed.
+fn resolve_exports(items: &[ModItem]) -> Result<()> {
+    let mut declared = HashSet::new();
+
+    for item in items {
+        let names = match *item {
+            ModItem::Import(ref import) => import.bindings(),
+            ModItem::Export(ref export) => export.bindings(),
+            ModItem::StmtListItem(StmtListItem::Decl(ref decl)) => decl.bound_names(),
+            ModItem::StmtListItem(StmtListItem::Stmt(ref stmt)) => stmt.var_declared_names()
+        };
+        declared.extend(names.into_iter().map(|id| id.name.as_ref()));
+    }
+
+    for item in items {
+        if let ModItem::Export(Export::Local(_, ref specs, _)) = *item {
+            for spec in specs {
+                if !declared.contains(&spec.local.value[..]) {
+                    return Err(Error::UnresolvedExport(spec.local.clone()));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 impl Program {
     pub fn script(self) -> Result<Script> {
         match self {
@@ -126,10 +340,11 @@ impl Program {
                     check.perform(true)?;
                 }
 
-                let Script { location, dirs, items } = script;
+                let Script { location, hashbang, dirs, items } = script;
 
                 Ok(Module {
                     location: location,
+                    hashbang: hashbang,
                     dirs: dirs,
                     items: items.into_iter().map(|item| item.into_mod_item()).collect()
                 })
@@ -139,19 +354,33 @@ impl Program {
     }
 }
 
-impl<I: Iterator<Item=char>> Parser<I> {
-    pub fn from_chars(i: I) -> Parser<I> {
+impl<I: Iterator<Item=char>> Parser<'static, Reader<I>> {
+    pub fn from_chars(i: I) -> Parser<'static, Reader<I>> {
         let lexer = Lexer::new(i);
         Parser::new(true, lexer)
     }
+}
 
-    pub fn new(validate: bool, lexer: Lexer<I>) -> Parser<I> {
+impl<'a> Parser<'a, StrReader<'a>> {
+    // Parses a string in place, which is faster and allocates less than
+    // parsing its chars.
+    pub fn from_slice(s: &'a str) -> Parser<'a, StrReader<'a>> {
+        let lexer = Lexer::from_slice(s);
+        Parser::new(true, lexer)
+    }
+}
+
+impl<'a, I: Input<'a>> Parser<'a, I> {
+    pub fn new(validate: bool, lexer: Lexer<'a, I>) -> Parser<'a, I> {
         Parser {
             goal: Goal::Unknown,
             validate: validate,
+            web_compat: false,
             deferred: Vec::new(),
             lexer: lexer,
-            context: Context::new()
+            context: Context::new(),
+            cover: Cover::new(),
+            classes: Vec::new()
         }
     }
 
@@ -160,18 +389,18 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn unexpected<T>(&mut self) -> Result<T> {
-        Err(Error::UnexpectedToken(self.lexer.reread_token()))
+        Err(Error::UnexpectedToken(self.lexer.reread_token().into_owned()))
     }
 
     fn match_directive_opt(&mut self) -> Result<Option<Dir>> {
-        let span = self.start();
+        let span = self.start()?;
         let token1 = self.read()?;
 
         if let TokenData::String(ref literal) = token1.value {
             if !self.peek()?.expression_continuation() {
                 return Ok(Some(span.end_with_auto_semi(self, Newline::Required, |semi| Dir {
                     location: None,
-                    string: literal.clone(),
+                    string: literal.clone().into_owned(),
                     semi: semi
                 })?));
             }
@@ -184,6 +413,29 @@ impl<I: Iterator<Item=char>> Parser<I> {
     fn set_module(&mut self) {
         self.goal = Goal::Module;
         self.context.strict = Strict::Yes;
+        // Modules allow `await` at the top level.
+        self.context.asynchronous = true;
+        self.lexer.allow_html_comments(false);
+    }
+
+    // Comments are only kept if they're asked for before parsing.
+    pub fn collect_comments(&mut self) {
+        self.lexer.collect_comments();
+    }
+
+    // The comments parsed so far, in source order.
+    pub fn take_comments(&mut self) -> Vec<Comment> {
+        self.lexer.take_comments()
+    }
+
+    // Keeps every token with its exact source and leading trivia, for
+    // tools that need to print back untouched parts of the source.
+    pub fn collect_tokens(&mut self) {
+        self.lexer.collect_tokens();
+    }
+
+    pub fn take_tokens(&mut self) -> Vec<SourceToken<'a>> {
+        self.lexer.take_tokens()
     }
 
     pub fn module(&mut self) -> Result<Module> {
@@ -192,6 +444,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
         self.span(&mut |this| {
             Ok(Module {
                 location: None,
+                hashbang: this.hashbang()?,
                 dirs: this.body_directives()?,
                 items: this.module_items()?
             })
@@ -201,6 +454,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
     pub fn program(&mut self) -> Result<Program> {
         debug_assert_eq!(self.goal, Goal::Unknown);
         self.span(&mut |this| {
+            let hashbang = this.hashbang()?;
             let dirs = this.body_directives()?;
 
             match this.program_items()? {
@@ -208,12 +462,14 @@ impl<I: Iterator<Item=char>> Parser<I> {
                     let checks = this.take_deferred();
                     Ok(Program::Ambiguous(checks, Script {
                         location: None,
+                        hashbang: hashbang,
                         dirs: dirs,
                         items: items
                     }))
                 }
                 ProgramItems::Module(items) => Ok(Program::Module(Module {
                     location: None,
+                    hashbang: hashbang,
                     dirs: dirs,
                     items: items
                 }))
@@ -225,19 +481,52 @@ impl<I: Iterator<Item=char>> Parser<I> {
         debug_assert_eq!(self.goal, Goal::Unknown);
         self.goal = Goal::Script;
         self.context.strict = TriState::from(strict);
-        self.script_body()
+        let mut script = self.script_body()?;
+        script.hashbang = self.lexer.hashbang().map(String::from);
+        Ok(script)
+    }
+
+    // The lexer skips over a hashbang line along with the whitespace
+    // before the first token.
+    fn hashbang(&mut self) -> Result<Option<String>> {
+        self.peek()?;
+        Ok(self.lexer.hashbang().map(String::from))
     }
 
     fn script_body(&mut self) -> Result<Script> {
         self.span(&mut |this| {
             Ok(Script {
                 location: None,
+                hashbang: None,
                 dirs: this.body_directives()?,
-                items: this.statement_list()?
+                items: this.script_items()?
             })
         })
     }
 
+    fn script_items(&mut self) -> Result<Vec<StmtListItem>> {
+        let mut items = Vec::new();
+
+        while !self.peek()?.follow_statement_list() {
+            if !self.context.function {
+                match self.peek()?.value {
+                    TokenData::Reserved(Reserved::Import) => {
+                        if self.import_declaration_follows()? {
+                            return Err(Error::ImportInScript(self.import_declaration()?));
+                        }
+                    }
+                    TokenData::Reserved(Reserved::Export) => {
+                        return Err(Error::ExportInScript(self.export_declaration()?));
+                    }
+                    _ => { }
+                }
+            }
+            items.push(self.stmt_list_item(true)?);
+        }
+
+        Ok(items)
+    }
+
     fn body_directives(&mut self) -> Result<Vec<Dir>> {
         let mut dirs = Vec::new();
 
@@ -254,6 +543,11 @@ impl<I: Iterator<Item=char>> Parser<I> {
             dirs.push(dir);
         }
 
+        // A "use strict" directive also applies to the ones before it.
+        for dir in &dirs {
+            self.check_legacy_escape(dir.location, &dir.string)?;
+        }
+
         Ok(dirs)
     }
 
@@ -275,18 +569,25 @@ impl<I: Iterator<Item=char>> Parser<I> {
         let mut stmts: Vec<StmtListItem> = Vec::new();
 
         loop {
-            match self.peek()?.value {
+            let module = match self.peek()?.value {
                 TokenData::EOF => break,
-                TokenData::Reserved(Reserved::Import)
-              | TokenData::Reserved(Reserved::Export) => {
-                    self.force_deferred_module_validation()?;
-                    let items = self.more_module_items(stmts.into_iter().map(|stmt| stmt.into_mod_item()).collect())?;
-                    return Ok(ProgramItems::Module(items));
+                TokenData::Reserved(Reserved::Import) => self.import_declaration_follows()?,
+                TokenData::Reserved(Reserved::Export) => true,
+                _ => false
+            };
+
+            if !module {
+                stmts.push(self.stmt_list_item(true)?);
+                // An `import.meta` inside the statement may have settled the goal.
+                if self.goal != Goal::Module {
+                    continue;
                 }
-                _ => { }
             }
 
-            stmts.push(self.stmt_list_item(true)?);
+            self.force_deferred_module_validation()?;
+            self.set_module();
+            let items = self.more_module_items(stmts.into_iter().map(|stmt| stmt.into_mod_item()).collect())?;
+            return Ok(ProgramItems::Module(items));
         }
 
         Ok(ProgramItems::Script(stmts))
@@ -297,22 +598,309 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn more_module_items(&mut self, mut items: Vec<ModItem>) -> Result<Vec<ModItem>> {
+        let mut imported = HashSet::new();
+        let mut exported = HashSet::new();
+
         loop {
             match self.peek()?.value {
                 TokenData::EOF => break,
-                // ES6: import declaration
-                TokenData::Reserved(Reserved::Import) => unimplemented!(),
-                // ES6: export declaration
-                TokenData::Reserved(Reserved::Export) => unimplemented!(),
+                TokenData::Reserved(Reserved::Import) => {
+                    if self.import_declaration_follows()? {
+                        let import = self.import_declaration()?;
+                        for id in import.bindings() {
+                            if !imported.insert(id.name.clone()) {
+                                return Err(Error::DuplicateImport(id.clone()));
+                            }
+                        }
+                        items.push(ModItem::Import(import));
+                        continue;
+                    }
+                }
+                TokenData::Reserved(Reserved::Export) => {
+                    let export = self.export_declaration()?;
+                    for (location, name) in export.exported_names() {
+                        if !exported.insert(name.to_string()) {
+                            return Err(Error::DuplicateExport(location, name.to_string()));
+                        }
+                    }
+                    items.push(ModItem::Export(export));
+                    continue;
+                }
                 _ => { }
             }
 
             items.push(ModItem::StmtListItem(self.stmt_list_item(true)?));
         }
 
+        resolve_exports(&items)?;
+
         Ok(items)
     }
 
+    // An `import` followed by `(` or `.` starts an expression instead.
+    fn import_declaration_follows(&mut self) -> Result<bool> {
+        let token = self.read()?;
+        let follows = match self.peek()?.value {
+            TokenData::LParen | TokenData::Dot => false,
+            _ => true
+        };
+        self.lexer.unread_token(token);
+        Ok(follows)
+    }
+
+    fn import_declaration(&mut self) -> Result<Import> {
+        let span = self.start()?;
+        self.reread(TokenData::Reserved(Reserved::Import));
+        let import = match self.peek()?.value {
+            TokenData::String(_) => {
+                let source = self.module_specifier()?;
+                Import::ForEffect(None, source, self.import_attributes()?)
+            }
+            _ => self.import_clause()?
+        };
+        span.end_with_auto_semi(self, Newline::Required, |_| import)
+    }
+
+    fn import_clause(&mut self) -> Result<Import> {
+        let default = self.id_opt(true)?;
+        if default.is_some() && !self.matches(TokenData::Comma)? {
+            let source = self.from_clause()?;
+            let attributes = self.import_attributes()?;
+            return Ok(Import::Named(None, default, Vec::new(), source, attributes));
+        }
+        match self.peek()?.value {
+            TokenData::Star => {
+                self.reread(TokenData::Star);
+                self.expect(TokenData::Identifier(Name::Atom(Atom::As)))?;
+                let ns = self.id(true)?;
+                let source = self.from_clause()?;
+                let attributes = self.import_attributes()?;
+                Ok(Import::Namespace(None, default, ns, source, attributes))
+            }
+            TokenData::LBrace => {
+                let specs = self.import_specifiers()?;
+                let source = self.from_clause()?;
+                let attributes = self.import_attributes()?;
+                // `import {} from "m"` binds nothing, just like `import "m"`.
+                if default.is_none() && specs.is_empty() {
+                    return Ok(Import::ForEffect(None, source, attributes));
+                }
+                Ok(Import::Named(None, default, specs, source, attributes))
+            }
+            _ => self.unexpected()
+        }
+    }
+
+    fn import_specifiers(&mut self) -> Result<Vec<ImportSpec>> {
+        self.reread(TokenData::LBrace);
+        let mut specs = Vec::new();
+        while !self.matches(TokenData::RBrace)? {
+            specs.push(self.import_specifier()?);
+            if !self.matches(TokenData::Comma)? {
+                self.expect(TokenData::RBrace)?;
+                break;
+            }
+        }
+        Ok(specs)
+    }
+
+    fn import_specifier(&mut self) -> Result<ImportSpec> {
+        self.span(&mut |this| {
+            let token = this.read()?;
+            let imported = DotKey {
+                location: Some(token.location),
+                value: match token.value.clone() {
+                    TokenData::Identifier(name) => name.into_string(),
+                    TokenData::Reserved(word) => word.into_string(),
+                    _ => { return Err(Error::UnexpectedToken(token.into_owned())); }
+                }
+            };
+            let local = if this.matches(TokenData::Identifier(Name::Atom(Atom::As)))? {
+                this.id(true)?
+            } else {
+                // Without a renaming, the imported name must itself
+                // be a valid binding.
+                match token.value {
+                    TokenData::Identifier(name) => this.new_id(true, name, token.location)?,
+                    _ => { return Err(Error::UnexpectedToken(token.into_owned())); }
+                }
+            };
+            Ok(ImportSpec {
+                location: None,
+                imported: imported,
+                local: local
+            })
+        })
+    }
+
+    fn export_declaration(&mut self) -> Result<Export> {
+        let span = self.start()?;
+        self.reread(TokenData::Reserved(Reserved::Export));
+        match self.peek()?.value {
+            TokenData::Reserved(Reserved::Default) => {
+                self.reread(TokenData::Reserved(Reserved::Default));
+                self.export_default(span)
+            }
+            TokenData::Star => {
+                self.reread(TokenData::Star);
+                let ns = if self.matches(TokenData::Identifier(Name::Atom(Atom::As)))? {
+                    Some(self.id_name()?)
+                } else {
+                    None
+                };
+                let source = self.from_clause()?;
+                let attributes = self.import_attributes()?;
+                span.end_with_auto_semi(self, Newline::Required, |semi| match ns {
+                    Some(ns) => Export::AllAs(None, ns, source, attributes, semi),
+                    None => Export::All(None, source, attributes, semi)
+                })
+            }
+            TokenData::LBrace => {
+                let specs = self.export_specifiers()?;
+                if self.peek()?.value == TokenData::Identifier(Name::Atom(Atom::From)) {
+                    let source = self.from_clause()?;
+                    let attributes = self.import_attributes()?;
+                    let specs = specs.into_iter().map(|(_, spec)| spec).collect();
+                    return span.end_with_auto_semi(self, Newline::Required, |semi| Export::From(None, specs, source, attributes, semi));
+                }
+                // Without a `from` clause, the exported bindings are
+                // references to local declarations.
+                for &(ref token, _) in &specs {
+                    match token.value {
+                        TokenData::Identifier(ref name) => { self.new_id(false, name.clone(), token.location)?; }
+                        _ => { return Err(Error::UnexpectedToken(token.clone().into_owned())); }
+                    }
+                }
+                let specs = specs.into_iter().map(|(_, spec)| spec).collect();
+                span.end_with_auto_semi(self, Newline::Required, |semi| Export::Local(None, specs, semi))
+            }
+            TokenData::Reserved(Reserved::Var) => {
+                match self.var_statement()? {
+                    Stmt::Var(location, dtors, semi) => Ok(Export::Var(location, dtors, semi)),
+                    _ => unreachable!()
+                }
+            }
+            TokenData::Reserved(Reserved::Function)
+          | TokenData::Reserved(Reserved::Class)
+          | TokenData::Reserved(Reserved::Const)
+          | TokenData::Identifier(_) => {
+                let token = self.peek()?.clone();
+                match self.stmt_list_item(true)? {
+                    StmtListItem::Decl(decl) => Ok(Export::Decl(decl)),
+                    StmtListItem::Stmt(_) => Err(Error::UnexpectedToken(token.into_owned()))
+                }
+            }
+            _ => self.unexpected()
+        }
+    }
+
+    fn export_default(&mut self, span: SpanTracker) -> Result<Export> {
+        match self.peek()?.value {
+            TokenData::Reserved(Reserved::Function) => {
+                return self.function(|this, _, _| this.id_opt(true)).map(Export::DefaultFun);
+            }
+            TokenData::Reserved(Reserved::Class) => {
+                return self.class(|this| this.id_opt(true)).map(Export::DefaultClass);
+            }
+            TokenData::Identifier(Name::Atom(Atom::Async)) => {
+                let token = self.lexer.reread_token();
+                let function = self.async_function_follows()?;
+                self.lexer.unread_token(token);
+                if function {
+                    return self.function(|this, _, _| this.id_opt(true)).map(Export::DefaultFun);
+                }
+            }
+            _ => { }
+        }
+        let expr = self.allow_in(true, |this| this.assignment_expression())?;
+        span.end_with_auto_semi(self, Newline::Required, |semi| Export::DefaultExpr(None, expr, semi))
+    }
+
+    fn export_specifiers(&mut self) -> Result<Vec<(Token<'a>, ExportSpec)>> {
+        self.reread(TokenData::LBrace);
+        let mut specs = Vec::new();
+        while !self.matches(TokenData::RBrace)? {
+            let token = self.peek()?.clone();
+            specs.push((token, self.export_specifier()?));
+            if !self.matches(TokenData::Comma)? {
+                self.expect(TokenData::RBrace)?;
+                break;
+            }
+        }
+        Ok(specs)
+    }
+
+    fn export_specifier(&mut self) -> Result<ExportSpec> {
+        self.span(&mut |this| {
+            let local = this.id_name()?;
+            let exported = if this.matches(TokenData::Identifier(Name::Atom(Atom::As)))? {
+                this.id_name()?
+            } else {
+                local.clone()
+            };
+            Ok(ExportSpec {
+                location: None,
+                local: local,
+                exported: exported
+            })
+        })
+    }
+
+    fn from_clause(&mut self) -> Result<StringLiteral<'static>> {
+        self.expect(TokenData::Identifier(Name::Atom(Atom::From)))?;
+        self.module_specifier()
+    }
+
+    // WithClause ::= "with" "{" (AttributeKey ":" StringLiteral)*[","] "}"
+    fn import_attributes(&mut self) -> Result<Vec<ImportAttribute>> {
+        let mut attributes = Vec::new();
+        if !self.matches(TokenData::Reserved(Reserved::With))? {
+            return Ok(attributes);
+        }
+        self.expect(TokenData::LBrace)?;
+        let mut keys = HashSet::new();
+        while self.peek()?.value != TokenData::RBrace {
+            let attribute = self.span(&mut |this| {
+                let token = this.read()?;
+                let location = Some(token.location);
+                let key = match token.value {
+                    TokenData::Identifier(name) => PropKey::Id(location, name.into_string()),
+                    TokenData::Reserved(word) => PropKey::Id(location, word.into_string()),
+                    TokenData::String(literal) => PropKey::String(location, literal.into_owned()),
+                    _ => { return Err(Error::UnexpectedToken(token.into_owned())); }
+                };
+                this.expect(TokenData::Colon)?;
+                let value = this.module_specifier()?;
+                Ok(ImportAttribute { location: None, key: key, value: value })
+            })?;
+            let name = match attribute.key {
+                PropKey::Id(_, ref name) => name.clone(),
+                PropKey::String(_, ref literal) => literal.value.to_string(),
+                _ => unreachable!()
+            };
+            if !keys.insert(name.clone()) {
+                return Err(Error::DuplicateImportAttribute(attribute.location, name));
+            }
+            attributes.push(attribute);
+            if !self.matches(TokenData::Comma)? {
+                break;
+            }
+        }
+        self.expect(TokenData::RBrace)?;
+        Ok(attributes)
+    }
+
+    fn module_specifier(&mut self) -> Result<StringLiteral<'static>> {
+        let token = self.read()?;
+        match token.value {
+            TokenData::String(literal) => {
+                self.check_legacy_escape(Some(token.location), &literal)?;
+                Ok(literal.into_owned())
+            }
+            _ => Err(Error::UnexpectedToken(token.into_owned()))
+        }
+    }
+
     fn statement_list(&mut self) -> Result<Vec<StmtListItem>> {
         let mut items = Vec::new();
         while !self.peek()?.follow_statement_list() {
@@ -324,12 +912,21 @@ impl<I: Iterator<Item=char>> Parser<I> {
 
     fn function_declaration(&mut self) -> Result<Decl> {
         self.span(&mut |this| {
-            Ok(Decl::Fun(this.function(|this| this.id(true))?))
+            Ok(Decl::Fun(this.function(|this, _, _| this.id(true))?))
         })
     }
 
-    fn formal_parameters(&mut self) -> Result<Params> {
-        self.span(&mut |this| {
+    fn in_parameters<T, F>(&mut self, generator: bool, asynchronous: bool, parse: F) -> Result<T>
+        where F: FnOnce(&mut Self) -> Result<T>
+    {
+        let parameters = replace(&mut self.context.parameters, true);
+        let result = self.in_generator(generator, |this| this.in_async(asynchronous, parse));
+        self.context.parameters = parameters;
+        result
+    }
+
+    fn formal_parameters(&mut self, generator: bool, asynchronous: bool) -> Result<Params> {
+        self.in_parameters(generator, asynchronous, |this| this.span(&mut |this| {
             this.expect(TokenData::LParen)?;
             let mut list = Vec::new();
             let mut rest = None;
@@ -339,17 +936,11 @@ impl<I: Iterator<Item=char>> Parser<I> {
                         break;
                     }
                     TokenData::Ellipsis => {
-                        rest = Some(this.span(&mut |this| {
-                            this.reread(TokenData::Ellipsis);
-                            Ok(RestPatt {
-                                location: None,
-                                patt: this.pattern()?
-                            })
-                        })?);
+                        rest = Some(this.rest_pattern()?);
                         break;
                     }
                     _ => {
-                        list.push(this.pattern()?);
+                        list.push(this.binding_element()?);
                         if !this.matches(TokenData::Comma)? {
                             break;
                         }
@@ -362,7 +953,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
                 list: list,
                 rest: rest
             })
-        })
+        }))
     }
 
     fn pattern(&mut self) -> Result<Patt<Id>> {
@@ -378,11 +969,377 @@ impl<I: Iterator<Item=char>> Parser<I> {
         }
     }
 
+    // BindingElement ::=
+    //   BindingPattern ("=" AssignmentExpression)?
+    fn binding_element(&mut self) -> Result<Patt<Id>> {
+        let patt = self.pattern()?;
+        if !self.matches(TokenData::Assign)? {
+            return Ok(patt);
+        }
+        let default = self.allow_in(true, |this| this.assignment_expression())?;
+        Ok(Patt::Default(span(&patt, &default), Box::new(patt), Box::new(default)))
+    }
+
+    fn rest_pattern(&mut self) -> Result<RestPatt<Id>> {
+        self.span(&mut |this| {
+            this.reread(TokenData::Ellipsis);
+            Ok(RestPatt {
+                location: None,
+                patt: this.pattern()?
+            })
+        })
+    }
+
     fn binding_pattern(&mut self) -> Result<CompoundPatt<Id>> {
-        if !self.peek()?.first_binding() {
-            return self.unexpected();
+        match self.peek()?.value {
+            TokenData::LBrace => self.object_binding_pattern(),
+            TokenData::LBrack => self.array_binding_pattern(),
+            _ => self.unexpected()
+        }
+    }
+
+    fn object_binding_pattern(&mut self) -> Result<CompoundPatt<Id>> {
+        self.span(&mut |this| {
+            this.reread(TokenData::LBrace);
+            let mut props = Vec::new();
+            let mut rest = None;
+            loop {
+                match this.peek()?.value {
+                    TokenData::RBrace => {
+                        break;
+                    }
+                    TokenData::Ellipsis => {
+                        rest = Some(Box::new(this.object_rest_pattern()?));
+                        break;
+                    }
+                    _ => {
+                        props.push(this.binding_property()?);
+                        if !this.matches(TokenData::Comma)? {
+                            break;
+                        }
+                    }
+                }
+            }
+            this.expect(TokenData::RBrace)?;
+            Ok(CompoundPatt::Obj(None, props, rest))
+        })
+    }
+
+    // The rest of an object pattern can't be destructured further.
+    fn object_rest_pattern(&mut self) -> Result<RestPatt<Id>> {
+        self.span(&mut |this| {
+            this.reread(TokenData::Ellipsis);
+            Ok(RestPatt {
+                location: None,
+                patt: Patt::Simple(this.id(true)?)
+            })
+        })
+    }
+
+    fn binding_property(&mut self) -> Result<PropPatt<Id>> {
+        let first = self.read()?;
+        if let TokenData::Identifier(_) = first.value {
+            match self.peek()?.value {
+                TokenData::Colon => { }
+                TokenData::Assign => {
+                    let id = self.new_id_from_token(true, first)?;
+                    self.reread(TokenData::Assign);
+                    let default = self.allow_in(true, |this| this.assignment_expression())?;
+                    return Ok(PropPatt::ShorthandDefault(span(&id, &default), id, default));
+                }
+                _ => { return self.new_id_from_token(true, first).map(PropPatt::Shorthand); }
+            }
+        }
+        self.lexer.unread_token(first);
+        let key = self.property_key()?;
+        self.expect(TokenData::Colon)?;
+        let patt = self.binding_element()?;
+        Ok(PropPatt::Regular(span(&key, &patt), key, patt))
+    }
+
+    fn array_binding_pattern(&mut self) -> Result<CompoundPatt<Id>> {
+        self.span(&mut |this| {
+            this.reread(TokenData::LBrack);
+            let mut elts = Vec::new();
+            let mut rest = None;
+            loop {
+                match this.peek()?.value {
+                    TokenData::RBrack => {
+                        break;
+                    }
+                    // Elision.
+                    TokenData::Comma => {
+                        this.reread(TokenData::Comma);
+                        elts.push(None);
+                    }
+                    TokenData::Ellipsis => {
+                        rest = Some(Box::new(this.rest_pattern()?));
+                        break;
+                    }
+                    _ => {
+                        elts.push(Some(this.binding_element()?));
+                        if !this.matches(TokenData::Comma)? {
+                            break;
+                        }
+                    }
+                }
+            }
+            this.expect(TokenData::RBrack)?;
+            Ok(CompoundPatt::Arr(None, elts, rest))
+        })
+    }
+
+    fn class_declaration(&mut self) -> Result<Decl> {
+        self.class(|this| this.id(true)).map(Decl::Class)
+    }
+
+    // Class ::= "class" BindingIdentifier? ("extends" LHSExpression)? "{" ClassElement* "}"
+    fn class<Id, F>(&mut self, get_id: F) -> Result<Class<Id>>
+        where F: Fn(&mut Self) -> Result<Id>
+    {
+        // All parts of a class are strict mode code.
+        let strict = replace(&mut self.context.strict, Strict::Yes);
+        let result = self.span(&mut |this| {
+            this.reread(TokenData::Reserved(Reserved::Class));
+            let id = get_id(this)?;
+            let extends = if this.matches(TokenData::Reserved(Reserved::Extends))? {
+                Some(Box::new(this.lhs_expression()?))
+            } else {
+                None
+            };
+            let body = this.class_body(extends.is_some())?;
+            Ok(Class { location: None, id: id, extends: extends, body: body })
+        });
+        self.context.strict = strict;
+        result
+    }
+
+    fn class_body(&mut self, derived: bool) -> Result<Vec<ClassElement>> {
+        self.classes.push(ClassScope::new());
+        let result = self.class_elements(derived);
+        let scope = self.classes.pop().unwrap();
+        let elements = result?;
+
+        // Names that aren't declared here may belong to an enclosing class.
+        for key in scope.unresolved {
+            if scope.declared.contains_key(&key.value) {
+                continue;
+            }
+            match self.classes.last_mut() {
+                Some(outer) => { outer.unresolved.push(key); }
+                None => { return Err(Error::UndeclaredPrivateName(key)); }
+            }
+        }
+        Ok(elements)
+    }
+
+    fn class_elements(&mut self, derived: bool) -> Result<Vec<ClassElement>> {
+        self.expect(TokenData::LBrace)?;
+        let mut elements = Vec::new();
+        let mut has_constructor = false;
+        loop {
+            match self.peek()?.value {
+                TokenData::RBrace => {
+                    break;
+                }
+                TokenData::Semi => {
+                    self.reread(TokenData::Semi);
+                }
+                _ => {
+                    let element = self.class_element(derived)?;
+                    let location = *element.tracking_ref();
+                    let is_name = |name| element.key().map_or(false, |key| key.is_name(name));
+                    if element.is_static() {
+                        if is_name("prototype") {
+                            return Err(Error::StaticPrototype(location));
+                        }
+                        if let ClassElement::Field(..) = element {
+                            if is_name("constructor") {
+                                return Err(Error::SpecialConstructor(location));
+                            }
+                        }
+                    } else if is_name("constructor") {
+                        match element {
+                            ClassElement::Method(_, _, ref fun) if !fun.generator && !fun.asynchronous => {
+                                if has_constructor {
+                                    return Err(Error::DuplicateConstructor(location));
+                                }
+                                has_constructor = true;
+                            }
+                            _ => { return Err(Error::SpecialConstructor(location)); }
+                        }
+                    }
+                    self.classes.last_mut().unwrap().declare(&element)?;
+                    elements.push(element);
+                }
+            }
         }
-        Err(Error::UnsupportedFeature("destructuring"))
+        self.expect(TokenData::RBrace)?;
+        Ok(elements)
+    }
+
+    // ClassElement ::=
+    //   "static"? MethodDefinition
+    //   "static"? FieldDefinition ";"
+    //   "static" "{" StatementList "}"
+    fn class_element(&mut self, derived: bool) -> Result<ClassElement> {
+        let mut is_static = false;
+        let first = self.read()?;
+        let start = Some(first.location);
+        let tracker = SpanTracker::new(first.location.start);
+        if first.value == TokenData::Identifier(Name::Atom(Atom::Static)) && self.static_modifier_follows()? {
+            if self.peek()?.value == TokenData::LBrace {
+                return self.static_block(start);
+            }
+            is_static = true;
+        } else {
+            self.lexer.unread_token(first);
+        }
+        let first = self.read()?;
+        match first.value {
+            TokenData::Identifier(Name::Atom(Atom::Get)) => {
+                if let Some(key) = self.class_element_name_opt()? {
+                    self.expect(TokenData::LParen)?;
+                    self.expect(TokenData::RParen)?;
+                    let body = self.method_body(false, false, &[], false)?;
+                    return Ok(ClassElement::Getter(span(&start, &body), is_static, key, body));
+                }
+                let key = PropKey::Id(Some(first.location), "get".to_string());
+                self.more_class_member(tracker, start, is_static, key, derived)
+            }
+            TokenData::Identifier(Name::Atom(Atom::Set)) => {
+                if let Some(key) = self.class_element_name_opt()? {
+                    self.expect(TokenData::LParen)?;
+                    let param = self.in_parameters(false, false, |this| this.binding_element())?;
+                    self.expect(TokenData::RParen)?;
+                    let body = self.method_body(false, false, &[param.clone()], false)?;
+                    return Ok(ClassElement::Setter(span(&start, &body), is_static, key, param, body));
+                }
+                let key = PropKey::Id(Some(first.location), "set".to_string());
+                self.more_class_member(tracker, start, is_static, key, derived)
+            }
+            TokenData::Identifier(Name::Atom(Atom::Async)) if self.async_method_follows()? => {
+                let generator = self.matches(TokenData::Star)?;
+                let key = self.class_element_name()?;
+                self.more_class_method(start, is_static, true, generator, key, derived)
+            }
+            TokenData::Star => {
+                let key = self.class_element_name()?;
+                self.more_class_method(start, is_static, false, true, key, derived)
+            }
+            _ => {
+                self.lexer.unread_token(first);
+                let key = self.class_element_name()?;
+                self.more_class_member(tracker, start, is_static, key, derived)
+            }
+        }
+    }
+
+    // A `static` prefix is a modifier unless it's the element's own name.
+    fn static_modifier_follows(&mut self) -> Result<bool> {
+        Ok(match self.peek()?.value {
+            TokenData::LParen
+          | TokenData::Assign
+          | TokenData::Semi
+          | TokenData::RBrace => false,
+            _ => true
+        })
+    }
+
+    // "static" . "{" StatementList "}"
+    fn static_block(&mut self, start: Option<Span>) -> Result<ClassElement> {
+        self.expect(TokenData::LBrace)?;
+        let inner = Context {
+            function: false,
+            initializer: true,
+            static_block: true,
+            .. self.context.new_method(false)
+        };
+        let outer = replace(&mut self.context, inner);
+        let items = self.statement_list();
+        self.context = outer;
+        let items = items?;
+        let end = self.expect(TokenData::RBrace)?;
+        Ok(ClassElement::StaticBlock(span(&start, &Some(end.location)), items))
+    }
+
+    // ClassElementName ::=
+    //   PropertyName
+    //   PrivateIdentifier
+    fn class_element_name_opt(&mut self) -> Result<Option<PropKey>> {
+        let token = self.read()?;
+        match token.value {
+            TokenData::PrivateName(name) => Ok(Some(PropKey::Private(Some(token.location), name.into_owned()))),
+            _ => {
+                self.lexer.unread_token(token);
+                self.property_key_opt()
+            }
+        }
+    }
+
+    fn class_element_name(&mut self) -> Result<PropKey> {
+        match self.class_element_name_opt()? {
+            Some(key) => Ok(key),
+            None => self.unexpected()
+        }
+    }
+
+    // ClassElementName . ("(" FormalParameters ")" "{" FunctionBody "}" | Initializer? ";")
+    fn more_class_member(&mut self, tracker: SpanTracker, start: Option<Span>, is_static: bool, key: PropKey, derived: bool) -> Result<ClassElement> {
+        if self.peek()?.value == TokenData::LParen {
+            return self.more_class_method(start, is_static, false, false, key, derived);
+        }
+        let value = if self.matches(TokenData::Assign)? {
+            // Initializers are evaluated like the bodies of methods.
+            let inner = Context { initializer: true, .. self.context.new_method(false) };
+            let outer = replace(&mut self.context, inner);
+            let value = self.assignment_expression();
+            self.context = outer;
+            Some(value?)
+        } else {
+            None
+        };
+        tracker.end_with_auto_semi(self, Newline::Required, |_| ClassElement::Field(None, is_static, key, value))
+    }
+
+    fn more_class_method(&mut self, start: Option<Span>, is_static: bool, asynchronous: bool, generator: bool, key: PropKey, derived: bool) -> Result<ClassElement> {
+        let super_call = derived && !is_static && !generator && !asynchronous && key.is_name("constructor");
+        let fun = self.method(asynchronous, generator, key, super_call)?;
+        Ok(ClassElement::Method(span(&start, &fun), is_static, fun))
+    }
+
+    // MethodDefinition ::= PropertyName "(" FormalParameters ")" "{" FunctionBody "}"
+    fn method(&mut self, asynchronous: bool, generator: bool, key: PropKey, super_call: bool) -> Result<Fun<PropKey>> {
+        let params = self.formal_parameters(generator, asynchronous)?;
+        let body = self.method_body(generator, asynchronous, &params.list, super_call)?;
+        Ok(Fun {
+            location: span(&key, &body),
+            id: key,
+            generator: generator,
+            asynchronous: asynchronous,
+            params: params,
+            body: body
+        })
+    }
+
+    // An `async` prefix on the same line turns a method name into a
+    // modifier, unless the name itself is what follows.
+    fn async_method_follows(&mut self) -> Result<bool> {
+        let next = self.peek()?;
+        Ok(!next.newline && match next.value {
+            TokenData::LParen
+          | TokenData::Assign
+          | TokenData::Semi
+          | TokenData::Comma
+          | TokenData::Colon
+          | TokenData::RBrace => false,
+            _ => true
+        })
+    }
+
+    fn async_function_follows(&mut self) -> Result<bool> {
+        let next = self.peek()?;
+        Ok(!next.newline && next.value == TokenData::Reserved(Reserved::Function))
     }
 
     fn strict_check<F>(&mut self, f: F) -> Result<()>
@@ -412,46 +1369,92 @@ impl<I: Iterator<Item=char>> Parser<I> {
         Ok(())
     }
 
+    fn annex_b(&self) -> bool {
+        self.web_compat && self.goal == Goal::Script
+    }
+
+    fn check_legacy_escape(&mut self, location: Option<Span>, literal: &StringLiteral) -> Result<()> {
+        if !literal.has_legacy_escape() {
+            return Ok(());
+        }
+        self.strict_check(|_| Some(Check::Strict(Error::LegacyEscape(location))))
+    }
+
+    // The identifier parser is passed the generator and async flags,
+    // since the name of a function expression is bound inside it.
     fn function<Id, F>(&mut self, get_id: F) -> Result<Fun<Id>>
-        where F: Fn(&mut Self) -> Result<Id>
+        where F: Fn(&mut Self, bool, bool) -> Result<Id>
     {
         self.span(&mut |this| {
-            this.reread(TokenData::Reserved(Reserved::Function));
-            let id = get_id(this)?;
-            let params = this.formal_parameters()?;
-            let body = this.function_body(&params.list)?;
-            Ok(Fun { location: None, id: id, params: params, body: body })
+            let asynchronous = this.matches(TokenData::Identifier(Name::Atom(Atom::Async)))?;
+            this.expect(TokenData::Reserved(Reserved::Function))?;
+            let generator = this.matches(TokenData::Star)?;
+            let id = get_id(this, generator, asynchronous)?;
+            let params = this.formal_parameters(generator, asynchronous)?;
+            let body = this.function_body(generator, asynchronous, &params.list)?;
+            Ok(Fun {
+                location: None,
+                id: id,
+                generator: generator,
+                asynchronous: asynchronous,
+                params: params,
+                body: body
+            })
         })
     }
 
-    fn function_body(&mut self, params: &[Patt<Id>]) -> Result<Script> {
-        let inner = self.context.new_function();
+    fn function_body(&mut self, generator: bool, asynchronous: bool, params: &[Patt<Id>]) -> Result<Script> {
+        let mut inner = self.context.new_function();
+        inner.generator = generator;
+        inner.asynchronous = asynchronous;
+        self.body_in_context(inner, params)
+    }
+
+    fn method_body(&mut self, generator: bool, asynchronous: bool, params: &[Patt<Id>], super_call: bool) -> Result<Script> {
+        let mut inner = self.context.new_method(super_call);
+        inner.generator = generator;
+        inner.asynchronous = asynchronous;
+        self.body_in_context(inner, params)
+    }
+
+    fn body_in_context(&mut self, inner: Context, params: &[Patt<Id>]) -> Result<Script> {
         let outer = replace(&mut self.context, inner);
+        // Nothing in a function body is part of enclosing arrow parameters.
+        let outer_cover = replace(&mut self.cover, Cover::new());
         self.expect(TokenData::LBrace)?;
         // ES6: if the body has "use strict" check for simple parameters
         let body = self.script_body()?;
         self.strict_check(|_| {
             if body.dirs.iter().any(|dir| dir.pragma() == "use strict") {
                 for param in params {
-                    if let Patt::Compound(ref compound) = *param {
-                        return Some(Check::Strict(Error::CompoundParamWithUseStrict(compound.clone())));
+                    if !param.is_simple() {
+                        return Some(Check::Strict(Error::NonSimpleParamWithUseStrict(param.clone())));
                     }
                 }
             }
             None
         })?;
         self.expect(TokenData::RBrace)?;
+        self.cover = outer_cover;
         self.context = outer;
         Ok(body)
     }
 
     fn stmt_list_item(&mut self, allow_decl: bool) -> Result<StmtListItem> {
+        let generator = self.context.generator;
+        let asynchronous = self.context.asynchronous;
         (match self.peek()?.value {
             TokenData::Reserved(Reserved::Function) => {
                 if !allow_decl {
                     return self.unexpected();
                 }
-                return self.function_declaration().map(StmtListItem::Decl);
+                return self.function_declaration().map(StmtListItem::Decl);
+            }
+            TokenData::Reserved(Reserved::Class) => {
+                if !allow_decl {
+                    return self.unexpected();
+                }
+                return self.class_declaration().map(StmtListItem::Decl);
             }
             TokenData::LBrace                       => self.block().map(Stmt::Block),
             TokenData::Reserved(Reserved::Var)      => self.var_statement(),
@@ -474,8 +1477,18 @@ impl<I: Iterator<Item=char>> Parser<I> {
             TokenData::Reserved(Reserved::Do)       => self.do_statement(),
             TokenData::Reserved(Reserved::For)      => self.for_statement(),
             TokenData::Reserved(Reserved::Debugger) => self.debugger_statement(),
+            // An operator can't be a label, and what follows it may be a regexp.
+            TokenData::Identifier(Name::Atom(Atom::Yield)) if generator => self.expression_statement(),
+            TokenData::Identifier(Name::Atom(Atom::Await)) if asynchronous => self.expression_statement(),
             TokenData::Identifier(_)                => {
                 let token = self.lexer.reread_token();
+                if token.value == TokenData::Identifier(Name::Atom(Atom::Async)) && self.async_function_follows()? {
+                    self.lexer.unread_token(token);
+                    if !allow_decl {
+                        return self.unexpected();
+                    }
+                    return self.function_declaration().map(StmtListItem::Decl);
+                }
                 match self.peek_op()?.value {
                     TokenData::Colon => {
                         let id = self.new_id_from_token(false, token)?;
@@ -519,11 +1532,29 @@ impl<I: Iterator<Item=char>> Parser<I> {
         }
 
         let label_type = self.peek()?.label_type();
-        self.with_labels(labels, label_type, |this| this.statement())
+        self.with_labels(labels, label_type, |this| this.statement_or_function())
+    }
+
+    // Sloppy web scripts allow a plain function declaration in place of
+    // the body of an `if` or labelled statement.
+    fn statement_or_function(&mut self) -> Result<Stmt> {
+        if self.peek()?.value != TokenData::Reserved(Reserved::Function) || !self.annex_b() {
+            return self.statement();
+        }
+        let token = self.peek()?.clone();
+        let fun = match self.function_declaration()? {
+            Decl::Fun(fun) => fun,
+            _ => unreachable!()
+        };
+        if fun.generator {
+            return Err(Error::UnexpectedToken(token.into_owned()));
+        }
+        self.strict_check(|_| Some(Check::Strict(Error::FunctionStatement(Some(token.location)))))?;
+        Ok(Stmt::Fun(fun))
     }
 
     fn expression_statement(&mut self) -> Result<Stmt> {
-        let span = self.start();
+        let span = self.start()?;
         let expr = self.allow_in(true, |this| this.expression())?;
         Ok(span.end_with_auto_semi(self, Newline::Required, |semi| Stmt::Expr(None, expr, semi))?)
     }
@@ -541,7 +1572,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn var_statement(&mut self) -> Result<Stmt> {
-        let span = self.start();
+        let span = self.start()?;
         self.reread(TokenData::Reserved(Reserved::Var));
         let dtors = self.comma_separated(Self::declarator)?;
         span.end_with_auto_semi(self, Newline::Required, |semi| Stmt::Var(None, dtors, semi))
@@ -554,24 +1585,25 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn const_declaration(&mut self) -> Result<Decl> {
-        let span = self.start();
+        let span = self.start()?;
         self.reread(TokenData::Reserved(Reserved::Const));
         let dtors = self.comma_separated(Self::const_declarator)?;
         span.end_with_auto_semi(self, Newline::Required, |semi| Decl::Const(None, dtors, semi))
     }
 
-    fn new_id_from_token(&mut self, binding: bool, token: Token) -> Result<Id> {
+    fn new_id_from_token(&mut self, binding: bool, token: Token<'a>) -> Result<Id> {
         match token.value {
             TokenData::Identifier(name) => self.new_id(binding, name, token.location),
             _ => unreachable!()
         }
     }
 
-    fn new_id(&mut self, binding: bool, name: Name, location: Span) -> Result<Id> {
+    fn new_id(&mut self, binding: bool, name: Name<'a>, location: Span) -> Result<Id> {
+        if binding {
+            self.binding_check(&name, location)?;
+        }
+        self.keyword_check(&name, location)?;
         self.strict_check(|_| {
-            if binding && name.is_illegal_strict_binding() {
-                return Some(Check::Strict(Error::IllegalStrictBinding(location, name.atom().unwrap())));
-            }
             let is_reserved = name.is_strict_reserved();
             if is_reserved != TriState::No {
                 let error = Error::ContextualKeyword(location, name.atom().unwrap());
@@ -585,7 +1617,93 @@ impl<I: Iterator<Item=char>> Parser<I> {
             }
             None
         })?;
-        Ok(Id::new(name, Some(location)))
+        Ok(Id::new(name.into_owned(), Some(location)))
+    }
+
+    // `yield` and `await` are keywords inside generators and async
+    // functions, respectively, and `await` is reserved in static blocks.
+    fn keyword_check(&mut self, name: &Name, location: Span) -> Result<()> {
+        match *name {
+            Name::Atom(Atom::Yield) if self.context.generator => {
+                Err(Error::ContextualKeyword(location, Atom::Yield))
+            }
+            Name::Atom(Atom::Await) if self.context.asynchronous || self.context.static_block => {
+                Err(Error::ContextualKeyword(location, Atom::Await))
+            }
+            _ => Ok(())
+        }
+    }
+
+    // Class field initializers and static blocks have no `arguments`.
+    fn reference_check(&mut self, name: &Name, location: Span) -> Result<()> {
+        match *name {
+            Name::Atom(Atom::Arguments) if self.context.initializer => {
+                Err(Error::IllegalArguments(location))
+            }
+            _ => Ok(())
+        }
+    }
+
+    fn binding_check(&mut self, name: &Name, location: Span) -> Result<()> {
+        self.strict_check(|_| {
+            if name.is_illegal_strict_binding() {
+                return Some(Check::Strict(Error::IllegalStrictBinding(location, name.atom().unwrap())));
+            }
+            None
+        })
+    }
+
+    // Identifiers in a cover grammar were parsed as references, so
+    // once the cover is reinterpreted as a binding they must be
+    // checked again.
+    fn cover_binding_check(&mut self, patt: &Patt<Id>) -> Result<()> {
+        match *patt {
+            Patt::Simple(ref id) => {
+                self.keyword_check(&id.name, id.location.unwrap())?;
+                self.binding_check(&id.name, id.location.unwrap())?;
+            }
+            Patt::Compound(CompoundPatt::Arr(_, ref elts, ref rest)) => {
+                for elt in elts {
+                    if let Some(ref elt) = *elt {
+                        self.cover_binding_check(elt)?;
+                    }
+                }
+                if let Some(ref rest) = *rest {
+                    self.cover_binding_check(&rest.patt)?;
+                }
+            }
+            Patt::Compound(CompoundPatt::Obj(_, ref props, ref rest)) => {
+                for prop in props {
+                    match *prop {
+                        PropPatt::Regular(_, _, ref patt) => { self.cover_binding_check(patt)?; }
+                        PropPatt::Shorthand(ref id)
+                      | PropPatt::ShorthandDefault(_, ref id, _) => {
+                            self.keyword_check(&id.name, id.location.unwrap())?;
+                            self.binding_check(&id.name, id.location.unwrap())?;
+                        }
+                    }
+                }
+                if let Some(ref rest) = *rest {
+                    self.cover_binding_check(&rest.patt)?;
+                }
+            }
+            Patt::Default(_, ref patt, _) => { self.cover_binding_check(patt)?; }
+        }
+        Ok(())
+    }
+
+    // Arrow parameters can't repeat a name, even in sloppy code.
+    fn cover_params_check(&mut self, params: &Params) -> Result<()> {
+        let mut names = HashSet::new();
+        for patt in params.list.iter().chain(params.rest.iter().map(|rest| &rest.patt)) {
+            self.cover_binding_check(patt)?;
+            for id in patt.bound_names() {
+                if !names.insert(&id.name) {
+                    return Err(Error::DuplicateParam(id.clone()));
+                }
+            }
+        }
+        Ok(())
     }
 
     fn id(&mut self, binding: bool) -> Result<Id> {
@@ -595,7 +1713,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
             _ => Err(Error::UnexpectedToken(Token {
                 location: location,
                 newline: newline,
-                value: data
+                value: data.into_owned()
             }))
         }
     }
@@ -651,10 +1769,10 @@ impl<I: Iterator<Item=char>> Parser<I> {
         self.span(&mut |this| {
             this.expect(TokenData::Reserved(Reserved::If))?;
             let test = this.paren_expression()?;
-            let cons = Box::new(this.statement()?);
+            let cons = Box::new(this.if_clause()?);
             let alt = if this.peek()?.value == TokenData::Reserved(Reserved::Else) {
                 this.reread(TokenData::Reserved(Reserved::Else));
-                Some(Box::new(this.statement()?))
+                Some(Box::new(this.if_clause()?))
             } else {
                 None
             };
@@ -662,15 +1780,30 @@ impl<I: Iterator<Item=char>> Parser<I> {
         })
     }
 
+    // A clause may be a function in web scripts, but not a labelled one.
+    fn if_clause(&mut self) -> Result<Stmt> {
+        let clause = self.statement_or_function()?;
+        if let Stmt::Label(..) = clause {
+            if let Some(location) = labelled_function(&clause) {
+                return Err(Error::LabelledFunction(location));
+            }
+        }
+        Ok(clause)
+    }
+
     fn iteration_body(&mut self) -> Result<Stmt> {
         let iteration = replace(&mut self.context.iteration, true);
         let result = self.statement();
         replace(&mut self.context.iteration, iteration);
-        result
+        let body = result?;
+        if let Some(location) = labelled_function(&body) {
+            return Err(Error::LabelledFunction(location));
+        }
+        Ok(body)
     }
 
     fn do_statement(&mut self) -> Result<Stmt> {
-        let span = self.start();
+        let span = self.start()?;
         self.reread(TokenData::Reserved(Reserved::Do));
         let body = Box::new(self.iteration_body()?);
         self.expect(TokenData::Reserved(Reserved::While))?;
@@ -692,8 +1825,18 @@ impl<I: Iterator<Item=char>> Parser<I> {
     fn for_statement(&mut self) -> Result<Stmt> {
         self.span(&mut |this| {
             this.reread(TokenData::Reserved(Reserved::For));
+            let await_location = if this.context.asynchronous {
+                match this.peek()?.value {
+                    TokenData::Identifier(Name::Atom(Atom::Await)) => {
+                        Some(this.reread(TokenData::Identifier(Name::Atom(Atom::Await))).location)
+                    }
+                    _ => None
+                }
+            } else {
+                None
+            };
             this.expect(TokenData::LParen)?;
-            match this.peek()?.value {
+            let stmt = match this.peek()?.value {
                 TokenData::Reserved(Reserved::Var)           => this.for_var(),
                 TokenData::Identifier(Name::Atom(Atom::Let)) => this.for_let(),
                 TokenData::Reserved(Reserved::Const)         => this.for_const(),
@@ -702,6 +1845,14 @@ impl<I: Iterator<Item=char>> Parser<I> {
                     this.more_for(None)
                 }
                 _                                            => this.for_expr()
+            }?;
+            // Only `for`-`of` loops can await their iteration.
+            match (await_location, stmt) {
+                (None, stmt) => Ok(stmt),
+                (Some(_), Stmt::ForOf(location, head, iter, body)) => {
+                    Ok(Stmt::ForAwait(location, head, iter, body))
+                }
+                (Some(location), _) => Err(Error::ForAwaitWithoutOf(location))
             }
         })
     }
@@ -740,6 +1891,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
                         let rhs = self.allow_in(false, |this| this.assignment_expression())?;
                         self.more_for_head(&var_location, Dtor::from_compound_init(patt, rhs), ForHead::Var)
                     }
+                    Patt::Default(..) => unreachable!("default values only occur within patterns")
                 }
             }
             TokenData::Comma
@@ -860,15 +2012,20 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn for_expr(&mut self) -> Result<Stmt> {
-        let lhs = self.allow_in(false, |this| this.expression())?;
+        let (lhs, cover) = self.cover(|this| this.allow_in(false, |this| {
+            let first = this.assignment()?;
+            this.more_expressions(first)
+        }))?;
         match self.peek()?.value {
             TokenData::Semi => {
+                cover.into_expr(&mut self.cover)?;
                 let semi_location = Some(self.reread(TokenData::Semi).location);
                 let head = Some(Box::new(ForHead::Expr(span(&lhs, &semi_location), lhs)));
                 self.more_for(head)
             }
             TokenData::Reserved(Reserved::In) => {
                 self.reread(TokenData::Reserved(Reserved::In));
+                cover.into_patt(&mut self.cover)?;
                 let lhs_location = *lhs.tracking_ref();
                 let lhs = match lhs.into_assign_patt() {
                     Ok(lhs) => lhs,
@@ -879,6 +2036,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
             }
             TokenData::Identifier(Name::Atom(Atom::Of)) => {
                 self.reread(TokenData::Identifier(Name::Atom(Atom::Of)));
+                cover.into_patt(&mut self.cover)?;
                 let lhs_location = *lhs.tracking_ref();
                 let lhs = match lhs.into_assign_patt() {
                     Ok(lhs) => lhs,
@@ -981,7 +2139,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
                 TokenData::Reserved(Reserved::Default) => {
                     if found_default {
                         let token = self.reread(TokenData::Reserved(Reserved::Default));
-                        return Err(Error::DuplicateDefault(token));
+                        return Err(Error::DuplicateDefault(token.into_owned()));
                     }
                     found_default = true;
                     cases.push(self.default()?);
@@ -1013,7 +2171,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn break_statement(&mut self) -> Result<Stmt> {
-        let span = self.start();
+        let span = self.start()?;
         let break_token = self.reread(TokenData::Reserved(Reserved::Break));
         let arg = if self.has_arg_same_line()? {
             let id = self.id(false)?;
@@ -1023,7 +2181,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
             Some(id)
         } else {
             if !self.context.iteration && !self.context.switch {
-                return Err(Error::IllegalBreak(break_token));
+                return Err(Error::IllegalBreak(break_token.into_owned()));
             }
             None
         };
@@ -1033,7 +2191,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn continue_statement(&mut self) -> Result<Stmt> {
-        let span = self.start();
+        let span = self.start()?;
         let continue_token = self.reread(TokenData::Reserved(Reserved::Continue));
         let arg = if self.has_arg_same_line()? {
             let id = self.id(false)?;
@@ -1045,7 +2203,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
             Some(id)
         } else {
             if !self.context.iteration {
-                return Err(Error::IllegalContinue(continue_token));
+                return Err(Error::IllegalContinue(continue_token.into_owned()));
             }
             None
         };
@@ -1055,7 +2213,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn return_statement(&mut self) -> Result<Stmt> {
-        let span = self.start();
+        let span = self.start()?;
         self.reread(TokenData::Reserved(Reserved::Return));
         let arg = if self.has_arg_same_line()? {
             Some(self.allow_in(true, |this| this.expression())?)
@@ -1076,7 +2234,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
         self.span(&mut |this| {
             let token = this.reread(TokenData::Reserved(Reserved::With));
             this.strict_check(|_| {
-                Some(Check::Strict(Error::StrictWith(token)))
+                Some(Check::Strict(Error::StrictWith(token.into_owned())))
             })?;
             let obj = this.paren_expression()?;
             let body = Box::new(this.statement()?);
@@ -1085,10 +2243,10 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn throw_statement(&mut self) -> Result<Stmt> {
-        let span = self.start();
+        let span = self.start()?;
         let token = self.reread(TokenData::Reserved(Reserved::Throw));
         if !self.has_arg_same_line()? {
-            return Err(Error::ThrowArgument(token));
+            return Err(Error::ThrowArgument(token.into_owned()));
         }
         let arg = self.allow_in(true, |this| this.expression())?;
         span.end_with_auto_semi(self, Newline::Required, |semi| {
@@ -1104,7 +2262,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
                 TokenData::Reserved(Reserved::Catch)
               | TokenData::Reserved(Reserved::Finally) => { }
                 _ => {
-                    return Err(Error::OrphanTry(this.read()?));
+                    return Err(Error::OrphanTry(this.read()?.into_owned()));
                 }
             }
             let catch = this.catch_opt()?.map(Box::new);
@@ -1118,9 +2276,13 @@ impl<I: Iterator<Item=char>> Parser<I> {
             TokenData::Reserved(Reserved::Catch) => {
                 self.span(&mut |this| {
                     this.reread(TokenData::Reserved(Reserved::Catch));
-                    this.expect(TokenData::LParen)?;
-                    let param = this.pattern()?;
-                    this.expect(TokenData::RParen)?;
+                    let param = if this.matches(TokenData::LParen)? {
+                        let param = this.pattern()?;
+                        this.expect(TokenData::RParen)?;
+                        Some(param)
+                    } else {
+                        None
+                    };
 
                     let body = this.block()?;
                     Ok(Catch { location: None, param: param, body: body })
@@ -1141,11 +2303,17 @@ impl<I: Iterator<Item=char>> Parser<I> {
     }
 
     fn debugger_statement(&mut self) -> Result<Stmt> {
-        let span = self.start();
+        let span = self.start()?;
         self.reread(TokenData::Reserved(Reserved::Debugger));
         Ok(span.end_with_auto_semi(self, Newline::Required, |semi| Stmt::Debugger(None, semi))?)
     }
 
+    fn function_expression(&mut self) -> Result<Fun<Option<Id>>> {
+        self.function(|this, generator, asynchronous| {
+            this.in_generator(generator, |this| this.in_async(asynchronous, |this| this.id_opt(true)))
+        })
+    }
+
     fn paren_expression(&mut self) -> Result<Expr> {
         self.expect(TokenData::LParen)?;
         let result = self.allow_in(true, |this| this.expression())?;
@@ -1168,30 +2336,214 @@ impl<I: Iterator<Item=char>> Parser<I> {
         let token = self.read()?;
         let location = token.location;
         Ok(match token.value {
-            TokenData::Identifier(name)          => Expr::Id(self.new_id(false, name, location)?),
+            TokenData::Identifier(Name::Atom(Atom::Async)) if self.async_function_follows()? => {
+                self.lexer.unread_token(token);
+                return Ok(Expr::Fun(self.function_expression()?));
+            }
+            TokenData::Identifier(name)          => {
+                self.reference_check(&name, location)?;
+                Expr::Id(self.new_id(false, name, location)?)
+            }
             TokenData::Reserved(Reserved::Null)  => Expr::Null(Some(location)),
             TokenData::Reserved(Reserved::This)  => Expr::This(Some(location)),
             TokenData::Reserved(Reserved::True)  => Expr::True(Some(location)),
             TokenData::Reserved(Reserved::False) => Expr::False(Some(location)),
-            TokenData::Number(literal)           => Expr::Number(Some(location), literal),
-            TokenData::String(literal)           => Expr::String(Some(location), literal),
-            TokenData::RegExp(literal)           => Expr::RegExp(Some(location), literal),
+            TokenData::Number(literal)           => Expr::Number(Some(location), literal.into_owned()),
+            TokenData::BigInt(literal)           => Expr::BigInt(Some(location), literal.into_owned()),
+            TokenData::String(literal)           => {
+                self.check_legacy_escape(Some(location), &literal)?;
+                Expr::String(Some(location), literal.into_owned())
+            }
+            TokenData::RegExp(literal)           => {
+                // The pattern is only validated; its tree isn't kept. Annex B
+                // patterns are allowed everywhere unless a `u` or `v` flag
+                // asks for the strict grammar.
+                regexp::parse(&literal, location.start, true).map_err(Error::InvalidRegExp)?;
+                Expr::RegExp(Some(location), literal)
+            }
             TokenData::LBrack                    => { return self.array_literal(token); }
             TokenData::LBrace                    => { return self.object_literal(token); }
             TokenData::Reserved(Reserved::Function) => {
                 self.lexer.unread_token(token);
-                return Ok(Expr::Fun(self.function(|this| this.id_opt(true))?));
+                return Ok(Expr::Fun(self.function_expression()?));
+            }
+            TokenData::Reserved(Reserved::Class) => {
+                self.lexer.unread_token(token);
+                return Ok(Expr::Class(self.class(|this| this.id_opt(true))?));
             }
             TokenData::LParen => {
                 self.lexer.unread_token(token);
                 return self.paren_expression();
             }
+            TokenData::PrivateName(_) => { return self.private_in(token); }
+            TokenData::NoSubstTemplate(_)
+          | TokenData::TemplateHead(_) => {
+                self.lexer.unread_token(token);
+                return Ok(Expr::Template(self.template(false)?));
+            }
             // ES6: more cases
-            _ => { return Err(Error::UnexpectedToken(token)); }
+            _ => { return Err(Error::UnexpectedToken(token.into_owned())); }
         })
     }
 
-    fn array_literal(&mut self, start: Token) -> Result<Expr> {
+    // TemplateLiteral ::=
+    //   NoSubstitutionTemplate
+    //   TemplateHead Expression (TemplateMiddle Expression)* TemplateTail
+    fn template(&mut self, tagged: bool) -> Result<Template> {
+        let mut token = self.read_op()?;
+        let start = Some(token.location);
+        let mut quasis = Vec::new();
+        let mut exprs = Vec::new();
+        loop {
+            let location = token.location;
+            let (literal, done) = match token.value {
+                TokenData::NoSubstTemplate(literal)
+              | TokenData::TemplateTail(literal) => (literal, true),
+                TokenData::TemplateHead(literal)
+              | TokenData::TemplateMiddle(literal) => (literal, false),
+                _ => { return Err(Error::UnexpectedToken(token.into_owned())); }
+            };
+            // ES2018: invalid escapes are only allowed in tagged templates.
+            if !tagged && literal.cooked.is_none() {
+                return Err(Error::InvalidTemplateEscape(location));
+            }
+            quasis.push(literal);
+            if done {
+                return Ok(Template {
+                    location: span(&start, &Some(location)),
+                    quasis: quasis,
+                    exprs: exprs
+                });
+            }
+            exprs.push(self.allow_in(true, |this| this.expression())?);
+            if self.peek_op()?.value != TokenData::RBrace {
+                return self.unexpected();
+            }
+            token = self.lexer.read_template_continuation().map_err(Error::LexError)?;
+        }
+    }
+
+    // CoverParenthesizedExpressionAndArrowParameterList ::=
+    //   "(" Expression ")"
+    //   "(" ")"
+    //   "(" "..." BindingPattern ")"
+    //   "(" Expression "," "..." BindingPattern ")"
+    fn cover_parenthesized(&mut self) -> Result<Parenthesized> {
+        let open = self.reread(TokenData::LParen);
+        let (items, cover) = self.cover(|this| this.allow_in(true, |this| {
+            let mut items = Vec::new();
+            while this.peek()?.value != TokenData::RParen {
+                let item = this.expr_list_item()?;
+                let spread = item.is_spread();
+                items.push(item);
+                let comma = match this.matches_token(TokenData::Comma)? {
+                    Some(comma) => comma,
+                    None => { break; }
+                };
+                // Only arrow parameters may end with a comma, and not
+                // after a rest parameter.
+                if this.peek()?.value == TokenData::RParen {
+                    if spread {
+                        this.cover.defer_patt_error(Error::CommaAfterRest(comma.location));
+                    }
+                    this.cover.defer_expr_error(Error::UnexpectedToken(comma.into_owned()));
+                }
+            }
+            Ok(items)
+        }))?;
+        let close = self.expect(TokenData::RParen)?;
+        if self.arrow_follows()? {
+            let location = span(&Some(open.location), &Some(close.location));
+            let params = match items.into_params(location) {
+                Ok(params) => params,
+                Err(cover_err) => { return Err(Error::InvalidParams(location, cover_err)); }
+            };
+            cover.into_params(&params)?;
+            self.cover_params_check(&params)?;
+            return Ok(Parenthesized::Params(params));
+        }
+        // Without an arrow, this can only be a parenthesized expression.
+        cover.into_expr(&mut self.cover)?;
+        let mut exprs = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                ExprListItem::Expr(expr) => { exprs.push(expr); }
+                ExprListItem::Spread(_, _) => { return self.unexpected(); }
+            }
+        }
+        let expr = match exprs.len() {
+            0 => { return self.unexpected(); }
+            1 => exprs.pop().unwrap(),
+            _ => {
+                let location = self.vec_span(&exprs);
+                Expr::Seq(location, exprs)
+            }
+        };
+        if let Some(location) = *expr.tracking_ref() {
+            self.cover.parens.push(location);
+        }
+        Ok(Parenthesized::Expr(expr))
+    }
+
+    fn arrow_follows(&mut self) -> Result<bool> {
+        let next = self.peek_op()?;
+        Ok(next.value == TokenData::Arrow && !next.newline)
+    }
+
+    // ArrowFunction ::=
+    //   "async"? ArrowParameters [no line terminator] "=>" ConciseBody
+    fn arrow_function(&mut self, start: Option<Span>, asynchronous: bool, params: Params) -> Result<Expr> {
+        self.reread(TokenData::Arrow);
+        let body = match self.peek()?.value {
+            TokenData::LBrace => {
+                let mut inner = self.context.new_arrow();
+                inner.asynchronous = asynchronous;
+                ArrowBody::Body(self.body_in_context(inner, &params.list)?)
+            }
+            _ => {
+                let mut inner = self.context.new_arrow();
+                inner.asynchronous = asynchronous;
+                inner.allow_in = self.context.allow_in;
+                let outer = replace(&mut self.context, inner);
+                let outer_cover = replace(&mut self.cover, Cover::new());
+                let body = self.assignment_expression();
+                self.cover = outer_cover;
+                self.context = outer;
+                ArrowBody::Expr(Box::new(body?))
+            }
+        };
+        let location = span(&start, &body);
+        Ok(Expr::Arrow(Arrow {
+            location: location,
+            asynchronous: asynchronous,
+            params: params,
+            body: body
+        }))
+    }
+
+    // "async" . [no line terminator] Arguments
+    //
+    // The arguments are reinterpreted as async arrow parameters if an
+    // arrow follows; otherwise this is a call to a function named `async`.
+    fn cover_async_arrow(&mut self, token: Token<'a>) -> Result<Parenthesized> {
+        let open = self.peek_op()?.location;
+        let (args, cover) = self.cover(|this| this.cover_arguments())?;
+        if self.arrow_follows()? {
+            let location = span(&Some(open), &Some(args.end));
+            let params = match args.args.into_params(location) {
+                Ok(params) => params,
+                Err(cover_err) => { return Err(Error::InvalidParams(location, cover_err)); }
+            };
+            cover.into_params(&params)?;
+            self.in_async(true, |this| this.cover_params_check(&params))?;
+            return Ok(Parenthesized::Params(params));
+        }
+        cover.into_expr(&mut self.cover)?;
+        let callee = Expr::Id(self.new_id_from_token(false, token)?);
+        Ok(Parenthesized::Expr(args.append_to(callee)))
+    }
+
+    fn array_literal(&mut self, start: Token<'a>) -> Result<Expr> {
         self.allow_in(true, |this| {
             let start_location = Some(start.location);
             let mut elts = Vec::new();
@@ -1215,12 +2567,12 @@ impl<I: Iterator<Item=char>> Parser<I> {
             TokenData::Ellipsis => {
                 self.span(&mut |this| {
                     this.reread(TokenData::Ellipsis);
-                    let expr = this.assignment_expression()?;
+                    let expr = this.cover_assignment_expression()?;
                     Ok(ExprListItem::Spread(None, expr))
                 })
             }
             _ => {
-                Ok(ExprListItem::Expr(self.assignment_expression()?))
+                Ok(ExprListItem::Expr(self.cover_assignment_expression()?))
             }
         }
     }
@@ -1232,16 +2584,35 @@ impl<I: Iterator<Item=char>> Parser<I> {
         self.expr_list_item().map(Some)
     }
 
-    fn object_literal(&mut self, start: Token) -> Result<Expr> {
+    fn object_literal(&mut self, start: Token<'a>) -> Result<Expr> {
         let start_location = Some(start.location);
         let mut props = Vec::new();
+        let mut proto = false;
         loop {
             if self.peek()?.value == TokenData::RBrace {
                 break;
             }
-            props.push(self.object_property()?);
-            if !self.matches(TokenData::Comma)? {
-                break;
+            let prop = self.object_property()?;
+            if let Prop::Regular(location, ref key, PropVal::Init(_)) = prop {
+                if key.is_name("__proto__") {
+                    if proto {
+                        self.cover.defer_expr_error(Error::DuplicateProto(location));
+                    }
+                    proto = true;
+                }
+            }
+            let spread = match prop {
+                Prop::Spread(..) => true,
+                _ => false
+            };
+            props.push(prop);
+            let comma = match self.matches_token(TokenData::Comma)? {
+                Some(comma) => comma,
+                None => { break; }
+            };
+            // A rest property can't be followed by a comma.
+            if spread && self.peek()?.value == TokenData::RBrace {
+                self.cover.defer_patt_error(Error::CommaAfterRest(comma.location));
             }
         }
         let end_location = Some(self.expect(TokenData::RBrace)?.location);
@@ -1252,22 +2623,27 @@ impl<I: Iterator<Item=char>> Parser<I> {
         Ok(match self.peek()?.value {
             TokenData::Colon => {
                 self.skip()?;
-                let val = self.allow_in(true, |this| this.assignment_expression())?;
+                let val = self.allow_in(true, |this| this.cover_assignment_expression())?;
                 Prop::Regular(span(key.tracking_ref(), val.tracking_ref()), key, PropVal::Init(val))
             }
-            TokenData::LParen => {
-                let params = self.formal_parameters()?;
-                let body = self.function_body(&params.list)?;
-                Prop::Method(Fun {
-                    location: span(key.tracking_ref(), body.tracking_ref()),
-                    id: key,
-                    params: params,
-                    body: body
-                })
+            TokenData::LParen => Prop::Method(self.method(false, false, key, false)?),
+            // CoverInitializedName ::= IdentifierReference Initializer
+            TokenData::Assign => {
+                if let PropKey::Id(location, name) = key {
+                    let id = self.new_id(false, Name::from(name), location.unwrap())?;
+                    self.reread(TokenData::Assign);
+                    let default = self.allow_in(true, |this| this.assignment_expression())?;
+                    self.cover.defer_expr_error(Error::CoverInitializedName(location.unwrap()));
+                    Prop::CoverInit(span(&id, &default), id, default)
+                } else {
+                    return self.unexpected();
+                }
             }
             TokenData::Comma | TokenData::RBrace => {
                 if let PropKey::Id(location, name) = key {
-                    Prop::Shorthand(self.new_id(false, Name::from(name), location.unwrap())?)
+                    let name = Name::from(name);
+                    self.reference_check(&name, location.unwrap())?;
+                    Prop::Shorthand(self.new_id(false, name, location.unwrap())?)
                 } else {
                     return self.unexpected();
                 }
@@ -1282,8 +2658,16 @@ impl<I: Iterator<Item=char>> Parser<I> {
         Ok(Some(match token.value {
             TokenData::Identifier(name) => PropKey::Id(location, name.into_string()),
             TokenData::Reserved(word) => PropKey::Id(location, word.into_string()),
-            TokenData::String(s) => PropKey::String(location, s),
-            TokenData::Number(n) => PropKey::Number(location, n),
+            TokenData::String(s) => {
+                self.check_legacy_escape(location, &s)?;
+                PropKey::String(location, s.into_owned())
+            }
+            TokenData::Number(n) => PropKey::Number(location, n.into_owned()),
+            TokenData::LBrack => {
+                let expr = self.allow_in(true, |this| this.assignment_expression())?;
+                let end_location = Some(self.expect(TokenData::RBrack)?.location);
+                PropKey::Computed(span(&location, &end_location), expr)
+            }
             _ => {
                 self.lexer.unread_token(token);
                 return Ok(None);
@@ -1305,7 +2689,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
                 if let Some(key) = self.property_key_opt()? {
                     let paren_location = Some(self.expect(TokenData::LParen)?.location);
                     self.expect(TokenData::RParen)?;
-                    let body = self.function_body(&vec![])?;
+                    let body = self.method_body(false, false, &[], false)?;
                     let val_location = span(&paren_location, &body);
                     let prop_location = span(&key, &body);
                     return Ok(Prop::Regular(prop_location, key, PropVal::Get(val_location, body)));
@@ -1316,9 +2700,9 @@ impl<I: Iterator<Item=char>> Parser<I> {
             TokenData::Identifier(Name::Atom(Atom::Set)) => {
                 if let Some(key) = self.property_key_opt()? {
                     let paren_location = Some(self.expect(TokenData::LParen)?.location);
-                    let param = self.pattern()?;
+                    let param = self.in_parameters(false, false, |this| this.binding_element())?;
                     self.expect(TokenData::RParen)?;
-                    let body = self.function_body(&[param.clone()])?;
+                    let body = self.method_body(false, false, &[param.clone()], false)?;
                     let val_location = span(&paren_location, &body);
                     let prop_location = span(&key, &body);
                     return Ok(Prop::Regular(prop_location, key, PropVal::Set(val_location, param, body)));
@@ -1329,7 +2713,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
             TokenData::Reserved(_) => {
                 match self.peek()?.value {
                     TokenData::Comma | TokenData::RBrace => {
-                        return Err(Error::UnexpectedToken(first));
+                        return Err(Error::UnexpectedToken(first.into_owned()));
                     }
                     _ => {
                         self.lexer.unread_token(first);
@@ -1338,7 +2722,19 @@ impl<I: Iterator<Item=char>> Parser<I> {
                     }
                 }
             }
-            // ES6: TokenData::Star
+            TokenData::Identifier(Name::Atom(Atom::Async)) if self.async_method_follows()? => {
+                let generator = self.matches(TokenData::Star)?;
+                let key = self.property_key()?;
+                Ok(Prop::Method(self.method(true, generator, key, false)?))
+            }
+            TokenData::Star => {
+                let key = self.property_key()?;
+                Ok(Prop::Method(self.method(false, true, key, false)?))
+            }
+            TokenData::Ellipsis => {
+                let expr = self.allow_in(true, |this| this.assignment_expression())?;
+                Ok(Prop::Spread(span(&Some(first.location), &expr), expr))
+            }
             _ => {
                 self.lexer.unread_token(first);
                 let key = self.property_key()?;
@@ -1356,18 +2752,64 @@ impl<I: Iterator<Item=char>> Parser<I> {
             let target_location = Some(self.expect(TokenData::Identifier(Name::Atom(Atom::Target)))?.location);
             return Ok(Expr::NewTarget(span(&Some(new.location), &target_location)));
         }
+        if let Some(import) = self.matches_token(TokenData::Reserved(Reserved::Import))? {
+            return self.import_meta(import);
+        }
         self.primary_expression()
     }
 
+    // ImportMeta ::= "import" "." "meta"
+    fn import_meta(&mut self, import: Token<'a>) -> Result<Expr> {
+        self.expect(TokenData::Dot)?;
+        let meta_location = Some(self.expect(TokenData::Identifier(Name::Atom(Atom::Meta)))?.location);
+        let location = span(&Some(import.location), &meta_location);
+        match self.goal {
+            Goal::Script => { return Err(Error::ImportMetaInScript(location)); }
+            Goal::Module => { }
+            // Only modules have `import.meta`, so this settles the goal.
+            Goal::Unknown => {
+                self.force_deferred_module_validation()?;
+                self.goal = Goal::Module;
+                self.context.strict = Strict::Yes;
+            }
+        }
+        Ok(Expr::ImportMeta(location))
+    }
+
+    // ImportCall ::= "import" "(" AssignmentExpression ("," AssignmentExpression)? ","? ")"
+    fn import_call(&mut self, import: Token<'a>) -> Result<Expr> {
+        self.reread(TokenData::LParen);
+        let (specifier, options) = self.allow_in(true, |this| {
+            let specifier = this.assignment_expression()?;
+            let mut options = None;
+            if this.matches(TokenData::Comma)? && this.peek()?.value != TokenData::RParen {
+                options = Some(Box::new(this.assignment_expression()?));
+                this.matches(TokenData::Comma)?;
+            }
+            Ok((specifier, options))
+        })?;
+        let end_location = Some(self.expect(TokenData::RParen)?.location);
+        Ok(Expr::ImportCall(span(&Some(import.location), &end_location), Box::new(specifier), options))
+    }
+
     // "new"+n . (MemberBaseExpression | "super" Deref) Deref* Arguments<n Suffix*
-    fn new_expression(&mut self, news: Vec<Token>) -> Result<Expr> {
-        // ES6: if let Some(super) = self.match_token(TokenData::Reserved(Reserved::Super))? {
-        let base = self.member_base_expression()?;
+    fn new_expression(&mut self, news: Vec<Token<'a>>) -> Result<Expr> {
+        let base = if let Some(token) = self.matches_token(TokenData::Reserved(Reserved::Super))? {
+            match self.deref_opt()? {
+                Some(Deref::Private(_)) => { return Err(Error::IllegalSuper(token.into_owned())); }
+                Some(deref) if self.context.super_property => {
+                    deref.append_to(Expr::Super(Some(token.location)))
+                }
+                _ => { return Err(Error::IllegalSuper(token.into_owned())); }
+            }
+        } else {
+            self.member_base_expression()?
+        };
         self.more_new_expression(news, base)
     }
 
     // "new"+n MemberBaseExpression . Deref* Arguments<n Suffix*
-    fn more_new_expression(&mut self, news: Vec<Token>, mut base: Expr) -> Result<Expr> {
+    fn more_new_expression(&mut self, news: Vec<Token<'a>>, mut base: Expr) -> Result<Expr> {
         while let Some(deref) = self.deref_opt()? {
             base = deref.append_to(base);
         }
@@ -1375,23 +2817,53 @@ impl<I: Iterator<Item=char>> Parser<I> {
         for new in news.into_iter().rev() {
             has_args = has_args && self.peek_op()?.value == TokenData::LParen;
             base = if has_args {
-                self.arguments()?.append_to_new(new, base)
+                self.arguments()?.append_to_new(new.location, base)
             } else {
                 let location = span(&Some(new.location), &base);
                 Expr::New(location, Box::new(base), None)
             };
         }
+        if !has_args && self.peek_op()?.value == TokenData::QuestionDot {
+            return self.unexpected();
+        }
         self.more_suffixes(base)
     }
 
     // CallExpression ::=
-    //   (MemberBaseExpression | "super" Suffix) Suffix*
+    //   (MemberBaseExpression | "super" Suffix | ImportCall) Suffix*
     fn call_expression(&mut self) -> Result<Expr> {
-        // ES6: super
-        let base = self.primary_expression()?;
+        let base = if let Some(token) = self.matches_token(TokenData::Reserved(Reserved::Super))? {
+            self.super_suffix(token)?
+        } else if let Some(token) = self.matches_token(TokenData::Reserved(Reserved::Import))? {
+            if self.peek()?.value == TokenData::LParen {
+                self.import_call(token)?
+            } else {
+                self.import_meta(token)?
+            }
+        } else {
+            self.primary_expression()?
+        };
         self.more_suffixes(base)
     }
 
+    // "super" . Suffix
+    fn super_suffix(&mut self, token: Token<'a>) -> Result<Expr> {
+        let allowed = match self.peek_op()?.value {
+            TokenData::LParen => self.context.super_call,
+            TokenData::Dot
+          | TokenData::LBrack => self.context.super_property,
+            _ => false
+        };
+        if !allowed {
+            return Err(Error::IllegalSuper(token.into_owned()));
+        }
+        let suffix = self.suffix_opt()?.unwrap();
+        if let Suffix::Deref(Deref::Private(_)) = suffix {
+            return Err(Error::IllegalSuper(token.into_owned()));
+        }
+        Ok(suffix.append_to(Expr::Super(Some(token.location))))
+    }
+
     // Suffix ::=
     //   Deref
     //   Arguments
@@ -1400,35 +2872,53 @@ impl<I: Iterator<Item=char>> Parser<I> {
             TokenData::Dot    => self.deref_dot().map(|deref| Some(Suffix::Deref(deref))),
             TokenData::LBrack => self.deref_brack().map(|deref| Some(Suffix::Deref(deref))),
             TokenData::LParen => self.arguments().map(|args| Some(Suffix::Arguments(args))),
+            TokenData::NoSubstTemplate(_)
+          | TokenData::TemplateHead(_) => {
+                self.template(true).map(|tmpl| Some(Suffix::Deref(Deref::Template(tmpl))))
+            }
             _ => Ok(None)
         }
     }
 
-    // Arguments ::= "(" Argument*[","] ")"
     fn arguments(&mut self) -> Result<Arguments> {
+        let (args, cover) = self.cover(|this| this.cover_arguments())?;
+        cover.into_expr(&mut self.cover)?;
+        Ok(args)
+    }
+
+    // Arguments ::= "(" Argument*[","] ")"
+    fn cover_arguments(&mut self) -> Result<Arguments> {
         self.allow_in(true, |this| {
             this.expect(TokenData::LParen)?;
             let mut args = Vec::new();
-            if this.peek()?.value != TokenData::RParen {
-                loop {
-                    args.push(this.expr_list_item()?);
-                    if !this.matches(TokenData::Comma)? {
-                        break;
-                    }
+            while this.peek()?.value != TokenData::RParen {
+                let arg = this.expr_list_item()?;
+                let spread = arg.is_spread();
+                args.push(arg);
+                let comma = match this.matches_token(TokenData::Comma)? {
+                    Some(comma) => comma,
+                    None => { break; }
+                };
+                // A rest parameter of an async arrow can't be followed by a comma.
+                if spread && this.peek()?.value == TokenData::RParen {
+                    this.cover.defer_patt_error(Error::CommaAfterRest(comma.location));
                 }
             }
             let end = this.expect(TokenData::RParen)?;
-            Ok(Arguments { args: args, end: end })
+            Ok(Arguments { args: args, end: end.location })
         })
     }
 
     // Deref ::=
     //   "[" Expression "]"
     //   "." IdentifierName
+    //   TemplateLiteral
     fn deref_opt(&mut self) -> Result<Option<Deref>> {
         match self.peek_op()?.value {
             TokenData::LBrack => self.deref_brack().map(Some),
             TokenData::Dot    => self.deref_dot().map(Some),
+            TokenData::NoSubstTemplate(_)
+          | TokenData::TemplateHead(_) => self.template(true).map(|tmpl| Some(Deref::Template(tmpl))),
             _ => Ok(None)
         }
     }
@@ -1437,7 +2927,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
         self.reread(TokenData::LBrack);
         let expr = self.allow_in(true, |this| this.expression())?;
         let end = self.expect(TokenData::RBrack)?;
-        Ok(Deref::Brack(expr, end))
+        Ok(Deref::Brack(expr, end.location))
     }
 
     fn id_name(&mut self) -> Result<DotKey> {
@@ -1447,22 +2937,112 @@ impl<I: Iterator<Item=char>> Parser<I> {
             value: match token.value {
                 TokenData::Identifier(name) => name.into_string(),
                 TokenData::Reserved(word) => word.into_string(),
-                _ => { return Err(Error::UnexpectedToken(token)); }
+                _ => { return Err(Error::UnexpectedToken(token.into_owned())); }
             }
         })
     }
 
     fn deref_dot(&mut self) -> Result<Deref> {
         self.reread(TokenData::Dot);
+        if let TokenData::PrivateName(_) = self.peek()?.value {
+            return Ok(Deref::Private(self.private_name()?));
+        }
         Ok(Deref::Dot(self.id_name()?))
     }
 
-    // MemberBaseExpression . Suffix*
+    fn private_name(&mut self) -> Result<DotKey> {
+        let token = self.read()?;
+        self.private_reference(token)
+    }
+
+    // Records a reference to a private name, which must be declared by
+    // one of the enclosing class bodies.
+    fn private_reference(&mut self, token: Token<'a>) -> Result<DotKey> {
+        let key = match token.value {
+            TokenData::PrivateName(name) => DotKey { location: Some(token.location), value: name.into_owned() },
+            _ => { return Err(Error::UnexpectedToken(token.into_owned())); }
+        };
+        match self.classes.last_mut() {
+            Some(scope) => { scope.unresolved.push(key.clone()); }
+            None => { return Err(Error::UndeclaredPrivateName(key)); }
+        }
+        Ok(key)
+    }
+
+    // MemberBaseExpression . Suffix* OptionalChain?
     fn more_suffixes(&mut self, mut result: Expr) -> Result<Expr> {
         while let Some(suffix) = self.suffix_opt()? {
             result = suffix.append_to(result);
         }
-        Ok(result)
+        if self.peek_op()?.value != TokenData::QuestionDot {
+            return Ok(result);
+        }
+        self.optional_chain(result)
+    }
+
+    // OptionalChain ::= ("?." OptionalLink | Suffix)+
+    fn optional_chain(&mut self, mut chain: Expr) -> Result<Expr> {
+        loop {
+            match self.peek_op()?.value {
+                TokenData::QuestionDot => {
+                    self.reread(TokenData::QuestionDot);
+                    chain = self.optional_link(chain)?;
+                }
+                TokenData::NoSubstTemplate(_)
+              | TokenData::TemplateHead(_) => {
+                    return self.unexpected();
+                }
+                _ => {
+                    match self.suffix_opt()? {
+                        Some(suffix) => { chain = suffix.append_to(chain); }
+                        None => { break; }
+                    }
+                }
+            }
+        }
+        let location = *chain.tracking_ref();
+        Ok(Expr::OptionalChain(location, Box::new(chain)))
+    }
+
+    // OptionalLink ::=
+    //   Arguments
+    //   "[" Expression "]"
+    //   IdentifierName
+    //   PrivateIdentifier
+    fn optional_link(&mut self, base: Expr) -> Result<Expr> {
+        Ok(match self.peek_op()?.value {
+            TokenData::LParen => {
+                let args = self.arguments()?;
+                let location = span(&base, &Some(args.end));
+                Expr::OptionalCall(location, Box::new(base), args.args)
+            }
+            TokenData::LBrack => {
+                match self.deref_brack()? {
+                    Deref::Brack(prop, end) => {
+                        let location = span(&base, &Some(end));
+                        Expr::OptionalBrack(location, Box::new(base), Box::new(prop))
+                    }
+                    _ => unreachable!()
+                }
+            }
+            TokenData::PrivateName(_) => {
+                let key = self.private_name()?;
+                Expr::OptionalPrivateDot(span(&base, &key), Box::new(base), key)
+            }
+            _ => {
+                let key = self.id_name()?;
+                Expr::OptionalDot(span(&base, &key), Box::new(base), key)
+            }
+        })
+    }
+
+    // PrivateIdentifier . "in" ShiftExpression
+    fn private_in(&mut self, token: Token<'a>) -> Result<Expr> {
+        if !self.context.allow_in || self.peek_op()?.value != TokenData::Reserved(Reserved::In) {
+            return Err(Error::UnexpectedToken(token.into_owned()));
+        }
+        let key = self.private_reference(token)?;
+        Ok(Expr::PrivateName(key.location, key))
     }
 
     // LHSExpression ::=
@@ -1494,12 +3074,29 @@ impl<I: Iterator<Item=char>> Parser<I> {
 
     // UnaryExpression ::=
     //   Prefix* LHSExpression PostfixOperator?
-    fn unary_expression(&mut self) -> Result<Expr> {
+    //
+    // Also reports whether the outermost prefix is a unary operator or
+    // `await`, since those can't be the left operand of `**`.
+    fn unary_expression(&mut self) -> Result<(Expr, bool)> {
         let mut prefixes = Vec::new();
         while let Some(prefix) = self.match_prefix()? {
             prefixes.push(prefix);
         }
-        let mut arg = self.lhs_expression()?;
+        let unary = match prefixes.first() {
+            Some(&Prefix::Unop(_)) | Some(&Prefix::Await(_)) => true,
+            _ => false
+        };
+        let arg = self.lhs_expression()?;
+        Ok((self.more_unary(prefixes, arg)?, unary))
+    }
+
+    // Prefix* LHSExpression . PostfixOperator?
+    fn more_unary(&mut self, prefixes: Vec<Prefix>, mut arg: Expr) -> Result<Expr> {
+        if let Expr::PrivateName(location, _) = arg {
+            if !prefixes.is_empty() {
+                return Err(Error::MisplacedPrivateName(location));
+            }
+        }
         if let Some(postfix) = self.match_postfix_operator_opt()? {
             let arg_location = *arg.tracking_ref();
             arg = match arg.into_assign_target().map(Box::new) {
@@ -1515,9 +3112,16 @@ impl<I: Iterator<Item=char>> Parser<I> {
         for prefix in prefixes.into_iter().rev() {
             match prefix {
                 Prefix::Unop(op)      => {
+                    if op.tag == UnopTag::Delete && is_private_reference(&arg) {
+                        return Err(Error::DeletePrivateName(op.location));
+                    }
                     let location = span(&op, &arg);
                     arg = Expr::Unop(location, op, Box::new(arg));
                 }
+                Prefix::Await(location) => {
+                    let location = span(&Some(location), &arg);
+                    arg = Expr::Await(location, Box::new(arg));
+                }
                 _ => {
                     let arg_location = *arg.tracking_ref();
                     arg = match arg.into_assign_target().map(Box::new) {
@@ -1525,7 +3129,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
                             match prefix {
                                 Prefix::Inc(location) => Expr::PreInc(Some(location), target),
                                 Prefix::Dec(location) => Expr::PreDec(Some(location), target),
-                                Prefix::Unop(_) => unreachable!()
+                                Prefix::Unop(_) | Prefix::Await(_) => unreachable!()
                             }
                         }
                         Err(cover_err) => { return Err(Error::InvalidLHS(arg_location, cover_err)); }
@@ -1540,11 +3144,20 @@ impl<I: Iterator<Item=char>> Parser<I> {
     //   Unop
     //   "++"
     //   "--"
+    //   "await"
     fn match_prefix(&mut self) -> Result<Option<Prefix>> {
         let token = self.read()?;
         Ok(match token.value {
             TokenData::Inc => Some(Prefix::Inc(token.location)),
             TokenData::Dec => Some(Prefix::Dec(token.location)),
+            TokenData::Identifier(Name::Atom(Atom::Await)) if self.context.asynchronous => {
+                if self.context.parameters {
+                    return Err(Error::IllegalAwait(token.into_owned()));
+                }
+                let location = token.location;
+                self.cover.defer_params_error(Error::IllegalAwait(token.into_owned()));
+                Some(Prefix::Await(location))
+            }
             _ => {
                 self.lexer.unread_token(token);
                 self.match_unop()?.map(Prefix::Unop)
@@ -1594,8 +3207,8 @@ impl<I: Iterator<Item=char>> Parser<I> {
     // ConditionalExpression ::=
     //   UnaryExpression (Infix UnaryExpression)* ("?" AssignmentExpression ":" AssignmentExpression)?
     fn conditional_expression(&mut self) -> Result<Expr> {
-        let left = self.unary_expression()?;
-        let test = self.more_infix_expressions(left)?;
+        let (left, unary) = self.unary_expression()?;
+        let test = self.more_infix_expressions(left, unary)?;
         self.more_conditional(test)
     }
 
@@ -1610,14 +3223,152 @@ impl<I: Iterator<Item=char>> Parser<I> {
         Ok(left)
     }
 
-    // AssignmentExpression ::=
-    //   YieldPrefix* "yield"
-    //   YieldPrefix* ConditionalExpression (("=" | AssignmentOperator) AssignmentExpression)?
+    // Runs `parse` with no cover errors pending and returns the ones it
+    // leaves behind.
+    fn cover<T, F>(&mut self, parse: F) -> Result<(T, Cover)>
+        where F: FnOnce(&mut Self) -> Result<T>
+    {
+        let outer = replace(&mut self.cover, Cover::new());
+        let result = parse(self);
+        let inner = replace(&mut self.cover, outer);
+        Ok((result?, inner))
+    }
+
     fn assignment_expression(&mut self) -> Result<Expr> {
-        let left = self.conditional_expression()?;
+        let (expr, cover) = self.cover(Self::assignment)?;
+        cover.into_expr(&mut self.cover)?;
+        Ok(expr)
+    }
+
+    // An element or property value of a literal that may itself turn
+    // out to be a pattern, so its cover errors are left pending.
+    fn cover_assignment_expression(&mut self) -> Result<Expr> {
+        let (expr, cover) = self.cover(Self::assignment)?;
+        self.cover.merge(cover);
+        Ok(expr)
+    }
+
+    // AssignmentExpression ::=
+    //   ArrowFunction
+    //   YieldExpression
+    //   ConditionalExpression (("=" | AssignmentOperator) AssignmentExpression)?
+    fn assignment(&mut self) -> Result<Expr> {
+        let generator = self.context.generator;
+        let asynchronous = self.context.asynchronous;
+        let left = match self.peek()?.value {
+            TokenData::Identifier(Name::Atom(Atom::Yield)) if generator => {
+                return self.yield_expression();
+            }
+            // Looking for an arrow would read what follows `await` as an
+            // operator, so it goes straight to the prefix operators.
+            TokenData::Identifier(Name::Atom(Atom::Await)) if asynchronous => {
+                self.conditional_expression()?
+            }
+            TokenData::Identifier(_) => {
+                let token = self.lexer.reread_token();
+                if self.arrow_follows()? {
+                    let params = self.arrow_parameter(token)?;
+                    return self.arrow_function(*params.tracking_ref(), false, params);
+                }
+                if token.value == TokenData::Identifier(Name::Atom(Atom::Async)) && !self.peek_op()?.newline {
+                    let start = Some(token.location);
+                    match self.peek_op()?.value {
+                        TokenData::Identifier(_) => {
+                            let param = self.lexer.reread_token();
+                            if !self.arrow_follows()? {
+                                return Err(Error::UnexpectedToken(param.into_owned()));
+                            }
+                            let params = self.in_async(true, |this| this.arrow_parameter(param))?;
+                            return self.arrow_function(start, true, params);
+                        }
+                        TokenData::LParen => {
+                            match self.cover_async_arrow(token)? {
+                                Parenthesized::Params(params) => { return self.arrow_function(start, true, params); }
+                                Parenthesized::Expr(expr) => {
+                                    let left = self.more_conditional_expression(expr)?;
+                                    return self.more_assignment(left);
+                                }
+                            }
+                        }
+                        _ => { }
+                    }
+                }
+                self.lexer.unread_token(token);
+                self.conditional_expression()?
+            }
+            TokenData::LParen => {
+                match self.cover_parenthesized()? {
+                    Parenthesized::Params(params) => {
+                        return self.arrow_function(*params.tracking_ref(), false, params);
+                    }
+                    Parenthesized::Expr(expr) => self.more_conditional_expression(expr)?
+                }
+            }
+            _ => self.conditional_expression()?
+        };
+        // Pending cover errors are settled unless this expression may
+        // still become a pattern.
+        match left {
+            Expr::Obj(..) | Expr::Arr(..) => { }
+            _ => { replace(&mut self.cover, Cover::new()).into_expr(&mut self.cover)?; }
+        }
         self.more_assignment(left)
     }
 
+    fn arrow_parameter(&mut self, token: Token<'a>) -> Result<Params> {
+        let id = self.new_id_from_token(true, token)?;
+        Ok(Params {
+            location: *id.tracking_ref(),
+            list: vec![Patt::Simple(id)],
+            rest: None
+        })
+    }
+
+    // MemberBaseExpression . Suffix* PostfixOperator? (Infix UnaryExpression)* ("?" AssignmentExpression ":" AssignmentExpression)?
+    fn more_conditional_expression(&mut self, base: Expr) -> Result<Expr> {
+        let arg = self.more_suffixes(base)?;
+        let arg = self.more_unary(Vec::new(), arg)?;
+        let test = self.more_infix_expressions(arg, false)?;
+        self.more_conditional(test)
+    }
+
+    // YieldExpression ::=
+    //   "yield"
+    //   "yield" [no line terminator] "*"? AssignmentExpression
+    fn yield_expression(&mut self) -> Result<Expr> {
+        let token = self.lexer.reread_token();
+        if self.context.parameters {
+            return Err(Error::IllegalYield(token.into_owned()));
+        }
+        let start = Some(token.location);
+        // Parentheses only turn out to be arrow parameters once the arrow
+        // is found.
+        self.cover.defer_params_error(Error::IllegalYield(token.into_owned()));
+        let next = self.peek()?;
+        if next.newline {
+            return Ok(Expr::Yield(start, None));
+        }
+        match next.value {
+            TokenData::Star => {
+                self.reread(TokenData::Star);
+                let arg = self.assignment_expression()?;
+                Ok(Expr::YieldDelegate(span(&start, &arg), Box::new(arg)))
+            }
+            TokenData::RParen
+          | TokenData::RBrack
+          | TokenData::RBrace
+          | TokenData::Comma
+          | TokenData::Semi
+          | TokenData::Colon
+          | TokenData::Reserved(Reserved::In)
+          | TokenData::EOF => Ok(Expr::Yield(start, None)),
+            _ => {
+                let arg = self.assignment_expression()?;
+                Ok(Expr::Yield(span(&start, &arg), Some(Box::new(arg))))
+            }
+        }
+    }
+
     fn more_assignment(&mut self, left: Expr) -> Result<Expr> {
         let token = self.read_op()?;
         let left_location = *left.tracking_ref();
@@ -1626,6 +3377,7 @@ impl<I: Iterator<Item=char>> Parser<I> {
                 Ok(left) => left,
                 Err(cover_err) => { return Err(Error::InvalidLHS(left_location, cover_err)); }
             };
+            replace(&mut self.cover, Cover::new()).into_patt(&mut self.cover)?;
             let right = self.assignment_expression()?;
             let location = span(&left, &right);
             return Ok(Expr::Assign(location, left, Box::new(right)));
@@ -1642,13 +3394,20 @@ impl<I: Iterator<Item=char>> Parser<I> {
         Ok(left)
     }
 
-    fn more_infix_expressions(&mut self, left: Expr) -> Result<Expr> {
+    fn more_infix_expressions(&mut self, left: Expr, mut unary: bool) -> Result<Expr> {
         let mut stack = Stack::new();
         let mut operand = left;
         while let Some(op) = self.match_infix()? {
-            stack.extend(operand, op);
+            if let Infix::Binop(Op { tag: BinopTag::Exp, .. }) = op {
+                if unary {
+                    return Err(Error::UnaryBeforeExp(*operand.tracking_ref()));
+                }
+            }
+            stack.extend(operand, op)?;
             //println!("{}\n", stack);
-            operand = self.unary_expression()?;
+            let (next, next_unary) = self.unary_expression()?;
+            operand = next;
+            unary = next_unary;
         }
         Ok(stack.finish(operand))
     }
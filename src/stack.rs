@@ -3,7 +3,9 @@ use std::fmt::{Display, Formatter};
 use std::{cmp, usize};
 use joker::track::span;
 use easter::expr::Expr;
-use easter::punc::{Binop, Logop, Precedence};
+use easter::punc::{Binop, BinopTag, Logop, LogopTag, Precedence};
+use result::Result;
+use error::Error;
 
 #[derive(Debug)]
 pub enum Infix {
@@ -13,7 +15,12 @@ pub enum Infix {
 
 impl Infix {
     fn groups_left(&self, right: &Infix) -> bool {
-        self.precedence() >= right.precedence()
+        match (self, right) {
+            // `**` is the only right-associative operator.
+            (&Infix::Binop(Binop { tag: BinopTag::Exp, .. }),
+             &Infix::Binop(Binop { tag: BinopTag::Exp, .. })) => false,
+            _ => self.precedence() >= right.precedence()
+        }
     }
 }
 
@@ -116,20 +123,41 @@ impl<'a> Display for FrameExpr<'a> {
 
 #[derive(Debug)]
 pub struct Stack {
-    frames: Vec<Frame>
+    frames: Vec<Frame>,
+    // Whether `??` and `||`/`&&` have appeared, respectively. Frames get
+    // reduced as we go, so we remember them separately to catch mixing.
+    coalesce: bool,
+    logical: bool
 }
 
 impl Stack {
     pub fn new() -> Stack {
-        Stack { frames: Vec::new() }
+        Stack { frames: Vec::new(), coalesce: false, logical: false }
     }
 
-    pub fn extend(&mut self, mut left: Expr, op: Infix) {
+    pub fn extend(&mut self, mut left: Expr, op: Infix) -> Result<()> {
+        if let Infix::Logop(ref op) = op {
+            match op.tag {
+                LogopTag::Coalesce => { self.coalesce = true; }
+                LogopTag::Or | LogopTag::And => { self.logical = true; }
+            }
+            if self.coalesce && self.logical {
+                return Err(Error::MixedCoalesce(op.location));
+            }
+        }
+        if let Expr::PrivateName(location, _) = left {
+            // `#x` can only be the left operand of `in`.
+            let len = self.frames.len();
+            if len > 0 && self.frames[len - 1].op.groups_left(&op) {
+                return Err(Error::MisplacedPrivateName(location));
+            }
+        }
         let mut len;
         while { len = self.frames.len(); len > 0 } && self.frames[len - 1].op.groups_left(&op) {
             left = self.frames.pop().unwrap().fill(left);
         }
         self.frames.push(Frame { left: left, op: op });
+        Ok(())
     }
 
     pub fn finish(mut self, mut right: Expr) -> Expr {
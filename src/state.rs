@@ -2,51 +2,52 @@ use joker::token::{Token, TokenData};
 use result::Result;
 use error::Error;
 use parser::Parser;
+use joker::reader::Input;
 
-pub trait State {
+pub trait State<'a> {
     fn skip(&mut self) -> Result<()>;
-    fn read(&mut self) -> Result<Token>;
-    fn read_op(&mut self) -> Result<Token>;
-    fn peek(&mut self) -> Result<&Token>;
-    fn peek_op(&mut self) -> Result<&Token>;
-    fn expect(&mut self, expected: TokenData) -> Result<Token>;
-    fn matches_token(&mut self, expected: TokenData) -> Result<Option<Token>>;
-    fn matches(&mut self, expected: TokenData) -> Result<bool>;
-    fn matches_op(&mut self, expected: TokenData) -> Result<bool>;
-    fn reread(&mut self, expected: TokenData) -> Token;
+    fn read(&mut self) -> Result<Token<'a>>;
+    fn read_op(&mut self) -> Result<Token<'a>>;
+    fn peek(&mut self) -> Result<&Token<'a>>;
+    fn peek_op(&mut self) -> Result<&Token<'a>>;
+    fn expect(&mut self, expected: TokenData<'a>) -> Result<Token<'a>>;
+    fn matches_token(&mut self, expected: TokenData<'a>) -> Result<Option<Token<'a>>>;
+    fn matches(&mut self, expected: TokenData<'a>) -> Result<bool>;
+    fn matches_op(&mut self, expected: TokenData<'a>) -> Result<bool>;
+    fn reread(&mut self, expected: TokenData<'a>) -> Token<'a>;
     fn has_arg_same_line(&mut self) -> Result<bool>;
 }
 
-impl<I: Iterator<Item=char>> State for Parser<I> {
+impl<'a, I: Input<'a>> State<'a> for Parser<'a, I> {
     fn skip(&mut self) -> Result<()> {
         self.lexer.skip_token(false).map_err(Error::LexError)
     }
 
-    fn read(&mut self) -> Result<Token> {
+    fn read(&mut self) -> Result<Token<'a>> {
         self.lexer.read_token(false).map_err(Error::LexError)
     }
 
-    fn read_op(&mut self) -> Result<Token> {
+    fn read_op(&mut self) -> Result<Token<'a>> {
         self.lexer.read_token(true).map_err(Error::LexError)
     }
 
-    fn peek(&mut self) -> Result<&Token> {
+    fn peek(&mut self) -> Result<&Token<'a>> {
         self.lexer.peek_token(false).map_err(Error::LexError)
     }
 
-    fn peek_op(&mut self) -> Result<&Token> {
+    fn peek_op(&mut self) -> Result<&Token<'a>> {
         self.lexer.peek_token(true).map_err(Error::LexError)
     }
 
-    fn expect(&mut self, expected: TokenData) -> Result<Token> {
+    fn expect(&mut self, expected: TokenData<'a>) -> Result<Token<'a>> {
         let token = self.read()?;
         if token.value != expected {
-            return Err(Error::UnexpectedToken(token));
+            return Err(Error::UnexpectedToken(token.into_owned()));
         }
         Ok(token)
     }
 
-    fn matches_token(&mut self, expected: TokenData) -> Result<Option<Token>> {
+    fn matches_token(&mut self, expected: TokenData<'a>) -> Result<Option<Token<'a>>> {
         let token = self.read()?;
         if token.value != expected {
             self.lexer.unread_token(token);
@@ -55,7 +56,7 @@ impl<I: Iterator<Item=char>> State for Parser<I> {
         Ok(Some(token))
     }
 
-    fn matches(&mut self, expected: TokenData) -> Result<bool> {
+    fn matches(&mut self, expected: TokenData<'a>) -> Result<bool> {
         let token = self.read()?;
         if token.value != expected {
             self.lexer.unread_token(token);
@@ -64,7 +65,7 @@ impl<I: Iterator<Item=char>> State for Parser<I> {
         Ok(true)
     }
 
-    fn matches_op(&mut self, expected: TokenData) -> Result<bool> {
+    fn matches_op(&mut self, expected: TokenData<'a>) -> Result<bool> {
         let token = self.read_op()?;
         if token.value != expected {
             self.lexer.unread_token(token);
@@ -73,7 +74,7 @@ impl<I: Iterator<Item=char>> State for Parser<I> {
         Ok(true)
     }
 
-    fn reread(&mut self, expected: TokenData) -> Token {
+    fn reread(&mut self, expected: TokenData<'a>) -> Token<'a> {
         debug_assert!(self.lexer.repeek_token().value == expected);
         self.lexer.reread_token()
         // debug_assert!(self.peek().map(|actual| actual.value == expected).unwrap_or(false));
@@ -12,7 +12,7 @@ pub trait Follows {
     fn expression_continuation(&self) -> bool;
 }
 
-impl First for Token {
+impl<'a> First for Token<'a> {
     // first(LexicalBinding) =
     //   first(BindingIdentifier)
     // U first(BindingPattern)
@@ -39,7 +39,7 @@ impl First for Token {
     }
 }
 
-impl Follows for Token {
+impl<'a> Follows for Token<'a> {
     // follow(StatementList) =
     //   follow(CaseClause)
     // U follow(DefaultClause)
@@ -101,6 +101,7 @@ impl Follows for Token {
           | TokenData::Colon
           | TokenData::Arrow
           | TokenData::Number(_)
+          | TokenData::BigInt(_)
           | TokenData::String(_)
           | TokenData::RegExp(_)
           | TokenData::Identifier(_) => false,
@@ -115,7 +116,7 @@ pub trait HasLabelType {
     fn label_type(&self) -> LabelType;
 }
 
-impl HasLabelType for Token {
+impl<'a> HasLabelType for Token<'a> {
     fn label_type(&self) -> LabelType {
         match self.value {
             TokenData::Reserved(Reserved::Do)
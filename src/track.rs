@@ -2,44 +2,47 @@ use joker::track::{TrackingRef, TrackingMut, Posn, Span, span};
 use joker::token::{Token, TokenData};
 use easter::punc::Semi;
 use parser::Parser;
+use joker::reader::Input;
 use error::Error;
 use result::Result;
 use state::State;
 
 pub trait Tracking {
     fn vec_span<T: TrackingRef>(&self, v: &Vec<T>) -> Option<Span>;
-    fn posn(&self) -> Posn;
-    fn start(&self) -> SpanTracker;
+    fn prev_posn(&self) -> Posn;
+    fn start(&mut self) -> Result<SpanTracker>;
     fn span<F, T>(&mut self, parse: &mut F) -> Result<T>
       where F: FnMut(&mut Self) -> Result<T>,
             T: TrackingMut;
 }
 
-impl<I> Tracking for Parser<I> where I: Iterator<Item=char> {
+impl<'a, I> Tracking for Parser<'a, I> where I: Input<'a> {
     fn vec_span<T: TrackingRef>(&self, v: &Vec<T>) -> Option<Span> {
         let len = v.len();
         if len == 0 {
-            let here = self.posn();
+            let here = self.prev_posn();
             return Some(Span { start: here, end: here });
         }
         span(&v[0], &v[len - 1])
     }
 
-    fn posn(&self) -> Posn {
-        self.lexer.posn()
+    // Spans run from the start of their first token to the end of their
+    // last, so they never take in the whitespace and comments around them.
+    fn prev_posn(&self) -> Posn {
+        self.lexer.prev_posn()
     }
 
-    fn start(&self) -> SpanTracker {
-        SpanTracker::new(self.posn())
+    fn start(&mut self) -> Result<SpanTracker> {
+        Ok(SpanTracker::new(self.lexer.next_posn().map_err(Error::LexError)?))
     }
 
     fn span<F, T>(&mut self, parse: &mut F) -> Result<T>
       where F: FnMut(&mut Self) -> Result<T>,
             T: TrackingMut
     {
-        let start = self.posn();
+        let start = self.lexer.next_posn().map_err(Error::LexError)?;
         let mut value = parse(self)?;
-        let end = self.posn();
+        let end = self.prev_posn();
         *value.tracking_mut() = Some(Span { start: start, end: end });
         Ok(value)
     }
@@ -58,7 +61,7 @@ pub struct SpanTracker {
 impl SpanTracker {
 /*
     pub fn end<I, T>(&self, parser: &Parser<I>, value: T) -> Tracked<T>
-      where I: Iterator<Item=char>
+      where I: Input
     {
         Tracked { value: value, location: Some(Span { start: self.start, end: parser.posn() }) }
     }
@@ -68,18 +71,18 @@ impl SpanTracker {
         SpanTracker { start: start }
     }
 
-    pub fn end_with_auto_semi<I, T, F>(&self, parser: &mut Parser<I>, newline: Newline, cons: F)
+    pub fn end_with_auto_semi<'a, I, T, F>(&self, parser: &mut Parser<'a, I>, newline: Newline, cons: F)
         -> Result<T>
-      where I: Iterator<Item=char>,
+      where I: Input<'a>,
             F: FnOnce(Semi) -> T,
             T: TrackingMut
     {
-        let before = parser.posn();
+        let before = parser.prev_posn();
         match parser.peek()? {
             &Token { value: TokenData::Semi, location, .. } => {
                 parser.reread(TokenData::Semi);
                 let mut result = cons(Semi::Explicit(Some(location.start)));
-                *result.tracking_mut() = Some(Span { start: self.start, end: parser.posn() });
+                *result.tracking_mut() = Some(Span { start: self.start, end: parser.prev_posn() });
                 Ok(result)
             }
             &Token { value: TokenData::RBrace, .. }
@@ -91,7 +94,7 @@ impl SpanTracker {
             &Token { newline: found_newline, .. } => {
                 if newline == Newline::Required && !found_newline {
                     let token = parser.read()?;
-                    return Err(Error::FailedASI(token));
+                    return Err(Error::FailedASI(token.into_owned()));
                 }
                 let mut result = cons(Semi::Inserted);
                 *result.tracking_mut() = Some(Span { start: self.start, end: before });
@@ -0,0 +1,74 @@
+extern crate easter;
+extern crate esprit;
+extern crate joker;
+
+use easter::stmt::{Stmt, StmtListItem};
+use esprit::Parser;
+use esprit::comments::{attach_script, Attachments};
+use joker::token::{Comment, CommentKind};
+
+fn parse(source: &str) -> (Vec<StmtListItem>, Vec<Comment>, Attachments) {
+    let mut parser = Parser::from_chars(source.chars());
+    parser.collect_comments();
+    let script = parser.script(false).unwrap();
+    let comments = parser.take_comments();
+    let attachments = attach_script(&script, comments.clone());
+    (script.items, comments, attachments)
+}
+
+fn texts(comments: &[Comment]) -> Vec<&str> {
+    comments.iter().map(|comment| &comment.text[..]).collect()
+}
+
+#[test]
+pub fn collects_comments() {
+    let (_, comments, _) = parse("/* a\n */ x; // b\ny /**/");
+    let kinds: Vec<CommentKind> = comments.iter().map(|comment| comment.kind).collect();
+    assert_eq!(vec![CommentKind::Block, CommentKind::Line, CommentKind::Block], kinds);
+    assert_eq!(vec![" a\n ", " b", ""], texts(&comments));
+    assert_eq!("2:8..2:12", format!("{:?}", comments[1].location));
+}
+
+#[test]
+pub fn leaves_comments_out_by_default() {
+    let mut parser = Parser::from_chars("// a\nx".chars());
+    parser.script(false).unwrap();
+    assert!(parser.take_comments().is_empty());
+}
+
+#[test]
+pub fn attaches_comments() {
+    let (items, _, attachments) = parse("// license\n\n/** doc */\nfunction f() {\n  // empty\n}\nx; // eslint-disable-line\nif (y) { z() /* after z */ }");
+
+    let f = attachments.get(&items[0]).unwrap();
+    assert_eq!(vec![" license", "* doc "], texts(&f.leading));
+    assert!(f.trailing.is_empty());
+
+    let x = attachments.get(&items[1]).unwrap();
+    assert_eq!(vec![" eslint-disable-line"], texts(&x.trailing));
+
+    let z = match items[2] {
+        StmtListItem::Stmt(Stmt::If(_, _, ref cons, _)) => {
+            match **cons {
+                Stmt::Block(ref block) => block.items[0].clone(),
+                _ => panic!("expected block")
+            }
+        }
+        _ => panic!("expected if statement")
+    };
+    assert_eq!(vec![" after z "], texts(&attachments.get(&z).unwrap().trailing));
+    assert!(attachments.dangling.is_empty());
+}
+
+#[test]
+pub fn attaches_inner_and_dangling_comments() {
+    let (items, _, attachments) = parse("if (x) {\n  // nothing yet\n}");
+    let block = match items[0] {
+        StmtListItem::Stmt(Stmt::If(_, _, ref cons, _)) => (**cons).clone(),
+        _ => panic!("expected if statement")
+    };
+    assert_eq!(vec![" nothing yet"], texts(&attachments.get(&block).unwrap().inner));
+
+    let (_, _, attachments) = parse("/* nothing at all */");
+    assert_eq!(vec![" nothing at all "], texts(&attachments.dangling));
+}
@@ -0,0 +1,91 @@
+extern crate esprit;
+
+use esprit::{script, module};
+
+#[test]
+pub fn yield_in_arrow_params() {
+    assert!(script("function* g() { (a = yield) => 1 }").is_err());
+    assert!(script("function* g() { (a = [yield]) => 1 }").is_err());
+    assert!(script("function* g() { (a = yield) }").is_ok());
+    assert!(script("function* g() { [a = yield] = x }").is_ok());
+    assert!(script("function* g() { (a = function*() { yield }) => 1 }").is_ok());
+}
+
+#[test]
+pub fn yield_before_regexp() {
+    assert!(script("function* g() { yield /1/g }").is_ok());
+    assert!(script("function* g() { yield\n/1/g }").is_ok());
+    assert!(script("function* g() { x = yield /1/g }").is_ok());
+    assert!(script("function g() { yield /1/g }").is_ok());
+}
+
+#[test]
+pub fn await_in_arrow_params() {
+    assert!(script("async function f() { (a = await 1) => 1 }").is_err());
+    assert!(script("async function f() { async (a = await 1) => 1 }").is_err());
+    assert!(script("async function f() { async (a = [await 1]) => 1 }").is_err());
+    assert!(script("async function f() { (a = await 1) }").is_ok());
+    assert!(script("async function f() { async (a = await 1) }").is_ok());
+    assert!(script("async function f() { (a = async () => await 1) => 1 }").is_ok());
+    assert!(script("async function f() { (a = async function() { await 1 }) => 1 }").is_ok());
+}
+
+#[test]
+pub fn class_initializers() {
+    assert!(script("x = class { static { await; } }").is_err());
+    assert!(script("x = class { static { let await; } }").is_err());
+    assert!(script("x = class { static { function await() {} } }").is_err());
+    assert!(script("x = class { static { function f() { await; } } }").is_ok());
+    assert!(script("class A { x = await; }").is_ok());
+    assert!(script("class A { x = arguments; }").is_err());
+    assert!(script("class A { x = () => arguments; }").is_err());
+    assert!(script("class A { x = { arguments }; }").is_err());
+    assert!(script("class A { static { arguments; } }").is_err());
+    assert!(script("class A { x = function() { arguments }; }").is_ok());
+    assert!(script("class A { m() { arguments } }").is_ok());
+}
+
+#[test]
+pub fn await_before_regexp() {
+    assert!(script("async function f() { await /1/g }").is_ok());
+    assert!(script("async function f() { x = await /1/g }").is_ok());
+    assert!(script("function f() { await /1/g }").is_ok());
+}
+
+#[test]
+pub fn for_await_of() {
+    assert!(script("async function f() { for await (x of y) {} }").is_ok());
+    assert!(script("async function f() { for await (const x of y) {} }").is_ok());
+    assert!(script("async function* f() { for await (var [a, b] of y) {} }").is_ok());
+    assert!(module("for await (x of y) {}").is_ok());
+    assert!(script("async function f() { for await (x in y) {} }").is_err());
+    assert!(script("async function f() { for await (;;) {} }").is_err());
+    assert!(script("function f() { for await (x of y) {} }").is_err());
+}
+
+#[test]
+pub fn parenthesized_arrow_params() {
+    assert!(script("((a)) => 1").is_err());
+    assert!(script("(([a])) => 1").is_err());
+    assert!(script("(a, (b)) => 1").is_err());
+    assert!(script("([(a)]) => 1").is_err());
+    assert!(script("({ x: (a) }) => 1").is_err());
+    assert!(script("((a) = 1) => 1").is_err());
+    assert!(script("((a = 1)) => 1").is_err());
+    assert!(script("(...(a)) => 1").is_err());
+    assert!(script("async ((a)) => 1").is_err());
+    assert!(script("(a = (b)) => 1").is_ok());
+    assert!(script("(a = [(b)]) => 1").is_ok());
+    assert!(script("[(a)] = x").is_ok());
+    assert!(script("((a)) = 1").is_ok());
+}
+
+#[test]
+pub fn duplicate_arrow_params() {
+    assert!(script("(a, a) => 1").is_err());
+    assert!(script("(a, [a]) => 1").is_err());
+    assert!(script("(a, ...a) => 1").is_err());
+    assert!(script("async (a, a) => 1").is_err());
+    assert!(script("(a, b) => 1").is_ok());
+    assert!(script("function f(a, a) {}").is_ok());
+}
@@ -17,11 +17,12 @@ extern crate unjson;
 use easter::expr::Expr;
 use easter::patt::{AssignTarget, Patt};
 use easter::stmt::{Stmt, StmtListItem};
-use esprit::script;
-use estree::IntoScript;
+use esprit::{script, web_script, module};
+use estree::{IntoScript, IntoModule};
 use glob::glob;
 use joker::track::Untrack;
 use serde_json::value::Value;
+use std::fmt::Debug;
 use std::fs::{File, read_dir};
 use std::io::Read;
 use std::path::Path;
@@ -84,49 +85,80 @@ fn unit_tests(target: &mut Vec<TestDescAndFn>) {
                 tree_path.with_file_name(source_file_name)
             };
             if source_path.exists() {
-                let ignore = {
-                    let local_test_path = source_path.strip_prefix(&fixtures).unwrap().with_extension("");
-                    testignore.iter().any(|ignore| ignore.matches_path(&local_test_path))
-                };
-                Some((tree_path, source_path, ignore))
+                let local_test_path = source_path.strip_prefix(&fixtures).unwrap().with_extension("");
+                let ignore = testignore.iter().any(|ignore| ignore.matches_path(&local_test_path));
+                let module_fixture = module_path(&local_test_path);
+                let web_fixture = web_path(&local_test_path);
+                Some((tree_path, source_path, ignore, module_fixture, web_fixture))
             } else {
                 None
             }
         });
 
-    for (tree_path, source_path, ignore) in files {
+    for (tree_path, source_path, ignore, module_fixture, web_fixture) in files {
         add_test(target, source_path.strip_prefix(&root).unwrap().to_str().unwrap().to_string(), ignore, move || {
             let v: Value = serde_json::de::from_reader(File::open(tree_path).unwrap()).unwrap();
             let mut obj = v.into_object().unwrap();
-            let expected = match obj.extract_array("errors") {
-                Ok(errors) => {
-                    Err(errors[0].as_object().unwrap()["message"].clone())
-                }
-                Err(unjson::error::Error::MissingField(_)) => {
-                    Ok(obj.into_script().map_err(|err| {
-                        format!("failed to deserialize script: {}", err)
-                    }).unwrap())
-                }
-                Err(err) => panic!(err)
+            let is_module = match obj.get("sourceType") {
+                Some(source_type) => source_type.as_str() == Some("module"),
+                None => module_fixture
             };
             let mut source = String::new();
             File::open(source_path).unwrap().read_to_string(&mut source).unwrap();
-            match (script(&source[..]), expected) {
-                (Ok(mut actual_ast), expected) => {
-                    actual_ast.untrack();
-                    assert!(Ok(&actual_ast) == expected.as_ref(), "unit test got wrong result\n\
-                    expected: {:#?}\n\
-                    actual AST: {:#?}", expected, actual_ast);
-                }
-                (Err(actual_err), Ok(_)) => {
-                    panic!("unit test failed to parse:\n{:#?}", actual_err);
-                }
-                (Err(_), Err(_)) => {}
+            let errors = match obj.extract_array("errors") {
+                Ok(errors) => Some(errors[0].as_object().unwrap()["message"].clone()),
+                Err(unjson::error::Error::MissingField(_)) => None,
+                Err(err) => panic!(err)
+            };
+            if is_module {
+                let expected = match errors {
+                    Some(message) => Err(message),
+                    None => Ok(obj.into_module().map_err(|err| {
+                        format!("failed to deserialize module: {}", err)
+                    }).unwrap())
+                };
+                check_result(module(&source[..]), expected);
+            } else {
+                let expected = match errors {
+                    Some(message) => Err(message),
+                    None => Ok(obj.into_script().map_err(|err| {
+                        format!("failed to deserialize script: {}", err)
+                    }).unwrap())
+                };
+                let actual = if web_fixture { web_script(&source[..]) } else { script(&source[..]) };
+                check_result(actual, expected);
             }
         });
     }
 }
 
+// Expected failures carry no tree to record their source type, so
+// fall back to the fixture naming conventions.
+fn module_path(path: &Path) -> bool {
+    let path = path.to_str().unwrap();
+    path.contains("import-declaration") || path.contains("export-declaration") || path.contains("module")
+}
+
+// The fixtures that need the Annex B syntax of `web_script`.
+fn web_path(path: &Path) -> bool {
+    path == Path::new("statement/if/migrated_0003") || path == Path::new("statement/labelled/function-declaration")
+}
+
+fn check_result<T: Untrack + PartialEq + Debug>(actual: esprit::result::Result<T>, expected: Result<T, Value>) {
+    match (actual, expected) {
+        (Ok(mut actual_ast), expected) => {
+            actual_ast.untrack();
+            assert!(Ok(&actual_ast) == expected.as_ref(), "unit test got wrong result\n\
+            expected: {:#?}\n\
+            actual AST: {:#?}", expected, actual_ast);
+        }
+        (Err(actual_err), Ok(_)) => {
+            panic!("unit test failed to parse:\n{:#?}", actual_err);
+        }
+        (Err(_), Err(_)) => {}
+    }
+}
+
 fn main() {
     let args: Vec<_> = env::args().collect();
     let mut tests = Vec::new();
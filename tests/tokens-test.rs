@@ -0,0 +1,60 @@
+extern crate esprit;
+extern crate joker;
+
+use esprit::Parser;
+use joker::token::{SourceToken, TokenData, TriviaKind, CommentKind};
+
+fn tokens(source: &str, module: bool) -> Vec<SourceToken> {
+    let mut parser = Parser::from_chars(source.chars());
+    parser.collect_tokens();
+    if module {
+        parser.module().unwrap();
+    } else {
+        parser.script(false).unwrap();
+    }
+    parser.take_tokens()
+}
+
+fn print(tokens: &[SourceToken]) -> String {
+    tokens.iter().map(|token| token.to_string()).collect()
+}
+
+#[test]
+pub fn round_trips() {
+    let sources = [
+        "",
+        "  \n",
+        "#!/usr/bin/env node\r\n'use strict';\r\n",
+        "a = b\n/c/g.test(d) / e\t// f\u{2028}g",
+        "x = `a${ {b: `c${d}e`}.b }f${g}`;;",
+        "/* a\r\n */ if (x) /* b */ { y() }\n\n\n",
+        "<!-- a\nb\n--> c\n\u{feff}d",
+        "class A { #x = 1n; static { this.#x?.y } }",
+    ];
+    for source in sources.iter() {
+        assert_eq!(*source, print(&tokens(source, false)));
+    }
+    let source = "import a from 'b' with { type: 'json' };\nexport default /* c */ a;\n";
+    assert_eq!(source, print(&tokens(source, true)));
+}
+
+#[test]
+pub fn attaches_trivia_to_following_token() {
+    let tokens = tokens("a /* b */\r\n// c\n;", false);
+    assert_eq!(3, tokens.len());
+    assert_eq!("a", tokens[0].source);
+    assert!(tokens[0].leading.is_empty());
+
+    let kinds: Vec<TriviaKind> = tokens[1].leading.iter().map(|trivia| trivia.kind).collect();
+    assert_eq!(vec![TriviaKind::Whitespace,
+                    TriviaKind::Comment(CommentKind::Block),
+                    TriviaKind::Newlines,
+                    TriviaKind::Comment(CommentKind::Line),
+                    TriviaKind::Newlines], kinds);
+    assert_eq!("\r\n", tokens[1].leading[2].source);
+    assert_eq!(TokenData::Semi, tokens[1].token.value);
+    assert!(tokens[1].token.newline);
+
+    assert_eq!(TokenData::EOF, tokens[2].token.value);
+    assert_eq!("", tokens[2].source);
+}
@@ -0,0 +1,52 @@
+extern crate esprit;
+
+use esprit::{script, strict, web_script, module};
+
+#[test]
+pub fn legacy_escapes_are_sloppy_only() {
+    assert!(script("'\\07'; '\\8'").is_ok());
+    assert!(web_script("'\\07'").is_ok());
+    assert!(strict("'\\07'").is_err());
+    assert!(strict("'\\9'").is_err());
+    assert!(module("'\\07'").is_err());
+    assert!(strict("'\\0'").is_ok());
+}
+
+#[test]
+pub fn html_comments_are_script_only() {
+    assert!(script("<!-- x\ny\n--> z").is_ok());
+    assert!(web_script("<!-- x").is_ok());
+    assert!(module("<!-- x").is_err());
+}
+
+#[test]
+pub fn function_statements_need_web_compat() {
+    assert!(web_script("if (x) function f() {}").is_ok());
+    assert!(web_script("l: function f() {}").is_ok());
+    assert!(web_script("if (x) l: function f() {}").is_err());
+    assert!(web_script("if (x) ; else l: m: function f() {}").is_err());
+    assert!(script("if (x) function f() {}").is_err());
+    assert!(strict("if (x) function f() {}").is_err());
+}
+
+#[test]
+pub fn use_strict_directive_turns_on_strict_mode() {
+    assert!(script("with (x) {}").is_ok());
+    assert!(script("'use strict'; with (x) {}").is_err());
+    assert!(script("\"use strict\"; '\\07'").is_err());
+    assert!(script("'\\07'; 'use strict'").is_err());
+    assert!(script("function f() { 'use strict'; with (x) {} }").is_err());
+    assert!(script("function f() { 'use strict' } with (x) {}").is_ok());
+}
+
+#[test]
+pub fn legacy_regexp_patterns_need_no_web_compat() {
+    let patterns = ["/{/", "/]/", "/a{,5}/", "/\\a/", "/\\x/", "/\\c/", "/\\01/", "/[\\1]/", "/[\\w-.]/", "/(?=a)*/"];
+    for pattern in patterns.iter() {
+        assert!(script(pattern).is_ok(), "{}", pattern);
+        assert!(strict(pattern).is_ok(), "{}", pattern);
+        assert!(module(pattern).is_ok(), "{}", pattern);
+    }
+    assert!(script("/{/u").is_err());
+    assert!(module("/\\a/u").is_err());
+}